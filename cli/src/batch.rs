@@ -0,0 +1,350 @@
+use crate::GenerateOptions;
+use indicatif::{ProgressBar, ProgressStyle};
+use lib::{AIBOMGenerator, OutputFormat};
+use serde::Serialize;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(clap::Args)]
+pub struct BatchArgs {
+    /// File listing one `MODEL_ID [REVISION]` per line; `-` or none reads stdin
+    #[arg(value_name = "FILE")]
+    input: Option<PathBuf>,
+
+    /// Directory for the generated AIBOMs, `index.json` and `failures.json`
+    #[arg(short, long, value_name = "DIR", default_value = "aiboms")]
+    output_dir: PathBuf,
+
+    /// Number of models generated concurrently
+    #[arg(short, long, value_name = "COUNT", default_value_t = 4)]
+    jobs: usize,
+
+    #[command(flatten)]
+    generate: GenerateOptions,
+}
+
+/// A model to generate an AIBOM for
+pub(crate) struct ModelRequest {
+    pub model_id: String,
+    pub revision: Option<String>,
+}
+
+/// Outcome of one model in a batch, as listed in `index.json`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BatchEntry {
+    pub model_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    /// AIBOM file, relative to the output directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<usize>,
+    /// Lineage nodes that failed or were skipped without failing the model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unresolved_dependencies: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BatchEntry {
+    pub fn succeeded(&self) -> bool {
+        self.error_kind.is_none()
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchIndex<'a> {
+    generated_at: String,
+    total: usize,
+    succeeded: usize,
+    failed: usize,
+    models: &'a [BatchEntry],
+}
+
+/// Generate every listed model; returns whether all succeeded
+pub fn run(args: &BatchArgs) -> Result<bool, Box<dyn std::error::Error>> {
+    let mut text = String::new();
+    match &args.input {
+        Some(path) if path != Path::new("-") => text = std::fs::read_to_string(path)?,
+        _ => {
+            std::io::stdin().read_to_string(&mut text)?;
+        }
+    }
+    let requests = parse_model_list(&text)?;
+
+    let generator = args.generate.builder()?.build()?;
+    let entries = generate_all(&generator, &requests, &args.output_dir, args.jobs)?;
+    write_index(&args.output_dir, &entries)
+}
+
+/// Parse `MODEL_ID [REVISION]` lines, skipping blank lines and `#` comments
+pub(crate) fn parse_model_list(text: &str) -> Result<Vec<ModelRequest>, String> {
+    let mut requests = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default();
+        let mut fields = line.split_whitespace();
+        let Some(model_id) = fields.next() else {
+            continue;
+        };
+        let revision = fields.next().map(str::to_string);
+        if fields.next().is_some() {
+            return Err(format!(
+                "line {}: expected MODEL_ID [REVISION], got '{}'",
+                number + 1,
+                line.trim()
+            ));
+        }
+        requests.push(ModelRequest {
+            model_id: model_id.to_string(),
+            revision,
+        });
+    }
+    if requests.is_empty() {
+        return Err("no model ids given".to_string());
+    }
+    Ok(requests)
+}
+
+/// Generate and write an AIBOM per request on `jobs` threads sharing
+/// `generator` (and so its Hub response cache). Failures are recorded in the
+/// returned entries, which keep the order of `requests`.
+pub(crate) fn generate_all(
+    generator: &AIBOMGenerator,
+    requests: &[ModelRequest],
+    output_dir: &Path,
+    jobs: usize,
+) -> Result<Vec<BatchEntry>, Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(output_dir)?;
+    run_parallel(
+        requests,
+        jobs,
+        |request| request.model_id.clone(),
+        |request| {
+            let entry = generate_one(generator, request, output_dir);
+            (entry.error.clone(), entry)
+        },
+    )
+}
+
+/// Apply `work` to every item on `jobs` threads behind a progress bar.
+/// `work` returns the item's result and an error message to print, if any;
+/// results keep the order of `items`.
+pub(crate) fn run_parallel<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    label: impl Fn(&T) -> String + Sync,
+    work: impl Fn(&T) -> (Option<String>, R) + Sync,
+) -> Result<Vec<R>, Box<dyn std::error::Error>> {
+    let progress = ProgressBar::new(items.len() as u64);
+    progress.set_style(ProgressStyle::with_template(
+        "{bar:30} {pos}/{len} {wide_msg}",
+    )?);
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(items.len()));
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.max(1).min(items.len()) {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    let label = label(item);
+                    progress.set_message(label.clone());
+                    let (error, result) = work(item);
+                    if let Some(error) = error {
+                        progress.suspend(|| eprintln!("Error: {}: {}", label, error));
+                    }
+                    progress.inc(1);
+                    results.lock().unwrap().push((index, result));
+                }
+            });
+        }
+    });
+    progress.finish_and_clear();
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    Ok(results.into_iter().map(|(_, result)| result).collect())
+}
+
+fn generate_one(
+    generator: &AIBOMGenerator,
+    request: &ModelRequest,
+    output_dir: &Path,
+) -> BatchEntry {
+    let mut entry = BatchEntry {
+        model_id: request.model_id.clone(),
+        revision: request.revision.clone(),
+        file: None,
+        components: None,
+        unresolved_dependencies: None,
+        error_kind: None,
+        error: None,
+    };
+
+    let result = generator
+        .generate_aibom_at_revision(&request.model_id, request.revision.as_deref())
+        .and_then(|(aibom, report)| Ok((generator.render(&aibom)?, aibom, report)));
+    let (content, aibom, report) = match result {
+        Ok(generated) => generated,
+        Err(e) => {
+            entry.error_kind = Some(e.kind().to_string());
+            entry.error = Some(e.message().to_string());
+            return entry;
+        }
+    };
+
+    let file = file_name(request, generator);
+    if let Err(e) = std::fs::write(output_dir.join(&file), content) {
+        entry.error_kind = Some("io".to_string());
+        entry.error = Some(e.to_string());
+        return entry;
+    }
+    entry.file = Some(file);
+    entry.components = Some(aibom.components.len());
+    entry.unresolved_dependencies = Some(report.failures.len() + report.skipped.len());
+    entry
+}
+
+/// `org__model[@revision].json`, safe as a single path component
+fn file_name(request: &ModelRequest, generator: &AIBOMGenerator) -> String {
+    let mut name = request.model_id.replace('/', "__");
+    if let Some(revision) = &request.revision {
+        name = format!("{}@{}", name, revision.replace('/', "_"));
+    }
+    format!("{}.{}", name, extension(generator))
+}
+
+/// File extension of the generator's output format
+pub(crate) fn extension(generator: &AIBOMGenerator) -> &'static str {
+    match generator.config().output.format {
+        OutputFormat::CyclonedxXml => "xml",
+        _ => "json",
+    }
+}
+
+/// Write `index.json` and `failures.json`; returns whether every model succeeded
+pub(crate) fn write_index(
+    output_dir: &Path,
+    entries: &[BatchEntry],
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let failures: Vec<&BatchEntry> = entries.iter().filter(|e| !e.succeeded()).collect();
+    let index = BatchIndex {
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        total: entries.len(),
+        succeeded: entries.len() - failures.len(),
+        failed: failures.len(),
+        models: entries,
+    };
+    std::fs::write(
+        output_dir.join("index.json"),
+        serde_json::to_string_pretty(&index)?,
+    )?;
+    std::fs::write(
+        output_dir.join("failures.json"),
+        serde_json::to_string_pretty(&failures)?,
+    )?;
+
+    println!(
+        "Generated {} of {} AIBOMs into {}",
+        index.succeeded,
+        index.total,
+        output_dir.display()
+    );
+    if !failures.is_empty() {
+        println!(
+            "{} failed, see {}",
+            failures.len(),
+            output_dir.join("failures.json").display()
+        );
+    }
+    Ok(failures.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(model_id: &str, error_kind: Option<&str>) -> BatchEntry {
+        BatchEntry {
+            model_id: model_id.to_string(),
+            revision: None,
+            file: error_kind.is_none().then(|| format!("{}.json", model_id)),
+            components: None,
+            unresolved_dependencies: None,
+            error_kind: error_kind.map(str::to_string),
+            error: error_kind.map(|kind| format!("{} error", kind)),
+        }
+    }
+
+    #[test]
+    fn parses_model_ids_with_optional_revisions() {
+        let requests = parse_model_list(
+            "# models to scan\norg/a\n\n  org/b   v1.0  # pinned\norg/c refs/pr/3\n",
+        )
+        .unwrap();
+        let parsed: Vec<_> = requests
+            .iter()
+            .map(|r| (r.model_id.as_str(), r.revision.as_deref()))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                ("org/a", None),
+                ("org/b", Some("v1.0")),
+                ("org/c", Some("refs/pr/3")),
+            ]
+        );
+
+        assert_eq!(
+            parse_model_list("org/a\norg/b main extra\n")
+                .err()
+                .as_deref(),
+            Some("line 2: expected MODEL_ID [REVISION], got 'org/b main extra'")
+        );
+        assert_eq!(
+            parse_model_list("# nothing\n\n").err().as_deref(),
+            Some("no model ids given")
+        );
+    }
+
+    #[test]
+    fn reports_failures_separately_from_the_index() {
+        let dir = std::env::temp_dir().join(format!("aibom-batch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut pinned = entry("org/b", Some("not-found"));
+        pinned.revision = Some("v2".to_string());
+        let entries = [entry("org/a", None), pinned];
+
+        assert!(!write_index(&dir, &entries).unwrap());
+        let read = |name: &str| -> serde_json::Value {
+            serde_json::from_str(&std::fs::read_to_string(dir.join(name)).unwrap()).unwrap()
+        };
+        let index = read("index.json");
+        assert_eq!(index["total"], 2);
+        assert_eq!(index["succeeded"], 1);
+        assert_eq!(index["failed"], 1);
+        assert_eq!(index["models"][0]["file"], "org/a.json");
+        assert_eq!(
+            read("failures.json"),
+            serde_json::json!([{
+                "modelId": "org/b",
+                "revision": "v2",
+                "errorKind": "not-found",
+                "error": "not-found error"
+            }])
+        );
+
+        assert!(write_index(&dir, &entries[..1]).unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,53 @@
+use crate::read_aibom;
+use clap::ValueEnum;
+use lib::{ChangeKind, diff};
+use std::path::PathBuf;
+
+#[derive(clap::Args)]
+pub struct DiffArgs {
+    /// Earlier AIBOM (CycloneDX JSON)
+    old: PathBuf,
+
+    /// Later AIBOM (CycloneDX JSON)
+    new: PathBuf,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "text")]
+    format: DiffFormat,
+
+    /// Only exit non-zero for these kinds of change (repeatable; default: any)
+    #[arg(long, value_name = "KIND", value_parser = parse_kind)]
+    fail_on: Vec<ChangeKind>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DiffFormat {
+    Text,
+    Json,
+    Markdown,
+}
+
+fn parse_kind(value: &str) -> Result<ChangeKind, String> {
+    ChangeKind::parse(value).ok_or_else(|| {
+        let kinds: Vec<&str> = ChangeKind::ALL.iter().map(|k| k.as_str()).collect();
+        format!("expected one of {}, got '{}'", kinds.join(", "), value)
+    })
+}
+
+/// Print the differences; returns whether none of the `--fail-on` kinds occurred
+pub fn run(args: &DiffArgs) -> Result<bool, Box<dyn std::error::Error>> {
+    let changes = diff(&read_aibom(&args.old)?, &read_aibom(&args.new)?);
+
+    match args.format {
+        DiffFormat::Text => print!("{}", changes.to_text()),
+        DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&changes)?),
+        DiffFormat::Markdown => print!("{}", changes.to_markdown()),
+    }
+
+    let gating = if args.fail_on.is_empty() {
+        &ChangeKind::ALL[..]
+    } else {
+        &args.fail_on[..]
+    };
+    Ok(!gating.iter().any(|kind| changes.has(*kind)))
+}
@@ -0,0 +1,110 @@
+use crate::read_aibom;
+use lib::{LintReport, Linter, Severity, lint_rules};
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[derive(clap::Args)]
+pub struct LintArgs {
+    /// AIBOM files (CycloneDX JSON) to lint
+    #[arg(value_name = "FILE", required_unless_present = "list_rules")]
+    files: Vec<PathBuf>,
+
+    /// Turn a rule off (repeatable)
+    #[arg(long, value_name = "RULE")]
+    disable: Vec<String>,
+
+    /// Override a rule's severity, e.g. missing-hash=warning (repeatable)
+    #[arg(long, value_name = "RULE=SEVERITY", value_parser = parse_override)]
+    severity: Vec<(String, Severity)>,
+
+    /// Exit non-zero when a finding reaches this severity
+    #[arg(long, value_name = "SEVERITY", default_value = "error", value_parser = parse_severity)]
+    fail_on: Severity,
+
+    /// Print results as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// List the available rules and exit
+    #[arg(long)]
+    list_rules: bool,
+}
+
+fn parse_severity(value: &str) -> Result<Severity, String> {
+    Severity::parse(value)
+        .ok_or_else(|| format!("expected info, warning or error, got '{}'", value))
+}
+
+fn parse_override(value: &str) -> Result<(String, Severity), String> {
+    let (rule, severity) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected RULE=SEVERITY, got '{}'", value))?;
+    Ok((rule.to_string(), parse_severity(severity)?))
+}
+
+#[derive(Serialize)]
+struct FileResult {
+    file: PathBuf,
+    #[serde(flatten)]
+    report: LintReport,
+}
+
+/// Lint every file, printing the findings; returns whether none reached `--fail-on`
+pub fn run(args: &LintArgs) -> Result<bool, Box<dyn std::error::Error>> {
+    if args.list_rules {
+        for rule in lint_rules() {
+            println!(
+                "{:<32} {:<8} {}",
+                rule.name,
+                rule.default_severity.as_str(),
+                rule.description
+            );
+        }
+        return Ok(true);
+    }
+
+    let mut linter = Linter::new();
+    for rule in &args.disable {
+        linter.disable(rule)?;
+    }
+    for (rule, severity) in &args.severity {
+        linter.set_severity(rule, Some(*severity))?;
+    }
+
+    let mut results = Vec::new();
+    for file in &args.files {
+        let aibom = read_aibom(file)?;
+        results.push(FileResult {
+            file: file.clone(),
+            report: linter.lint(&aibom),
+        });
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        for result in &results {
+            for finding in &result.report.findings {
+                println!(
+                    "{}: {} [{}] {}: {}",
+                    result.file.display(),
+                    finding.severity.as_str(),
+                    finding.rule,
+                    finding.pointer,
+                    finding.message
+                );
+            }
+            println!(
+                "{}: {} error(s), {} warning(s), {} info",
+                result.file.display(),
+                result.report.count(Severity::Error),
+                result.report.count(Severity::Warning),
+                result.report.count(Severity::Info)
+            );
+        }
+    }
+
+    Ok(results
+        .iter()
+        .all(|result| result.report.max_severity() < Some(args.fail_on)))
+}
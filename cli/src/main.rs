@@ -1,15 +1,31 @@
-use clap::Parser;
+mod batch;
+mod diff;
+mod lint;
+mod merge;
+mod scan;
+mod sign;
+mod validate;
+mod verify;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use indicatif::{ProgressBar, ProgressFinish, ProgressStyle};
 use lib::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "aibom-generator")]
 #[command(about = "Generate AI Bill of Materials (AIBOM) for machine learning models")]
 #[command(version = "1.0.0")]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The model ID to analyze (e.g., microsoft/DialoGPT-medium)
-    #[arg(value_name = "MODEL_ID")]
-    model_id: String,
+    #[arg(value_name = "MODEL_ID", required = true)]
+    model_id: Option<String>,
 
     /// Output file path for the generated AIBOM
     #[arg(short, long, value_name = "FILE", default_value = "aibom.json")]
@@ -18,22 +34,297 @@ struct Args {
     /// Enable verbose output
     #[arg(short, long)]
     verbose: bool,
+
+    /// Find models derived from MODEL_ID instead of its dependencies
+    #[arg(long)]
+    descendants: bool,
+
+    /// Maximum number of descendants to collect in --descendants mode
+    #[arg(long, value_name = "COUNT", default_value_t = 1000)]
+    max_descendants: usize,
+
+    /// Write the generation report (or the descendant report in --descendants mode)
+    /// as JSON to this file
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+
+    #[command(flatten)]
+    generate: GenerateOptions,
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    env_logger::init();
+/// Traversal, enrichment and output settings shared by all generating commands
+#[derive(clap::Args)]
+pub(crate) struct GenerateOptions {
+    /// Maximum lineage depth to traverse
+    #[arg(long, value_name = "DEPTH")]
+    max_depth: Option<usize>,
+
+    /// Maximum number of components in the generated AIBOM
+    #[arg(long, value_name = "COUNT")]
+    max_components: Option<usize>,
+
+    /// Maximum time in seconds to spend traversing lineage
+    #[arg(long, value_name = "SECONDS")]
+    max_time: Option<u64>,
+
+    /// Add placeholder components for dependencies that failed or were skipped
+    #[arg(long)]
+    annotate_failures: bool,
+
+    /// Skip a repository analysis to save requests (repeatable)
+    #[arg(long, value_enum, value_name = "ENRICHER")]
+    disable: Vec<Enricher>,
+
+    /// Write the AIBOM without indentation
+    #[arg(long)]
+    compact: bool,
+
+    /// Output document format
+    #[arg(long, value_enum, default_value = "cyclonedx-json")]
+    format: Format,
+
+    /// CycloneDX spec version; fields older versions lack are kept as
+    /// `aibom:downgrade:` properties where possible
+    #[arg(long, value_enum, default_value = "1.6")]
+    spec_version: Version,
+
+    #[command(flatten)]
+    sign: sign::SignOptions,
+}
+
+impl GenerateOptions {
+    /// Generator builder configured from these options
+    pub(crate) fn builder(&self) -> Result<GeneratorBuilder, SignatureError> {
+        let defaults = TraversalLimits::default();
+        let limits = TraversalLimits {
+            max_depth: self.max_depth.or(defaults.max_depth),
+            max_components: self.max_components.or(defaults.max_components),
+            max_duration: self
+                .max_time
+                .map(Duration::from_secs)
+                .or(defaults.max_duration),
+        };
+
+        let enabled = |enricher| !self.disable.contains(&enricher);
+        let enrichers = Enrichers {
+            adapter: enabled(Enricher::Adapter),
+            quantization: enabled(Enricher::Quantization),
+            merge: enabled(Enricher::Merge),
+            pipeline: enabled(Enricher::Pipeline),
+            remote_code: enabled(Enricher::RemoteCode),
+            license_files: enabled(Enricher::LicenseFiles),
+        };
+
+        let mut builder = AIBOMGenerator::builder()
+            .limits(limits)
+            .enrichers(enrichers)
+            .output(OutputOptions {
+                format: self.format.into(),
+                pretty: !self.compact,
+                spec_version: self.spec_version.into(),
+            })
+            .annotate_failures(self.annotate_failures);
+        if let Some(signer) = self.sign.signer()? {
+            builder = builder.signer(signer);
+        }
+        Ok(builder)
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Validate CycloneDX JSON BOM files against the bundled AIBOM subset
+    /// schemas
+    Validate(validate::ValidateArgs),
+    /// Check AIBOM files for dangling references, duplicates, missing
+    /// provenance data and placeholder values
+    Lint(lint::LintArgs),
+    /// Compare two AIBOM files, matching components by purl without version
+    Diff(diff::DiffArgs),
+    /// Combine the AIBOMs of several models into one for a product using them
+    Merge(merge::MergeArgs),
+    /// Generate an AIBOM for every model listed in a file or on stdin
+    Batch(batch::BatchArgs),
+    /// Generate an AIBOM for every repository of a Hub author and an
+    /// inventory of their licenses, base models and gated dependencies
+    Scan(scan::ScanArgs),
+    /// Add a JSF signature to a CycloneDX JSON BOM
+    Sign(sign::SignArgs),
+    /// Check the JSF signatures of CycloneDX JSON BOMs against trusted keys
+    Verify(verify::VerifyArgs),
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub(crate) enum Format {
+    CyclonedxJson,
+    CyclonedxXml,
+    Spdx3Json,
+}
+
+impl From<Format> for OutputFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::CyclonedxJson => OutputFormat::CyclonedxJson,
+            Format::CyclonedxXml => OutputFormat::CyclonedxXml,
+            Format::Spdx3Json => OutputFormat::Spdx3Json,
+        }
+    }
+}
 
+#[derive(Clone, Copy, ValueEnum)]
+pub(crate) enum Version {
+    #[value(name = "1.4")]
+    V1_4,
+    #[value(name = "1.5")]
+    V1_5,
+    #[value(name = "1.6")]
+    V1_6,
+}
+
+impl From<Version> for SpecVersion {
+    fn from(version: Version) -> Self {
+        match version {
+            Version::V1_4 => SpecVersion::V1_4,
+            Version::V1_5 => SpecVersion::V1_5,
+            Version::V1_6 => SpecVersion::V1_6,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Enricher {
+    Adapter,
+    Quantization,
+    Merge,
+    Pipeline,
+    RemoteCode,
+    LicenseFiles,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
+    match &args.command {
+        Some(Command::Validate(validate_args)) => {
+            let valid = validate::run(validate_args)?;
+            std::process::exit(if valid { 0 } else { 1 });
+        }
+        Some(Command::Lint(lint_args)) => {
+            let passed = lint::run(lint_args)?;
+            std::process::exit(if passed { 0 } else { 1 });
+        }
+        Some(Command::Diff(diff_args)) => {
+            let unchanged = diff::run(diff_args)?;
+            std::process::exit(if unchanged { 0 } else { 1 });
+        }
+        Some(Command::Merge(merge_args)) => return merge::run(merge_args),
+        Some(Command::Batch(batch_args)) => {
+            let succeeded = batch::run(batch_args)?;
+            std::process::exit(if succeeded { 0 } else { 1 });
+        }
+        Some(Command::Sign(sign_args)) => return sign::run(sign_args),
+        Some(Command::Verify(verify_args)) => {
+            let passed = verify::run(verify_args)?;
+            std::process::exit(if passed { 0 } else { 1 });
+        }
+        Some(Command::Scan(scan_args)) => {
+            let succeeded = scan::run(scan_args)?;
+            std::process::exit(if succeeded { 0 } else { 1 });
+        }
+        None => {}
+    }
+    let model_id = args
+        .model_id
+        .as_deref()
+        .expect("MODEL_ID is required without a subcommand");
+
+    // Library diagnostics go through `log`; --verbose raises the default level
+    let default_level = if args.verbose { "info" } else { "error" };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_level))
+        .init();
 
     if args.verbose {
-        println!("Generating AIBOM for model: {}", args.model_id);
+        println!("Generating AIBOM for model: {}", model_id);
         println!("Output file: {}", args.output.display());
     }
 
-    let mut generator = AIBOMGenerator::new()?;
-    let aibom = generator.generate_aibom(&args.model_id)?;
+    let progress = ProgressBar::new_spinner().with_finish(ProgressFinish::AndClear);
+    progress.set_style(ProgressStyle::with_template(
+        "{spinner} [{pos} components] {msg}",
+    )?);
+    progress.enable_steady_tick(Duration::from_millis(120));
+
+    let generator = args
+        .generate
+        .builder()?
+        .observer(progress_observer(&progress))
+        .build()?;
+    let (aibom, generation_report) = if args.descendants {
+        let (aibom, report) = generator.generate_descendants_aibom(
+            model_id,
+            args.generate.max_depth.unwrap_or(2),
+            args.max_descendants,
+        )?;
+
+        match &args.report {
+            Some(report_path) => {
+                std::fs::write(report_path, serde_json::to_string_pretty(&report)?)?;
+                println!("Descendant report saved to {}", report_path.display());
+            }
+            None => {
+                for entry in &report.descendants {
+                    println!(
+                        "{}\t{}\t{}\t{}",
+                        entry.depth,
+                        entry.model_id,
+                        entry.relation.as_deref().unwrap_or("unknown"),
+                        entry.parent
+                    );
+                }
+            }
+        }
+        if report.truncated {
+            println!(
+                "Warning: descendant search truncated at {} models",
+                args.max_descendants
+            );
+        }
+
+        (aibom, None)
+    } else {
+        let (aibom, report) = generator.generate_aibom_with_report(model_id)?;
+
+        for issue in &report.failures {
+            eprintln!(
+                "Warning: {} could not be resolved ({}): {}",
+                issue.model_id, issue.kind, issue.message
+            );
+        }
+        for issue in &report.skipped {
+            eprintln!("Warning: {} was skipped: {}", issue.model_id, issue.message);
+        }
 
-    let output_content = serde_json::to_string_pretty(&aibom)?;
+        (aibom, Some(report))
+    };
+
+    progress.finish_and_clear();
+
+    let (output_content, downgraded) = generator.render_with_notes(&aibom)?;
+    for note in &downgraded {
+        eprintln!(
+            "Warning: {} of {} {} for CycloneDX {}",
+            note.field,
+            note.bom_ref,
+            note.action,
+            generator.config().output.spec_version.as_str()
+        );
+    }
+    if let Some(mut report) = generation_report
+        && let Some(report_path) = &args.report
+    {
+        report.downgraded = downgraded;
+        std::fs::write(report_path, serde_json::to_string_pretty(&report)?)?;
+        println!("Generation report saved to {}", report_path.display());
+    }
 
     if args.verbose {
         println!("Generated AIBOM:");
@@ -46,3 +337,69 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Read a CycloneDX JSON AIBOM file
+pub(crate) fn read_aibom(path: &Path) -> Result<AIBOM, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("{}: not an AIBOM: {}", path.display(), e).into())
+}
+
+/// Render generation progress on a spinner (hidden when stderr is not a terminal)
+fn progress_observer(progress: &ProgressBar) -> Arc<dyn ProgressObserver> {
+    let progress = progress.clone();
+    Arc::new(move |event: &ProgressEvent| match event {
+        ProgressEvent::FetchStarted { model_id, depth } => {
+            progress.set_message(format!("fetching {} (depth {})", model_id, depth));
+        }
+        ProgressEvent::DependencyFound { dependency, .. } => {
+            progress.set_message(format!("found {}", dependency));
+        }
+        ProgressEvent::ComponentAdded { total, .. } => progress.set_position(*total as u64),
+        ProgressEvent::Warning { .. } => {}
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_cyclonedx_1_4_documents() {
+        let dir = std::env::temp_dir().join(format!("aibom-read-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let aibom = merge_aiboms("product", "1.0", &[]);
+        let legacy = dir.join("legacy.json");
+        let rendered = Renderer::new(OutputOptions {
+            spec_version: SpecVersion::V1_4,
+            ..Default::default()
+        })
+        .render(&aibom)
+        .unwrap();
+        std::fs::write(&legacy, rendered).unwrap();
+        let not_aibom = dir.join("other.json");
+        std::fs::write(&not_aibom, "{}").unwrap();
+
+        // 1.4 tools arrays are read back as tool components
+        let read = read_aibom(&legacy).unwrap();
+        assert_eq!(read.spec_version, "1.4");
+        let tools = |aibom: &AIBOM| -> Vec<(String, String)> {
+            aibom
+                .metadata
+                .tools
+                .components
+                .iter()
+                .map(|t| (t.name.clone(), t.version.clone()))
+                .collect()
+        };
+        assert_eq!(tools(&read), tools(&aibom));
+        let merged = merge_aiboms("product", "1.0", &[read]);
+        Renderer::new(OutputOptions::default())
+            .render(&merged)
+            .unwrap();
+
+        let error = read_aibom(&not_aibom).unwrap_err().to_string();
+        assert!(error.contains("not an AIBOM"), "{}", error);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
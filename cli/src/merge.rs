@@ -0,0 +1,75 @@
+use crate::sign::SignOptions;
+use crate::{Format, Version, read_aibom};
+use lib::{OutputOptions, Renderer, merge_aiboms};
+use std::path::PathBuf;
+
+#[derive(clap::Args)]
+pub struct MergeArgs {
+    /// AIBOMs (CycloneDX JSON) to combine
+    #[arg(value_name = "FILE", required = true)]
+    files: Vec<PathBuf>,
+
+    /// Name of the product the merged AIBOM describes
+    #[arg(long)]
+    name: String,
+
+    /// Version of the product
+    #[arg(long, value_name = "VERSION", default_value = "1.0")]
+    product_version: String,
+
+    /// Output file path for the merged AIBOM
+    #[arg(short, long, value_name = "FILE", default_value = "aibom.json")]
+    output: PathBuf,
+
+    /// Write the AIBOM without indentation
+    #[arg(long)]
+    compact: bool,
+
+    /// Output document format
+    #[arg(long, value_enum, default_value = "cyclonedx-json")]
+    format: Format,
+
+    /// CycloneDX spec version
+    #[arg(long, value_enum, default_value = "1.6")]
+    spec_version: Version,
+
+    #[command(flatten)]
+    sign: SignOptions,
+}
+
+pub fn run(args: &MergeArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let inputs = args
+        .files
+        .iter()
+        .map(|file| read_aibom(file))
+        .collect::<Result<Vec<_>, _>>()?;
+    let merged = merge_aiboms(&args.name, &args.product_version, &inputs);
+
+    let mut renderer = Renderer::new(OutputOptions {
+        format: args.format.into(),
+        pretty: !args.compact,
+        spec_version: args.spec_version.into(),
+    });
+    if let Some(signer) = args.sign.signer()? {
+        renderer = renderer.signer(signer)?;
+    }
+    let (output_content, downgraded) = renderer.render_with_notes(&merged)?;
+    for note in &downgraded {
+        eprintln!(
+            "Warning: {} of {} {} for CycloneDX {}",
+            note.field,
+            note.bom_ref,
+            note.action,
+            renderer.output().spec_version.as_str()
+        );
+    }
+    std::fs::write(&args.output, &output_content)?;
+
+    println!(
+        "Merged {} AIBOMs ({} components) into {}",
+        inputs.len(),
+        merged.components.len(),
+        args.output.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use crate::GenerateOptions;
+use crate::batch::{extension, run_parallel};
+use lib::{AIBOM, AIBOMGenerator, GenerationReport, Inventory, RepoKind};
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args)]
+pub struct ScanArgs {
+    /// Hub user or organization whose repositories to scan
+    #[arg(value_name = "AUTHOR")]
+    author: String,
+
+    /// Also scan the author's datasets
+    #[arg(long)]
+    datasets: bool,
+
+    /// Also scan the author's Spaces
+    #[arg(long)]
+    spaces: bool,
+
+    /// Maximum number of repositories listed per kind
+    #[arg(long, value_name = "COUNT", default_value_t = 1000)]
+    max_repos: usize,
+
+    /// Directory for the per-repository AIBOMs and `inventory.json`
+    #[arg(short, long, value_name = "DIR", default_value = "inventory")]
+    output_dir: PathBuf,
+
+    /// Number of repositories generated concurrently
+    #[arg(short, long, value_name = "COUNT", default_value_t = 4)]
+    jobs: usize,
+
+    #[command(flatten)]
+    generate: GenerateOptions,
+}
+
+/// Successful generation of one repository
+struct Generated {
+    aibom: AIBOM,
+    report: GenerationReport,
+    file: String,
+}
+
+/// Generate an AIBOM for every repository of the author and write the
+/// inventory; returns whether every repository succeeded
+pub fn run(args: &ScanArgs) -> Result<bool, Box<dyn std::error::Error>> {
+    let generator = args.generate.builder()?.build()?;
+
+    let mut kinds = vec![RepoKind::Model];
+    if args.datasets {
+        kinds.push(RepoKind::Dataset);
+    }
+    if args.spaces {
+        kinds.push(RepoKind::Space);
+    }
+    let mut repos = Vec::new();
+    for kind in kinds {
+        let listed = generator.list_author_repos(kind, &args.author, args.max_repos)?;
+        println!(
+            "Found {} {} for {}",
+            listed.len(),
+            kind.api_path(),
+            args.author
+        );
+        if !listed.is_empty() {
+            std::fs::create_dir_all(args.output_dir.join(kind.api_path()))?;
+        }
+        repos.extend(listed.into_iter().map(|repo| (kind, repo.id)));
+    }
+
+    let results = run_parallel(
+        &repos,
+        args.jobs,
+        |(_, id)| id.clone(),
+        |(kind, id)| {
+            let result = generate_one(&generator, *kind, id, &args.output_dir);
+            (
+                result.as_ref().err().map(|(_, message)| message.clone()),
+                result,
+            )
+        },
+    )?;
+
+    let mut inventory = Inventory::new(&args.author);
+    for ((kind, id), result) in repos.iter().zip(results) {
+        match result {
+            Ok(generated) => {
+                inventory
+                    .add(id, *kind, &generated.aibom, &generated.report)
+                    .file = Some(generated.file);
+            }
+            Err((error_kind, message)) => inventory.add_failure(id, *kind, &error_kind, &message),
+        }
+    }
+    write_inventory(&args.output_dir, &inventory)?;
+    Ok(inventory.failed == 0)
+}
+
+/// Generate and write the AIBOM of one repository; errors are `(kind, message)`
+fn generate_one(
+    generator: &AIBOMGenerator,
+    kind: RepoKind,
+    id: &str,
+    output_dir: &Path,
+) -> Result<Generated, (String, String)> {
+    let (aibom, report) = generator
+        .generate_repo_aibom(kind, id)
+        .map_err(|e| (e.kind().to_string(), e.message().to_string()))?;
+    let content = generator
+        .render(&aibom)
+        .map_err(|e| (e.kind().to_string(), e.message().to_string()))?;
+
+    let file = format!(
+        "{}/{}.{}",
+        kind.api_path(),
+        id.replace('/', "__"),
+        extension(generator)
+    );
+    std::fs::write(output_dir.join(&file), content)
+        .map_err(|e| ("io".to_string(), e.to_string()))?;
+    Ok(Generated {
+        aibom,
+        report,
+        file,
+    })
+}
+
+fn write_inventory(
+    output_dir: &Path,
+    inventory: &Inventory,
+) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(output_dir)?;
+    let path = output_dir.join("inventory.json");
+    std::fs::write(&path, serde_json::to_string_pretty(inventory)?)?;
+
+    println!(
+        "Generated {} of {} AIBOMs into {}",
+        inventory.succeeded,
+        inventory.total,
+        output_dir.display()
+    );
+    println!(
+        "{} licenses, {} base models, {} gated dependencies, {} failures; see {}",
+        inventory.licenses.len(),
+        inventory.base_models.len(),
+        inventory.gated_dependencies.len(),
+        inventory.failures.len(),
+        path.display()
+    );
+    Ok(())
+}
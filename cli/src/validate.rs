@@ -0,0 +1,65 @@
+use crate::Version;
+use lib::{SchemaError, Value, validate_document, validate_document_as};
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[derive(clap::Args)]
+pub struct ValidateArgs {
+    /// CycloneDX JSON files to check against the AIBOM subset schemas
+    #[arg(value_name = "FILE", required = true)]
+    files: Vec<PathBuf>,
+
+    /// Validate against this spec version instead of each file's specVersion
+    #[arg(long, value_enum)]
+    spec_version: Option<Version>,
+
+    /// Print results as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Serialize)]
+struct FileResult {
+    file: PathBuf,
+    valid: bool,
+    errors: Vec<SchemaError>,
+}
+
+/// Validate every file, printing the violations; returns whether all were valid
+pub fn run(args: &ValidateArgs) -> Result<bool, Box<dyn std::error::Error>> {
+    let mut results = Vec::new();
+    for file in &args.files {
+        let content = std::fs::read_to_string(file)?;
+        let errors = match serde_json::from_str::<Value>(&content) {
+            Ok(document) => match args.spec_version {
+                Some(version) => validate_document_as(&document, version.into()),
+                None => validate_document(&document),
+            },
+            Err(e) => vec![SchemaError {
+                pointer: String::new(),
+                schema_path: String::new(),
+                message: format!("not a JSON document: {}", e),
+            }],
+        };
+        results.push(FileResult {
+            file: file.clone(),
+            valid: errors.is_empty(),
+            errors,
+        });
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        for result in &results {
+            if result.valid {
+                println!("{}: valid (AIBOM subset)", result.file.display());
+            }
+            for error in &result.errors {
+                println!("{}: {}", result.file.display(), error);
+            }
+        }
+    }
+
+    Ok(results.iter().all(|result| result.valid))
+}
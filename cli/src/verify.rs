@@ -0,0 +1,116 @@
+use lib::{SignatureCheck, TrustedKey, Value, VerificationReport, verify_document};
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[derive(clap::Args)]
+pub struct VerifyArgs {
+    /// Signed CycloneDX JSON files to verify
+    #[arg(value_name = "FILE", required = true)]
+    files: Vec<PathBuf>,
+
+    /// Trusted public key (PEM), or a directory of them; named after the
+    /// file stem (repeatable)
+    #[arg(long, value_name = "PATH", required = true)]
+    trusted_key: Vec<PathBuf>,
+
+    /// Print results as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Serialize)]
+struct FileResult {
+    file: PathBuf,
+    passed: bool,
+    #[serde(flatten)]
+    report: VerificationReport,
+}
+
+/// Verify every file, printing signers and failures; returns whether all passed
+pub fn run(args: &VerifyArgs) -> Result<bool, Box<dyn std::error::Error>> {
+    let trusted = load_trusted_keys(&args.trusted_key)?;
+
+    let mut results = Vec::new();
+    for file in &args.files {
+        let content = std::fs::read_to_string(file)?;
+        let document: Value = serde_json::from_str(&content)
+            .map_err(|e| format!("{}: not JSON: {}", file.display(), e))?;
+        let report = verify_document(&document, &trusted);
+        results.push(FileResult {
+            file: file.clone(),
+            passed: report.passed(),
+            report,
+        });
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        for result in &results {
+            let file = result.file.display();
+            println!("{}: {}", file, describe(&result.report.document));
+            for component in &result.report.components {
+                if !component.check.valid {
+                    println!(
+                        "{}: component {}: {}",
+                        file,
+                        component.bom_ref,
+                        describe(&component.check)
+                    );
+                }
+            }
+            let failed = result
+                .report
+                .components
+                .iter()
+                .filter(|c| !c.check.valid)
+                .count();
+            if !result.report.components.is_empty() {
+                println!(
+                    "{}: {} component signatures, {} failed",
+                    file,
+                    result.report.components.len(),
+                    failed
+                );
+            }
+        }
+    }
+
+    Ok(results.iter().all(|result| result.passed))
+}
+
+/// `valid, signed by NAME (ALGORITHM)` or `INVALID: reason`
+fn describe(check: &SignatureCheck) -> String {
+    let mut text = match (&check.signer, &check.error) {
+        (Some(signer), None) => format!("valid, signed by {}", signer),
+        (Some(signer), Some(error)) => format!("INVALID: {} (key {})", error, signer),
+        (None, error) => format!("INVALID: {}", error.as_deref().unwrap_or_default()),
+    };
+    if let Some(algorithm) = &check.algorithm {
+        text.push_str(&format!(" [{}]", algorithm));
+    }
+    text
+}
+
+/// Read PEM public keys from files and the `.pem` files of directories
+fn load_trusted_keys(paths: &[PathBuf]) -> Result<Vec<TrustedKey>, Box<dyn std::error::Error>> {
+    let mut keys = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut files: Vec<PathBuf> = std::fs::read_dir(path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<_, _>>()?;
+            files.retain(|file| file.extension().is_some_and(|e| e == "pem"));
+            files.sort();
+            for file in files {
+                keys.push(TrustedKey::from_pem_file(&file)?);
+            }
+        } else {
+            keys.push(TrustedKey::from_pem_file(path)?);
+        }
+    }
+    if keys.is_empty() {
+        return Err("no trusted keys found".into());
+    }
+    Ok(keys)
+}
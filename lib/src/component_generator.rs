@@ -1,22 +1,37 @@
-use crate::*;
 use crate::license_handler::LicenseHandler;
-use crate::model_analyzer::ModelAnalyzer;
+use crate::model_analyzer::{ModelAnalyzer, encode_revision, gated_status};
+use crate::*;
 
 pub struct ComponentGenerator {
     license_handler: LicenseHandler,
     model_analyzer: ModelAnalyzer,
 }
 
+impl Default for ComponentGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ComponentGenerator {
     pub fn new() -> Self {
+        Self::with_hub(HubClient::default(), true)
+    }
+
+    /// Create a generator sharing `hub`; `probe_license_files` enables looking up
+    /// LICENSE files for licenses that are not SPDX identifiers
+    pub fn with_hub(hub: HubClient, probe_license_files: bool) -> Self {
         Self {
-            license_handler: LicenseHandler::new(),
-            model_analyzer: ModelAnalyzer::new(),
+            license_handler: LicenseHandler::with_hub(probe_license_files.then(|| hub.clone())),
+            model_analyzer: ModelAnalyzer::with_hub(hub),
         }
     }
 
-    pub fn create_dataset_component(&self, dataset_id: &str) -> Component {
-        let (org, dataset_name) = self.model_analyzer.extract_organization_from_dataset_id(dataset_id);
+    /// Create a dataset component; `relation` is "train" or "test"
+    pub fn create_dataset_component(&self, dataset_id: &str, relation: Option<&str>) -> Component {
+        let (org, dataset_name) = self
+            .model_analyzer
+            .extract_organization_from_dataset_id(dataset_id);
         let version = "1.0".to_string();
         let purl = format!("pkg:huggingface-dataset/{}@{}", dataset_id, version);
         let bom_ref = purl.clone();
@@ -26,7 +41,10 @@ impl ComponentGenerator {
             bom_ref: bom_ref.clone(),
             name: dataset_name.clone(),
             version: Some(version.clone()),
-            description: Some("Training dataset".to_string()),
+            description: Some(match relation {
+                Some("test") => "Evaluation dataset".to_string(),
+                _ => "Training dataset".to_string(),
+            }),
             group: Some(org.clone()),
             publisher: Some(org.clone()),
             supplier: Some(Organization {
@@ -39,6 +57,7 @@ impl ComponentGenerator {
             }),
             authors: Some(vec![Author { name: org.clone() }]),
             copyright: Some("NOASSERTION".to_string()),
+            hashes: None,
             licenses: None, // Dataset license would need separate API call
             external_references: Some(vec![ExternalReference {
                 ref_type: "website".to_string(),
@@ -46,24 +65,130 @@ impl ComponentGenerator {
                 comment: Some("Dataset repository".to_string()),
             }]),
             purl: Some(purl),
+            pedigree: None,
             model_card: None,
+            properties: None,
+            components: None,
+            signature: None,
         }
     }
 
-    pub fn model_info_to_component(&self, model_info: &ModelInfo, relation: Option<String>) -> Component {
-        let (org, model_name) = self.model_analyzer.extract_organization_from_model_id(&model_info.model_id);
-        let version = "1.0".to_string();
-        let purl = format!("pkg:huggingface/{}@{}", model_info.model_id, version);
+    /// Create the component of a scanned dataset repository, with its declared
+    /// license and gating status
+    pub fn create_dataset_info_component(&self, dataset_info: &DatasetInfo) -> Component {
+        let mut component = self.create_dataset_component(&dataset_info.id, None);
+        component.description = dataset_info
+            .card_data
+            .as_ref()
+            .and_then(|card_data| card_data.get("pretty_name"))
+            .and_then(|name| name.as_str())
+            .map(|name| name.to_string())
+            .or_else(|| Some("Dataset".to_string()));
+        component.licenses =
+            declared_license_name(dataset_info.card_data.as_ref(), &dataset_info.tags).map(
+                |license| {
+                    vec![License {
+                        license: self.license_handler.declared_license(&license),
+                    }]
+                },
+            );
+        component.properties = gated_status(dataset_info.gated.as_ref()).map(|status| {
+            vec![Property {
+                name: "ai.access.gated".to_string(),
+                value: status,
+            }]
+        });
+        component
+    }
+
+    /// Create the application component of a Space
+    pub fn create_space_component(&self, space_info: &SpaceInfo) -> Component {
+        let (org, space_name) = self
+            .model_analyzer
+            .extract_organization_from_model_id(&space_info.id);
+        let purl = format!("pkg:huggingface-space/{}@1.0", space_info.id);
+
+        Component {
+            component_type: "application".to_string(),
+            bom_ref: purl.clone(),
+            name: space_name,
+            version: Some("1.0".to_string()),
+            description: Some("Hugging Face Space".to_string()),
+            group: Some(org.clone()),
+            publisher: Some(org.clone()),
+            supplier: Some(Organization {
+                name: org.clone(),
+                url: Some(vec![format!("https://huggingface.co/{}", org)]),
+            }),
+            manufacturer: None,
+            authors: None,
+            copyright: None,
+            hashes: None,
+            licenses: declared_license_name(space_info.card_data.as_ref(), &space_info.tags).map(
+                |license| {
+                    vec![License {
+                        license: self.license_handler.declared_license(&license),
+                    }]
+                },
+            ),
+            external_references: Some(vec![ExternalReference {
+                ref_type: "website".to_string(),
+                url: format!("https://huggingface.co/spaces/{}", space_info.id),
+                comment: Some("Space repository".to_string()),
+            }]),
+            purl: Some(purl),
+            pedigree: None,
+            model_card: None,
+            properties: space_info.sdk.as_ref().map(|sdk| {
+                vec![Property {
+                    name: "ai.space.sdk".to_string(),
+                    value: sdk.clone(),
+                }]
+            }),
+            components: None,
+            signature: None,
+        }
+    }
+
+    pub fn model_info_to_component(
+        &self,
+        model_info: &ModelInfo,
+        relation: Option<String>,
+    ) -> Component {
+        let (org, model_name) = self
+            .model_analyzer
+            .extract_organization_from_model_id(&model_info.model_id);
+        // A model read at a revision is versioned by it, so it does not share a
+        // bom-ref with the same model's main branch
+        let version = model_info
+            .revision
+            .clone()
+            .unwrap_or_else(|| "1.0".to_string());
+        let purl = format!(
+            "pkg:huggingface/{}@{}",
+            model_info.model_id,
+            encode_revision(&version)
+        );
         let bom_ref = purl.clone();
+        let tree = encode_revision(model_info.revision.as_deref().unwrap_or("main"));
 
         // Extract license from tags if not available in license field
-        let license_str = model_info
-            .license
-            .clone()
-            .or_else(|| self.model_analyzer.extract_license_from_tags(&model_info.tags));
+        let license_str = model_info.license.clone().or_else(|| {
+            self.model_analyzer
+                .extract_license_from_tags(&model_info.tags)
+        });
 
         // Create ModelCard
-        let model_card = if self.model_analyzer.is_machine_learning_model(&model_info.tags) {
+        let model_card = if self
+            .model_analyzer
+            .is_machine_learning_model(&model_info.tags)
+            || model_info.adapter_config.is_some()
+            || model_info.quantization.is_some()
+            || model_info.conversion.is_some()
+            || model_info.merge_config.is_some()
+            || model_info.model_index.is_some()
+            || model_info.embedding.is_some()
+        {
             let task = self.model_analyzer.determine_task(&model_info.tags);
             let architecture = self.model_analyzer.get_model_architecture(model_info);
 
@@ -99,13 +224,16 @@ impl ComponentGenerator {
                 },
                 Property {
                     name: "downloadLocation".to_string(),
-                    value: format!("https://huggingface.co/{}/tree/main", model_info.model_id),
+                    value: format!(
+                        "https://huggingface.co/{}/tree/{}",
+                        model_info.model_id, tree
+                    ),
                 },
                 Property {
                     name: "external_references".to_string(),
                     value: format!(
-                        r#"[{{"type": "website", "url": "https://huggingface.co/{}", "comment": "Model repository"}}, {{"type": "distribution", "url": "https://huggingface.co/{}/tree/main", "comment": "Model files"}}]"#,
-                        model_info.model_id, model_info.model_id
+                        r#"[{{"type": "website", "url": "https://huggingface.co/{}", "comment": "Model repository"}}, {{"type": "distribution", "url": "https://huggingface.co/{}/tree/{}", "comment": "Model files"}}]"#,
+                        model_info.model_id, model_info.model_id, tree
                     ),
                 },
             ];
@@ -118,17 +246,52 @@ impl ComponentGenerator {
                 });
             }
 
+            // Add PEFT adapter details if available
+            if let Some(adapter_config) = &model_info.adapter_config {
+                properties.extend(self.adapter_properties(adapter_config));
+            }
+
+            // Add quantization details if available
+            if let Some(quantization) = &model_info.quantization {
+                properties.extend(self.quantization_properties(quantization));
+            }
+            if let Some(conversion) = &model_info.conversion {
+                properties.push(Property {
+                    name: "ai.conversion.format".to_string(),
+                    value: conversion.format.clone(),
+                });
+                if let Some(precision) = &conversion.precision {
+                    properties.push(Property {
+                        name: "ai.conversion.precision".to_string(),
+                        value: precision.clone(),
+                    });
+                }
+            }
+
+            // Add the merge recipe; per-source details are on the pedigree ancestors
+            if let Some(merge_config) = &model_info.merge_config {
+                properties.extend(self.merge_properties(merge_config));
+            }
+
+            // Embedding details go to `modelParameters.embedding`
+            let outputs = match &model_info.embedding {
+                Some(_) => "embedding",
+                None => "generated-text",
+            };
+
             Some(ModelCard {
                 model_parameters: Some(ModelParameters {
                     architecture_family: Some("transformer".to_string()),
                     model_architecture: Some(architecture),
                     task: Some(task),
+                    datasets: None,
                     inputs: Some(vec![InputOutputData {
                         format: "text".to_string(),
                     }]),
                     outputs: Some(vec![InputOutputData {
-                        format: "generated-text".to_string(),
+                        format: outputs.to_string(),
                     }]),
+                    embedding: model_info.embedding.clone(),
                 }),
                 properties: Some(properties),
                 quantitative_analysis: Some(QuantitativeAnalysis {
@@ -140,6 +303,50 @@ impl ComponentGenerator {
             None
         };
 
+        let components: Vec<Component> = model_info
+            .pipeline_components
+            .iter()
+            .map(|pipeline_component| {
+                self.pipeline_component_to_component(model_info, pipeline_component, &purl)
+            })
+            .collect();
+
+        let mut properties = Vec::new();
+
+        // Flag models that execute repository code at load time for security review
+        if !model_info.remote_code.is_empty() {
+            properties.push(Property {
+                name: "ai.security.trustRemoteCode".to_string(),
+                value: "required".to_string(),
+            });
+            properties.push(Property {
+                name: "ai.security.remoteCodeFiles".to_string(),
+                value: model_info
+                    .remote_code
+                    .iter()
+                    .map(|f| format!("{}/{}", f.repo, f.path))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            });
+        }
+
+        let mut licenses: Vec<License> = license_str
+            .as_ref()
+            .and_then(|license| self.license_handler.normalize_license(license, model_info))
+            .map(|license_info| License {
+                license: license_info,
+            })
+            .into_iter()
+            .collect();
+
+        // Gated access terms are click-through agreements on top of the license
+        if let Some(gating) = self.model_analyzer.extract_gating(model_info) {
+            properties.extend(self.gating_properties(&gating));
+            if let Some(terms_license) = self.gating_terms_license(&model_info.model_id, &gating) {
+                licenses.push(terms_license);
+            }
+        }
+
         Component {
             component_type: "machine-learning-model".to_string(),
             bom_ref: bom_ref.clone(),
@@ -158,26 +365,509 @@ impl ComponentGenerator {
             }),
             authors: Some(vec![Author { name: org.clone() }]),
             copyright: Some("NOASSERTION".to_string()),
-            licenses: license_str.as_ref().and_then(|license| {
-                self.license_handler.normalize_license(license, model_info)
-                    .map(|license_info| {
-                        vec![License {
-                            license: license_info,
-                        }]
-                    })
-            }),
+            hashes: None,
+            licenses: (!licenses.is_empty()).then_some(licenses),
             external_references: Some(vec![ExternalReference {
                 ref_type: "website".to_string(),
                 url: format!("https://huggingface.co/{}", model_info.model_id),
                 comment: None,
             }]),
             purl: Some(purl),
+            pedigree: None,
             model_card,
+            properties: (!properties.is_empty()).then_some(properties),
+            components: (!components.is_empty()).then_some(components),
+            signature: None,
+        }
+    }
+
+    fn gating_properties(&self, gating: &GatingInfo) -> Vec<Property> {
+        let mut properties = vec![Property {
+            name: "ai.access.gated".to_string(),
+            value: gating.status.clone(),
+        }];
+        if let Some(terms) = &gating.terms {
+            properties.push(Property {
+                name: "ai.access.terms".to_string(),
+                value: terms.clone(),
+            });
+        }
+        if let Some(terms_sha256) = &gating.terms_sha256 {
+            properties.push(Property {
+                name: "ai.access.termsSha256".to_string(),
+                value: terms_sha256.clone(),
+            });
+        }
+        if !gating.fields.is_empty() {
+            properties.push(Property {
+                name: "ai.access.gatedFields".to_string(),
+                value: gating.fields.join(","),
+            });
+        }
+        properties
+    }
+
+    /// The gating terms as an acknowledged license entry alongside the model's license
+    fn gating_terms_license(&self, model_id: &str, gating: &GatingInfo) -> Option<License> {
+        let terms = gating.terms.as_ref()?;
+
+        let properties = gating.terms_sha256.as_ref().map(|terms_sha256| {
+            vec![Property {
+                name: "ai.access.termsSha256".to_string(),
+                value: terms_sha256.clone(),
+            }]
+        });
+
+        Some(License {
+            license: LicenseInfo {
+                id: None,
+                name: Some(format!("Gated access terms for {}", model_id)),
+                url: Some(format!("https://huggingface.co/{}", model_id)),
+                text: Some(AttachedText {
+                    content_type: Some("text/plain".to_string()),
+                    encoding: None,
+                    content: terms.clone(),
+                }),
+                acknowledgement: Some("declared".to_string()),
+                properties,
+            },
+        })
+    }
+
+    /// Properties describing the lineage edge from a merged model to one of its inputs
+    pub fn merge_edge_properties(
+        &self,
+        merge_config: &MergeConfig,
+        model_id: &str,
+    ) -> Vec<Property> {
+        let mut properties = Vec::new();
+        if let Some(method) = &merge_config.merge_method {
+            properties.push(Property {
+                name: "ai.merge.method".to_string(),
+                value: method.clone(),
+            });
+        }
+        if merge_config.base_model.as_deref() == Some(model_id) {
+            properties.push(Property {
+                name: "ai.merge.isBaseModel".to_string(),
+                value: "true".to_string(),
+            });
+        }
+        if let Some(source) = merge_config.sources.iter().find(|s| s.model == model_id) {
+            if let Some(weight) = &source.weight {
+                properties.push(Property {
+                    name: "ai.merge.weight".to_string(),
+                    value: weight.clone(),
+                });
+            }
+            if let Some(density) = &source.density {
+                properties.push(Property {
+                    name: "ai.merge.density".to_string(),
+                    value: density.clone(),
+                });
+            }
+            for layer_range in &source.layer_ranges {
+                properties.push(Property {
+                    name: "ai.merge.layerRange".to_string(),
+                    value: layer_range.clone(),
+                });
+            }
+        }
+        properties
+    }
+
+    /// Create a reference to another model for use inside a pedigree. It carries
+    /// no bom-ref, since the model also appears as a top-level component.
+    pub fn create_lineage_reference(
+        &self,
+        model_id: &str,
+        edge: &DependencyReference,
+    ) -> Component {
+        let (org, model_name) = self
+            .model_analyzer
+            .extract_organization_from_model_id(model_id);
+
+        let mut properties = Vec::new();
+        if let Some(relation) = &edge.relation {
+            properties.push(Property {
+                name: "ai.model.relation".to_string(),
+                value: relation.clone(),
+            });
+        }
+        properties.extend(edge.properties.iter().flatten().cloned());
+
+        Component {
+            component_type: "machine-learning-model".to_string(),
+            bom_ref: String::new(),
+            name: model_name,
+            version: Some("1.0".to_string()),
+            description: None,
+            group: Some(org),
+            publisher: None,
+            supplier: None,
+            manufacturer: None,
+            authors: None,
+            copyright: None,
+            hashes: None,
+            licenses: None,
+            external_references: None,
+            purl: Some(edge.reference.clone()),
+            pedigree: None,
+            model_card: None,
+            properties: (!properties.is_empty()).then_some(properties),
+            components: None,
+            signature: None,
+        }
+    }
+
+    /// Create a placeholder for a model that could not be resolved, so references
+    /// to it stay valid and consumers can see why it is incomplete
+    pub fn create_unresolved_component(
+        &self,
+        model_id: &str,
+        kind: &str,
+        message: &str,
+    ) -> Component {
+        let (org, model_name) = self
+            .model_analyzer
+            .extract_organization_from_model_id(model_id);
+        let purl = format!("pkg:huggingface/{}@1.0", model_id);
+
+        Component {
+            component_type: "machine-learning-model".to_string(),
+            bom_ref: purl.clone(),
+            name: model_name,
+            version: Some("1.0".to_string()),
+            description: None,
+            group: Some(org),
+            publisher: None,
+            supplier: None,
+            manufacturer: None,
+            authors: None,
+            copyright: None,
+            hashes: None,
+            licenses: None,
+            external_references: Some(vec![ExternalReference {
+                ref_type: "website".to_string(),
+                url: format!("https://huggingface.co/{}", model_id),
+                comment: None,
+            }]),
+            purl: Some(purl),
+            pedigree: None,
+            model_card: None,
+            properties: Some(vec![
+                Property {
+                    name: "aibom:error.kind".to_string(),
+                    value: kind.to_string(),
+                },
+                Property {
+                    name: "aibom:error.message".to_string(),
+                    value: message.to_string(),
+                },
+            ]),
+            components: None,
+            signature: None,
+        }
+    }
+
+    /// Build a pedigree from a model's lineage edges: quantizations and conversions
+    /// are variants of their source, other derivations list it as an ancestor.
+    /// Pipeline components and datasets are runtime or training inputs, not lineage.
+    pub fn create_pedigree(&self, lineage: &[(String, DependencyReference)]) -> Option<Pedigree> {
+        let mut ancestors = Vec::new();
+        let mut variants = Vec::new();
+
+        for (model_id, edge) in lineage {
+            match edge.relation.as_deref() {
+                Some("component") | Some("train") => {}
+                Some("quantized") | Some("converted") => {
+                    variants.push(self.create_lineage_reference(model_id, edge))
+                }
+                _ => ancestors.push(self.create_lineage_reference(model_id, edge)),
+            }
+        }
+
+        if ancestors.is_empty() && variants.is_empty() {
+            return None;
+        }
+        Some(Pedigree {
+            ancestors: (!ancestors.is_empty()).then_some(ancestors),
+            descendants: None,
+            variants: (!variants.is_empty()).then_some(variants),
+            notes: None,
+        })
+    }
+
+    /// Create a source component for a Python file run with `trust_remote_code`
+    pub fn create_remote_code_component(&self, code_file: &RemoteCodeFile) -> Component {
+        let (org, _repo_name) = self
+            .model_analyzer
+            .extract_organization_from_model_id(&code_file.repo);
+        let version = code_file.revision.as_deref().map(encode_revision);
+        let purl = format!(
+            "pkg:huggingface/{}@{}#{}",
+            code_file.repo,
+            version.as_deref().unwrap_or("1.0"),
+            code_file.path
+        );
+
+        Component {
+            component_type: "file".to_string(),
+            bom_ref: purl.clone(),
+            name: code_file.path.clone(),
+            version: None,
+            description: Some(format!(
+                "Remote code executed with trust_remote_code from {}",
+                code_file.repo
+            )),
+            group: Some(org.clone()),
+            publisher: Some(org),
+            supplier: None,
+            manufacturer: None,
+            authors: None,
+            copyright: None,
+            hashes: code_file.sha256.as_ref().map(|sha256| {
+                vec![Hash {
+                    alg: "SHA-256".to_string(),
+                    content: sha256.clone(),
+                }]
+            }),
+            licenses: None,
+            external_references: Some(vec![ExternalReference {
+                ref_type: "distribution".to_string(),
+                url: format!(
+                    "https://huggingface.co/{}/blob/{}/{}",
+                    code_file.repo,
+                    version.as_deref().unwrap_or("main"),
+                    code_file.path
+                ),
+                comment: Some("Source file".to_string()),
+            }]),
+            purl: Some(purl),
+            pedigree: None,
+            model_card: None,
+            properties: Some(vec![Property {
+                name: "ai.remoteCode.references".to_string(),
+                value: code_file.references.join(","),
+            }]),
+            components: None,
+            signature: None,
+        }
+    }
+
+    /// Create a nested component for one module of a multi-part pipeline
+    pub fn pipeline_component_to_component(
+        &self,
+        model_info: &ModelInfo,
+        pipeline_component: &PipelineComponent,
+        parent_purl: &str,
+    ) -> Component {
+        let purl = format!("{}#{}", parent_purl, pipeline_component.name);
+
+        let mut properties = vec![Property {
+            name: "ai.pipeline.module".to_string(),
+            value: pipeline_component.name.clone(),
+        }];
+        if let Some(library) = &pipeline_component.library {
+            properties.push(Property {
+                name: "ai.pipeline.library".to_string(),
+                value: library.clone(),
+            });
+        }
+        if let Some(source_repo) = &pipeline_component.source_repo {
+            properties.push(Property {
+                name: "ai.pipeline.sourceRepo".to_string(),
+                value: source_repo.clone(),
+            });
+        }
+        // Record scalar config values; nested objects are too unstructured to flatten
+        if let Some(config) = pipeline_component
+            .config
+            .as_ref()
+            .and_then(|c| c.as_object())
+        {
+            for (key, value) in config {
+                if key.starts_with('_') {
+                    continue;
+                }
+                let value = match value {
+                    serde_json::Value::String(s) => s.clone(),
+                    serde_json::Value::Number(_) | serde_json::Value::Bool(_) => value.to_string(),
+                    _ => continue,
+                };
+                properties.push(Property {
+                    name: format!("ai.config.{}", key),
+                    value,
+                });
+            }
+        }
+
+        let architecture = pipeline_component.class_name.clone().or_else(|| {
+            pipeline_component
+                .config
+                .as_ref()
+                .and_then(|c| c.get("architectures"))
+                .and_then(|a| a.get(0))
+                .and_then(|a| a.as_str())
+                .map(String::from)
+        });
+        // transformers configs name their family; diffusers configs only their class
+        let architecture_family = pipeline_component
+            .config
+            .as_ref()
+            .and_then(|c| c.get("model_type"))
+            .and_then(|t| t.as_str())
+            .map(String::from);
+
+        Component {
+            // Modules without weights (schedulers, tokenizers) are configuration files
+            component_type: if pipeline_component.has_weights {
+                "machine-learning-model".to_string()
+            } else {
+                "file".to_string()
+            },
+            bom_ref: purl.clone(),
+            name: pipeline_component.name.clone(),
+            version: None,
+            description: pipeline_component
+                .class_name
+                .as_ref()
+                .map(|class_name| format!("{} module of {}", class_name, model_info.model_id)),
+            group: None,
+            publisher: None,
+            supplier: None,
+            manufacturer: None,
+            authors: None,
+            copyright: None,
+            hashes: None,
+            licenses: None,
+            external_references: Some(vec![ExternalReference {
+                ref_type: "distribution".to_string(),
+                url: format!(
+                    "https://huggingface.co/{}/tree/{}/{}",
+                    model_info.model_id,
+                    encode_revision(model_info.revision.as_deref().unwrap_or("main")),
+                    pipeline_component.name
+                ),
+                comment: None,
+            }]),
+            purl: Some(purl),
+            pedigree: None,
+            model_card: Some(ModelCard {
+                model_parameters: Some(ModelParameters {
+                    architecture_family,
+                    model_architecture: architecture,
+                    task: None,
+                    datasets: None,
+                    inputs: None,
+                    outputs: None,
+                    embedding: None,
+                }),
+                properties: Some(properties),
+                quantitative_analysis: None,
+            }),
+            properties: None,
+            components: None,
+            signature: None,
         }
     }
 
+    fn adapter_properties(&self, adapter_config: &AdapterConfig) -> Vec<Property> {
+        let mut properties = Vec::new();
+        let mut push = |name: &str, value: Option<String>| {
+            if let Some(value) = value {
+                properties.push(Property {
+                    name: name.to_string(),
+                    value,
+                });
+            }
+        };
+
+        push("ai.adapter.peftType", adapter_config.peft_type.clone());
+        push(
+            "ai.adapter.baseModel",
+            adapter_config.base_model_name_or_path.clone(),
+        );
+        push("ai.adapter.rank", adapter_config.r.map(|r| r.to_string()));
+        push(
+            "ai.adapter.alpha",
+            adapter_config.lora_alpha.map(|a| a.to_string()),
+        );
+        let target_modules = adapter_config.target_modules_list();
+        push(
+            "ai.adapter.targetModules",
+            (!target_modules.is_empty()).then(|| target_modules.join(",")),
+        );
+        push("ai.adapter.taskType", adapter_config.task_type.clone());
+
+        properties
+    }
+
+    fn quantization_properties(&self, quantization: &QuantizationInfo) -> Vec<Property> {
+        let mut properties = vec![
+            Property {
+                name: "ai.quantization.method".to_string(),
+                value: quantization.method.clone(),
+            },
+            Property {
+                name: "ai.quantization.source".to_string(),
+                value: quantization.source.clone(),
+            },
+        ];
+        let mut push = |name: &str, value: Option<String>| {
+            if let Some(value) = value {
+                properties.push(Property {
+                    name: name.to_string(),
+                    value,
+                });
+            }
+        };
+
+        push("ai.quantization.precision", quantization.precision.clone());
+        push(
+            "ai.quantization.bits",
+            quantization.bits.map(|b| b.to_string()),
+        );
+        push(
+            "ai.quantization.groupSize",
+            quantization.group_size.map(|g| g.to_string()),
+        );
+        push(
+            "ai.quantization.descAct",
+            quantization.desc_act.map(|d| d.to_string()),
+        );
+        for (key, value) in &quantization.settings {
+            push(&format!("ai.quantization.{}", key), Some(value.clone()));
+        }
+
+        properties
+    }
+
+    fn merge_properties(&self, merge_config: &MergeConfig) -> Vec<Property> {
+        let mut properties = vec![Property {
+            name: "ai.merge.configSource".to_string(),
+            value: merge_config.source_file.clone(),
+        }];
+        let mut push = |name: &str, value: Option<String>| {
+            if let Some(value) = value {
+                properties.push(Property {
+                    name: name.to_string(),
+                    value,
+                });
+            }
+        };
+
+        push("ai.merge.method", merge_config.merge_method.clone());
+        push("ai.merge.baseModel", merge_config.base_model.clone());
+        push("ai.merge.dtype", merge_config.dtype.clone());
+
+        properties
+    }
+
     pub fn create_main_application_component(&self, main_model_id: &str) -> Component {
-        let (_main_org, main_name) = self.model_analyzer.extract_organization_from_model_id(main_model_id);
+        let (_main_org, main_name) = self
+            .model_analyzer
+            .extract_organization_from_model_id(main_model_id);
         let main_purl = format!("pkg:generic/{}@1.0", main_model_id.replace("/", "%2F"));
 
         Component {
@@ -192,10 +882,291 @@ impl ComponentGenerator {
             manufacturer: None,
             authors: None,
             copyright: Some("NOASSERTION".to_string()),
+            hashes: None,
             licenses: None,
             external_references: None,
             purl: Some(main_purl.clone()),
+            pedigree: None,
             model_card: None,
+            properties: None,
+            components: None,
+            signature: None,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn edge(model_id: &str, relation: &str) -> (String, DependencyReference) {
+        (
+            model_id.to_string(),
+            DependencyReference {
+                reference: format!("pkg:huggingface/{}@1.0", model_id),
+                relation: Some(relation.to_string()),
+                scope: None,
+                properties: None,
+            },
+        )
+    }
+
+    fn names(components: &Option<Vec<Component>>) -> Vec<&str> {
+        components
+            .iter()
+            .flatten()
+            .map(|c| c.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn splits_pedigree_into_ancestors_and_variants() {
+        let generator = ComponentGenerator::with_hub(HubClient::stub([]), false);
+        assert!(generator.create_pedigree(&[]).is_none());
+        assert!(
+            generator
+                .create_pedigree(&[edge("org/parts", "component")])
+                .is_none()
+        );
+
+        let pedigree = generator
+            .create_pedigree(&[
+                edge("org/base", "finetune"),
+                edge("org/teacher", "distilled"),
+                edge("org/fp16", "quantized"),
+                edge("org/onnx", "converted"),
+                edge("org/vae", "component"),
+            ])
+            .unwrap();
+        assert_eq!(names(&pedigree.ancestors), vec!["base", "teacher"]);
+        assert_eq!(names(&pedigree.variants), vec!["fp16", "onnx"]);
+        assert!(pedigree.descendants.is_none());
+
+        let base = &pedigree.ancestors.as_ref().unwrap()[0];
+        assert_eq!(base.purl.as_deref(), Some("pkg:huggingface/org/base@1.0"));
+        assert_eq!(base.group.as_deref(), Some("org"));
+        let relation = &base.properties.as_ref().unwrap()[0];
+        assert_eq!(
+            (relation.name.as_str(), relation.value.as_str()),
+            ("ai.model.relation", "finetune")
+        );
+    }
+
+    #[test]
+    fn records_adapter_details_in_the_model_card() {
+        let generator = ComponentGenerator::with_hub(HubClient::stub([]), false);
+        let mut model_info: ModelInfo = serde_json::from_value(json!({
+            "modelId": "org/adapter",
+            "tags": ["peft"]
+        }))
+        .unwrap();
+        model_info.adapter_config = Some(
+            serde_json::from_value(json!({
+                "base_model_name_or_path": "org/base",
+                "peft_type": "LORA",
+                "r": 8,
+                "lora_alpha": 16,
+                "target_modules": ["q_proj", "v_proj"],
+                "task_type": "CAUSAL_LM"
+            }))
+            .unwrap(),
+        );
+
+        let component = generator.model_info_to_component(&model_info, Some("lora".to_string()));
+        let properties = component.model_card.unwrap().properties.unwrap();
+        let property = |name: &str| {
+            properties
+                .iter()
+                .find(|p| p.name == name)
+                .map(|p| p.value.as_str())
+        };
+        assert_eq!(property("ai.adapter.peftType"), Some("LORA"));
+        assert_eq!(property("ai.adapter.baseModel"), Some("org/base"));
+        assert_eq!(property("ai.adapter.rank"), Some("8"));
+        assert_eq!(property("ai.adapter.alpha"), Some("16"));
+        assert_eq!(property("ai.adapter.targetModules"), Some("q_proj,v_proj"));
+        assert_eq!(property("ai.adapter.taskType"), Some("CAUSAL_LM"));
+    }
+
+    #[test]
+    fn nests_pipeline_modules_under_their_pipeline() {
+        let generator = ComponentGenerator::with_hub(HubClient::stub([]), false);
+        let model_info: ModelInfo =
+            serde_json::from_value(json!({ "modelId": "org/sd", "tags": [] })).unwrap();
+        let module = |name: &str, has_weights: bool, config: Value| PipelineComponent {
+            name: name.to_string(),
+            library: Some("diffusers".to_string()),
+            class_name: None,
+            config: Some(config),
+            has_weights,
+            source_repo: None,
+            relation: None,
+        };
+
+        let mut unet = module(
+            "unet",
+            true,
+            json!({ "_class_name": "UNet2DConditionModel", "sample_size": 64, "block_out_channels": [320, 640] }),
+        );
+        unet.source_repo = Some("org/base-unet".to_string());
+        let component = generator.pipeline_component_to_component(
+            &model_info,
+            &unet,
+            "pkg:huggingface/org/sd@1.0",
+        );
+        assert_eq!(component.component_type, "machine-learning-model");
+        assert_eq!(component.bom_ref, "pkg:huggingface/org/sd@1.0#unet");
+        let model_card = component.model_card.unwrap();
+        let parameters = model_card.model_parameters.as_ref().unwrap();
+        // diffusers configs name the class, not the family
+        assert_eq!(parameters.architecture_family, None);
+        let properties: Vec<_> = model_card
+            .properties
+            .iter()
+            .flatten()
+            .map(|p| (p.name.as_str(), p.value.as_str()))
+            .collect();
+        assert_eq!(
+            properties,
+            vec![
+                ("ai.pipeline.module", "unet"),
+                ("ai.pipeline.library", "diffusers"),
+                ("ai.pipeline.sourceRepo", "org/base-unet"),
+                ("ai.config.sample_size", "64"),
+            ]
+        );
+
+        let scheduler = module("scheduler", false, json!({ "num_train_timesteps": 1000 }));
+        let component = generator.pipeline_component_to_component(
+            &model_info,
+            &scheduler,
+            "pkg:huggingface/org/sd@1.0",
+        );
+        assert_eq!(component.component_type, "file");
+
+        let mut text_encoder = module(
+            "text_encoder",
+            true,
+            json!({ "model_type": "clip_text_model", "architectures": ["CLIPTextModel"] }),
+        );
+        text_encoder.library = Some("transformers".to_string());
+        let component = generator.pipeline_component_to_component(
+            &model_info,
+            &text_encoder,
+            "pkg:huggingface/org/sd@1.0",
+        );
+        let parameters = component.model_card.unwrap().model_parameters.unwrap();
+        assert_eq!(
+            parameters.architecture_family.as_deref(),
+            Some("clip_text_model")
+        );
+        assert_eq!(
+            parameters.model_architecture.as_deref(),
+            Some("CLIPTextModel")
+        );
+    }
+
+    #[test]
+    fn records_embedding_output_as_structured_model_parameters() {
+        let generator = ComponentGenerator::with_hub(HubClient::stub([]), false);
+        let mut model_info: ModelInfo = serde_json::from_value(json!({
+            "modelId": "org/embedder",
+            "tags": ["sentence-transformers"]
+        }))
+        .unwrap();
+        let embedding = EmbeddingInfo {
+            pooling_mode: Some("mean".to_string()),
+            dimension: Some(768),
+            normalized: true,
+        };
+        model_info.embedding = Some(embedding.clone());
+
+        let component = generator.model_info_to_component(&model_info, None);
+        let model_card = component.model_card.unwrap();
+        let parameters = model_card.model_parameters.as_ref().unwrap();
+        assert_eq!(parameters.outputs.as_ref().unwrap()[0].format, "embedding");
+        assert_eq!(parameters.embedding.as_ref(), Some(&embedding));
+        assert!(
+            !model_card
+                .properties
+                .iter()
+                .flatten()
+                .any(|p| p.name.starts_with("ai.embedding."))
+        );
+        assert_eq!(
+            serde_json::to_value(parameters).unwrap()["embedding"],
+            json!({ "poolingMode": "mean", "dimension": 768, "normalized": true })
+        );
+
+        // Formats without the extension carry it as properties
+        let properties = model_card.properties_with_extensions();
+        assert_eq!(EmbeddingInfo::from_properties(&properties), Some(embedding));
+    }
+
+    #[test]
+    fn keeps_per_source_merge_details_on_the_edges_only() {
+        let generator = ComponentGenerator::with_hub(HubClient::stub([]), false);
+        let merge_config = ModelAnalyzer::new()
+            .parse_merge_config(
+                "models:\n  - model: org/a\n    parameters:\n      weight: 0.6\n      density: 0.5\n  - model: org/base\nmerge_method: ties\nbase_model: org/base\n",
+                "mergekit_config.yml",
+            )
+            .unwrap();
+        let mut model_info: ModelInfo = serde_json::from_value(json!({
+            "modelId": "org/merged",
+            "tags": ["mergekit"]
+        }))
+        .unwrap();
+        model_info.merge_config = Some(merge_config.clone());
+
+        let component = generator.model_info_to_component(&model_info, None);
+        let properties: Vec<(String, String)> = component
+            .model_card
+            .unwrap()
+            .properties
+            .into_iter()
+            .flatten()
+            .filter(|p| p.name.starts_with("ai.merge."))
+            .map(|p| (p.name, p.value))
+            .collect();
+        let expected = [
+            ("ai.merge.configSource", "mergekit_config.yml"),
+            ("ai.merge.method", "ties"),
+            ("ai.merge.baseModel", "org/base"),
+        ];
+        assert_eq!(
+            properties,
+            expected.map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+
+        let edge = |model_id: &str| -> Vec<(String, String)> {
+            generator
+                .merge_edge_properties(&merge_config, model_id)
+                .into_iter()
+                .map(|p| (p.name, p.value))
+                .collect()
+        };
+        let pairs = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        };
+        assert_eq!(
+            edge("org/a"),
+            pairs(&[
+                ("ai.merge.method", "ties"),
+                ("ai.merge.weight", "0.6"),
+                ("ai.merge.density", "0.5"),
+            ])
+        );
+        assert_eq!(
+            edge("org/base"),
+            pairs(&[
+                ("ai.merge.method", "ties"),
+                ("ai.merge.isBaseModel", "true")
+            ])
+        );
+    }
+}
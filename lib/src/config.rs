@@ -0,0 +1,171 @@
+use crate::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Bounds on lineage traversal, so pathological chains or giant merges cannot
+/// explode runtime
+#[derive(Debug, Clone)]
+pub struct TraversalLimits {
+    /// Maximum number of lineage hops from the main model
+    pub max_depth: Option<usize>,
+    /// Maximum number of components in the generated BOM
+    pub max_components: Option<usize>,
+    /// Maximum wall-clock time spent traversing
+    pub max_duration: Option<Duration>,
+}
+
+impl Default for TraversalLimits {
+    /// Hub lineage chains are rarely more than a few hops deep, so a depth of
+    /// 10 only cuts off runaway chains; 1000 components bounds merges of many
+    /// sources while keeping the BOM a size downstream tools handle. Time is
+    /// unbounded, as it depends on the Hub's latency.
+    fn default() -> Self {
+        Self {
+            max_depth: Some(10),
+            max_components: Some(1000),
+            max_duration: None,
+        }
+    }
+}
+
+/// Repository analyses run on top of the Hub model metadata. Each one costs
+/// extra requests per model, so they can be switched off individually.
+#[derive(Debug, Clone)]
+pub struct Enrichers {
+    /// PEFT `adapter_config.json`
+    pub adapter: bool,
+    /// Quantization configs and GGUF file names
+    pub quantization: bool,
+    /// Mergekit recipes
+    pub merge: bool,
+    /// Diffusers `model_index.json` and sentence-transformers `modules.json`
+    pub pipeline: bool,
+    /// Custom code executed with `trust_remote_code`
+    pub remote_code: bool,
+    /// LICENSE file lookup for licenses that are not SPDX identifiers
+    pub license_files: bool,
+}
+
+impl Default for Enrichers {
+    fn default() -> Self {
+        Self {
+            adapter: true,
+            quantization: true,
+            merge: true,
+            pipeline: true,
+            remote_code: true,
+            license_files: true,
+        }
+    }
+}
+
+/// How generated AIBOMs are rendered by [`AIBOMGenerator::render`]
+#[derive(Debug, Clone)]
+pub struct OutputOptions {
+    /// Document format
+    pub format: OutputFormat,
+    /// Indent the output for readability
+    pub pretty: bool,
+    /// CycloneDX spec version; older versions are produced by
+    /// [`formats::downgrade`](crate::formats::downgrade)
+    pub spec_version: SpecVersion,
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::default(),
+            pretty: true,
+            spec_version: SpecVersion::default(),
+        }
+    }
+}
+
+/// Everything that shapes a generation run
+#[derive(Debug, Clone, Default)]
+pub struct GeneratorConfig {
+    pub hub: HubConfig,
+    pub limits: TraversalLimits,
+    pub enrichers: Enrichers,
+    pub output: OutputOptions,
+    /// Add placeholder components for failed or skipped dependencies
+    pub annotate_failures: bool,
+}
+
+/// Builder for [`AIBOMGenerator`], obtained from [`AIBOMGenerator::builder`]
+#[derive(Default)]
+pub struct GeneratorBuilder {
+    config: GeneratorConfig,
+    hub_client: Option<HubClient>,
+    observer: Option<Arc<dyn ProgressObserver>>,
+    signer: Option<Signer>,
+}
+
+impl GeneratorBuilder {
+    /// Replace the whole configuration
+    pub fn config(mut self, config: GeneratorConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Hub endpoint, token and timeout; ignored when a client is supplied
+    pub fn hub(mut self, hub: HubConfig) -> Self {
+        self.config.hub = hub;
+        self
+    }
+
+    /// Share an existing Hub client (and its response cache) with this generator
+    pub fn hub_client(mut self, hub_client: HubClient) -> Self {
+        self.hub_client = Some(hub_client);
+        self
+    }
+
+    pub fn limits(mut self, limits: TraversalLimits) -> Self {
+        self.config.limits = limits;
+        self
+    }
+
+    pub fn enrichers(mut self, enrichers: Enrichers) -> Self {
+        self.config.enrichers = enrichers;
+        self
+    }
+
+    pub fn output(mut self, output: OutputOptions) -> Self {
+        self.config.output = output;
+        self
+    }
+
+    pub fn annotate_failures(mut self, annotate: bool) -> Self {
+        self.config.annotate_failures = annotate;
+        self
+    }
+
+    /// Report progress events of every run to `observer`
+    pub fn observer(mut self, observer: Arc<dyn ProgressObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Sign rendered output with `signer`; requires CycloneDX JSON output
+    pub fn signer(mut self, signer: Signer) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    pub fn build(self) -> Result<AIBOMGenerator, GenerateError> {
+        let mut renderer = Renderer::new(self.config.output.clone());
+        if let Some(signer) = self.signer {
+            renderer = renderer.signer(signer)?;
+        }
+        let hub = match self.hub_client {
+            Some(hub) => hub,
+            None => HubClient::new(&self.config.hub)?,
+        };
+        Ok(AIBOMGenerator::from_parts(
+            self.config,
+            hub,
+            self.observer,
+            renderer,
+        ))
+    }
+}
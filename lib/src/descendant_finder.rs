@@ -0,0 +1,134 @@
+use crate::model_analyzer::ModelAnalyzer;
+use crate::*;
+use std::collections::{HashSet, VecDeque};
+use tracing::{debug, warn};
+
+/// Walks the Hub's `base_model:` tags in reverse to find models derived from a base model
+pub struct DescendantFinder {
+    model_analyzer: ModelAnalyzer,
+    max_depth: usize,
+    max_descendants: usize,
+}
+
+impl DescendantFinder {
+    pub fn new(max_depth: usize, max_descendants: usize) -> Self {
+        Self::with_hub(HubClient::default(), max_depth, max_descendants)
+    }
+
+    pub fn with_hub(hub: HubClient, max_depth: usize, max_descendants: usize) -> Self {
+        Self {
+            model_analyzer: ModelAnalyzer::with_hub(hub),
+            max_depth,
+            max_descendants,
+        }
+    }
+
+    /// Breadth-first search of derivatives up to the configured depth
+    pub fn find_descendants(&self, base_model_id: &str) -> Result<DescendantReport, GenerateError> {
+        let mut descendants = Vec::new();
+        let mut truncated = false;
+        let mut visited = HashSet::from([base_model_id.to_string()]);
+        let mut queue = VecDeque::from([(base_model_id.to_string(), 0)]);
+
+        while let Some((parent, depth)) = queue.pop_front() {
+            if depth >= self.max_depth {
+                continue;
+            }
+
+            // Ask for one more than the remaining budget to detect truncation
+            let remaining = self.max_descendants.saturating_sub(descendants.len());
+            let filter = format!("base_model:{}", parent);
+            let children = self
+                .model_analyzer
+                .fetch_model_list(&[("filter", filter.as_str())], remaining + 1)?;
+
+            for child in children {
+                if !visited.insert(child.id.clone()) {
+                    continue;
+                }
+                if descendants.len() >= self.max_descendants {
+                    truncated = true;
+                    break;
+                }
+
+                debug!("Found descendant: {} (of {})", child.id, parent);
+                queue.push_back((child.id.clone(), depth + 1));
+                descendants.push(DescendantEntry {
+                    relation: self.relation_to_parent(&child.tags, &parent),
+                    model_id: child.id,
+                    parent: parent.clone(),
+                    depth: depth + 1,
+                });
+            }
+
+            if truncated {
+                warn!(
+                    "Stopped after {} descendants of {}",
+                    self.max_descendants, base_model_id
+                );
+                break;
+            }
+        }
+
+        Ok(DescendantReport {
+            base_model: base_model_id.to_string(),
+            max_depth: self.max_depth,
+            descendants,
+            truncated,
+        })
+    }
+
+    /// Read the relation from a child's `base_model:<relation>:<parent>` tag
+    fn relation_to_parent(&self, tags: &[String], parent: &str) -> Option<String> {
+        tags.iter().find_map(|tag| {
+            let rest = tag.strip_prefix("base_model:")?;
+            let (kind, model) = rest.split_once(':')?;
+            if model.eq_ignore_ascii_case(parent) {
+                self.model_analyzer.relation_from_tag_kind(kind)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn reads_relation_from_the_tag_naming_the_parent() {
+        let finder = DescendantFinder::with_hub(HubClient::stub([]), 2, 10);
+        let child_tags = tags(&[
+            "base_model:org/base",
+            "base_model:finetune:org/other",
+            "base_model:quantized:Org/Base",
+        ]);
+
+        assert_eq!(
+            finder
+                .relation_to_parent(&child_tags, "org/base")
+                .as_deref(),
+            Some("quantized")
+        );
+        assert_eq!(
+            finder
+                .relation_to_parent(&child_tags, "org/other")
+                .as_deref(),
+            Some("finetuned")
+        );
+        assert_eq!(finder.relation_to_parent(&child_tags, "org/missing"), None);
+    }
+
+    #[test]
+    fn unknown_relation_kinds_are_left_unset() {
+        let finder = DescendantFinder::with_hub(HubClient::stub([]), 2, 10);
+        let child_tags = tags(&["base_model:mystery:org/base"]);
+
+        assert_eq!(finder.relation_to_parent(&child_tags, "org/base"), None);
+    }
+}
@@ -0,0 +1,618 @@
+//! Comparing two AIBOMs, e.g. before and after a base model or dataset update.
+//!
+//! Components are matched by purl with the version removed, so a new revision
+//! of the same model shows up as a version change rather than a removal plus
+//! an addition.
+
+use crate::formats::downgrade::flatten;
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+
+/// Categories of change, for deciding which ones should fail a CI check
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Version,
+    License,
+    Lineage,
+    ModelCard,
+}
+
+impl ChangeKind {
+    pub const ALL: [Self; 6] = [
+        Self::Added,
+        Self::Removed,
+        Self::Version,
+        Self::License,
+        Self::Lineage,
+        Self::ModelCard,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Version => "version",
+            Self::License => "license",
+            Self::Lineage => "lineage",
+            Self::ModelCard => "model-card",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.as_str() == kind)
+    }
+}
+
+/// A component present on only one side
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ComponentSummary {
+    /// Purl without version, or `type:name` for components without a purl
+    pub key: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Old and new value of a single field; `None` when absent on that side
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// A component present on both sides whose contents differ
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ComponentChange {
+    pub key: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<FieldChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub licenses: Option<LicenseChange>,
+    /// Changed model card fields, named by dotted path
+    #[serde(rename = "modelCard", skip_serializing_if = "Vec::is_empty")]
+    pub model_card: Vec<FieldChange>,
+}
+
+/// Sorted license ids or names before and after
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LicenseChange {
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+/// A dependency graph edge between component keys
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+}
+
+/// Everything that differs between two AIBOMs
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct AIBOMDiff {
+    pub added: Vec<ComponentSummary>,
+    pub removed: Vec<ComponentSummary>,
+    pub changed: Vec<ComponentChange>,
+    #[serde(rename = "edgesAdded")]
+    pub edges_added: Vec<Edge>,
+    #[serde(rename = "edgesRemoved")]
+    pub edges_removed: Vec<Edge>,
+}
+
+impl AIBOMDiff {
+    pub fn is_empty(&self) -> bool {
+        !ChangeKind::ALL.into_iter().any(|kind| self.has(kind))
+    }
+
+    /// Whether the diff contains a change of `kind`
+    pub fn has(&self, kind: ChangeKind) -> bool {
+        match kind {
+            ChangeKind::Added => !self.added.is_empty(),
+            ChangeKind::Removed => !self.removed.is_empty(),
+            ChangeKind::Version => self.changed.iter().any(|c| c.version.is_some()),
+            ChangeKind::License => self.changed.iter().any(|c| c.licenses.is_some()),
+            ChangeKind::Lineage => !self.edges_added.is_empty() || !self.edges_removed.is_empty(),
+            ChangeKind::ModelCard => self.changed.iter().any(|c| !c.model_card.is_empty()),
+        }
+    }
+
+    /// Plain-text listing with `+`/`-`/`~` markers
+    pub fn to_text(&self) -> String {
+        if self.is_empty() {
+            return "No changes\n".to_string();
+        }
+        let mut out = String::new();
+        if !self.added.is_empty() {
+            let _ = writeln!(out, "Added components ({}):", self.added.len());
+            for component in &self.added {
+                let _ = writeln!(out, "  + {}", summary_line(component));
+            }
+        }
+        if !self.removed.is_empty() {
+            let _ = writeln!(out, "Removed components ({}):", self.removed.len());
+            for component in &self.removed {
+                let _ = writeln!(out, "  - {}", summary_line(component));
+            }
+        }
+        if !self.changed.is_empty() {
+            let _ = writeln!(out, "Changed components ({}):", self.changed.len());
+            for change in &self.changed {
+                let _ = writeln!(out, "  ~ {}", change.key);
+                for field in change_fields(change) {
+                    let _ = writeln!(
+                        out,
+                        "      {}: {} -> {}",
+                        field.field,
+                        or_none(&field.before),
+                        or_none(&field.after)
+                    );
+                }
+            }
+        }
+        if self.has(ChangeKind::Lineage) {
+            let _ = writeln!(out, "Lineage edges:");
+            for edge in &self.edges_added {
+                let _ = writeln!(out, "  + {} -> {}", edge.from, edge.to);
+            }
+            for edge in &self.edges_removed {
+                let _ = writeln!(out, "  - {} -> {}", edge.from, edge.to);
+            }
+        }
+        out
+    }
+
+    /// Markdown sections with tables, e.g. for a pull request comment
+    pub fn to_markdown(&self) -> String {
+        if self.is_empty() {
+            return "No AIBOM changes.\n".to_string();
+        }
+        let mut out = String::from("## AIBOM changes\n");
+        for (title, components) in [("Added", &self.added), ("Removed", &self.removed)] {
+            if components.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "\n### {} components\n", title);
+            let _ = writeln!(out, "| Component | Name | Version |\n|---|---|---|");
+            for component in components {
+                let _ = writeln!(
+                    out,
+                    "| `{}` | {} | {} |",
+                    component.key,
+                    cell(&component.name),
+                    cell(or_none(&component.version))
+                );
+            }
+        }
+        if !self.changed.is_empty() {
+            let _ = writeln!(out, "\n### Changed components\n");
+            let _ = writeln!(
+                out,
+                "| Component | Field | Before | After |\n|---|---|---|---|"
+            );
+            for change in &self.changed {
+                for field in change_fields(change) {
+                    let _ = writeln!(
+                        out,
+                        "| `{}` | {} | {} | {} |",
+                        change.key,
+                        field.field,
+                        cell(or_none(&field.before)),
+                        cell(or_none(&field.after))
+                    );
+                }
+            }
+        }
+        if self.has(ChangeKind::Lineage) {
+            let _ = writeln!(out, "\n### Lineage edges\n");
+            let _ = writeln!(out, "| Change | From | To |\n|---|---|---|");
+            for (marker, edges) in [
+                ("added", &self.edges_added),
+                ("removed", &self.edges_removed),
+            ] {
+                for edge in edges {
+                    let _ = writeln!(out, "| {} | `{}` | `{}` |", marker, edge.from, edge.to);
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Compare `old` with `new`
+pub fn diff(old: &AIBOM, new: &AIBOM) -> AIBOMDiff {
+    let old_components = keyed_components(old);
+    let new_components = keyed_components(new);
+    let mut result = AIBOMDiff::default();
+
+    for (key, component) in &new_components {
+        match old_components.get(key) {
+            None => result.added.push(summary(key, component)),
+            Some(previous) => {
+                if let Some(change) = compare(key, previous, component) {
+                    result.changed.push(change);
+                }
+            }
+        }
+    }
+    for (key, component) in &old_components {
+        if !new_components.contains_key(key) {
+            result.removed.push(summary(key, component));
+        }
+    }
+
+    let old_edges = edges(old);
+    let new_edges = edges(new);
+    result.edges_added = new_edges.difference(&old_edges).cloned().collect();
+    result.edges_removed = old_edges.difference(&new_edges).cloned().collect();
+    result
+}
+
+/// Purl with the `@version` segment removed; qualifiers and subpath are kept
+pub fn purl_key(purl: &str) -> String {
+    let split = purl.find(['?', '#']).unwrap_or(purl.len());
+    let (path, rest) = purl.split_at(split);
+    let name_start = path.rfind('/').unwrap_or(0);
+    match path[name_start..].find('@') {
+        Some(at) => format!("{}{}", &path[..name_start + at], rest),
+        None => purl.to_string(),
+    }
+}
+
+fn component_key(component: &Component) -> String {
+    match &component.purl {
+        Some(purl) => purl_key(purl),
+        None => format!("{}:{}", component.component_type, component.name),
+    }
+}
+
+/// All components by key, including the metadata component and nested
+/// components; the first occurrence of a key wins
+fn keyed_components(aibom: &AIBOM) -> BTreeMap<String, &Component> {
+    fn walk<'a>(component: &'a Component, out: &mut BTreeMap<String, &'a Component>) {
+        out.entry(component_key(component)).or_insert(component);
+        for child in component.components.iter().flatten() {
+            walk(child, out);
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    walk(&aibom.metadata.component, &mut out);
+    for component in &aibom.components {
+        walk(component, &mut out);
+    }
+    out
+}
+
+/// Dependency edges with bom-refs replaced by component keys, so edges to a
+/// new revision of the same model compare equal
+fn edges(aibom: &AIBOM) -> BTreeSet<Edge> {
+    let keys: HashMap<&str, String> = keyed_components(aibom)
+        .into_iter()
+        .filter(|(_, c)| !c.bom_ref.is_empty())
+        .map(|(key, c)| (c.bom_ref.as_str(), key))
+        .collect();
+    let key_of = |reference: &str| {
+        keys.get(reference)
+            .cloned()
+            .unwrap_or_else(|| reference.to_string())
+    };
+
+    aibom
+        .dependencies
+        .iter()
+        .flat_map(|dependency| {
+            dependency.depends_on.iter().map(|target| Edge {
+                from: key_of(&dependency.reference),
+                to: key_of(target),
+            })
+        })
+        .collect()
+}
+
+fn summary(key: &str, component: &Component) -> ComponentSummary {
+    ComponentSummary {
+        key: key.to_string(),
+        name: component.name.clone(),
+        version: component.version.clone(),
+    }
+}
+
+fn compare(key: &str, old: &Component, new: &Component) -> Option<ComponentChange> {
+    let version = (old.version != new.version).then(|| FieldChange {
+        field: "version".to_string(),
+        before: old.version.clone(),
+        after: new.version.clone(),
+    });
+    let (before, after) = (licenses(old), licenses(new));
+    let licenses = (before != after).then_some(LicenseChange { before, after });
+    let model_card = model_card_changes(old, new);
+
+    if version.is_none() && licenses.is_none() && model_card.is_empty() {
+        return None;
+    }
+    Some(ComponentChange {
+        key: key.to_string(),
+        name: new.name.clone(),
+        version,
+        licenses,
+        model_card,
+    })
+}
+
+fn licenses(component: &Component) -> Vec<String> {
+    let mut licenses: Vec<String> = component
+        .licenses
+        .iter()
+        .flatten()
+        .filter_map(|l| l.license.id.clone().or_else(|| l.license.name.clone()))
+        .collect();
+    licenses.sort();
+    licenses.dedup();
+    licenses
+}
+
+fn model_card_fields(component: &Component) -> BTreeMap<String, String> {
+    let mut properties = Vec::new();
+    if let Some(card) = &component.model_card {
+        let card = serde_json::to_value(card).unwrap_or_default();
+        flatten("modelCard", &card, &mut properties);
+    }
+    properties.into_iter().map(|p| (p.name, p.value)).collect()
+}
+
+fn model_card_changes(old: &Component, new: &Component) -> Vec<FieldChange> {
+    let old_fields = model_card_fields(old);
+    let new_fields = model_card_fields(new);
+    let names: BTreeSet<&String> = old_fields.keys().chain(new_fields.keys()).collect();
+    names
+        .into_iter()
+        .filter(|name| old_fields.get(*name) != new_fields.get(*name))
+        .map(|name| FieldChange {
+            field: name.clone(),
+            before: old_fields.get(name).cloned(),
+            after: new_fields.get(name).cloned(),
+        })
+        .collect()
+}
+
+/// Version, license and model card changes as uniform field changes
+fn change_fields(change: &ComponentChange) -> Vec<FieldChange> {
+    let mut fields: Vec<FieldChange> = change.version.iter().cloned().collect();
+    if let Some(licenses) = &change.licenses {
+        fields.push(FieldChange {
+            field: "licenses".to_string(),
+            before: (!licenses.before.is_empty()).then(|| licenses.before.join(", ")),
+            after: (!licenses.after.is_empty()).then(|| licenses.after.join(", ")),
+        });
+    }
+    fields.extend(change.model_card.iter().cloned());
+    fields
+}
+
+fn summary_line(component: &ComponentSummary) -> String {
+    match &component.version {
+        Some(version) => format!("{} ({} {})", component.key, component.name, version),
+        None => format!("{} ({})", component.key, component.name),
+    }
+}
+
+fn or_none(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("(none)")
+}
+
+/// Escape a value for a Markdown table cell
+fn cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{BomFixture, dataset, model};
+    use serde_json::json;
+
+    /// `org/model` at `version`, trained on `dataset`
+    fn aibom(version: &str, license: &str, task: &str, dataset_name: &str) -> AIBOM {
+        let purl = format!("pkg:huggingface/org/model@{}", version);
+        let data = format!("pkg:huggingface-dataset/{}@1.0", dataset_name);
+        let mut tuned = model("org/model", &purl);
+        tuned["version"] = json!(version);
+        tuned["licenses"] = json!([{ "license": { "id": license } }]);
+        tuned["modelCard"] = json!({ "modelParameters": { "task": task } });
+        BomFixture::new()
+            .component(tuned)
+            .component(dataset(dataset_name, &data))
+            .dependency(&purl, &[&data])
+            .build()
+    }
+
+    fn edge(from: &str, to: &str) -> Edge {
+        Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn strips_only_the_version_from_purl_keys() {
+        assert_eq!(
+            purl_key("pkg:huggingface/org/model@abc?file=x#sub"),
+            "pkg:huggingface/org/model?file=x#sub"
+        );
+        assert_eq!(
+            purl_key("pkg:huggingface/org/model@abc?file=x"),
+            purl_key("pkg:huggingface/org/model@def?file=x")
+        );
+        assert_ne!(
+            purl_key("pkg:huggingface/org/model@abc?file=x"),
+            purl_key("pkg:huggingface/org/model@abc?file=y")
+        );
+        // An @ in a qualifier value is not a version separator
+        assert_eq!(
+            purl_key("pkg:generic/model?download_url=https://user@host/model"),
+            "pkg:generic/model?download_url=https://user@host/model"
+        );
+    }
+
+    #[test]
+    fn finds_no_changes_between_identical_documents() {
+        let old = aibom("abc", "MIT", "text-generation", "train");
+        let changes = diff(&old, &old);
+        assert!(changes.is_empty());
+        assert_eq!(changes.to_text(), "No changes\n");
+        assert_eq!(changes.to_markdown(), "No AIBOM changes.\n");
+
+        let empty = BomFixture::new().build();
+        assert!(diff(&empty, &empty).is_empty());
+    }
+
+    #[test]
+    fn lists_every_component_of_a_new_document_as_added() {
+        let changes = diff(
+            &BomFixture::new().build(),
+            &aibom("abc", "MIT", "text-generation", "train"),
+        );
+        let added: Vec<&str> = changes.added.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(
+            added,
+            ["pkg:huggingface-dataset/train", "pkg:huggingface/org/model"]
+        );
+        assert!(changes.removed.is_empty() && changes.changed.is_empty());
+        assert_eq!(
+            changes.edges_added,
+            [edge(
+                "pkg:huggingface/org/model",
+                "pkg:huggingface-dataset/train"
+            )]
+        );
+    }
+
+    #[test]
+    fn matches_components_by_purl_ignoring_version() {
+        let old = aibom("abc", "MIT", "text-generation", "train");
+        let new = aibom("def", "MIT", "text-generation", "train");
+        let changes = diff(&old, &new);
+
+        assert!(changes.added.is_empty() && changes.removed.is_empty());
+        assert_eq!(
+            changes.changed[0].version,
+            Some(FieldChange {
+                field: "version".to_string(),
+                before: Some("abc".to_string()),
+                after: Some("def".to_string()),
+            })
+        );
+        assert!(!changes.has(ChangeKind::Lineage));
+    }
+
+    #[test]
+    fn matches_components_without_purl_by_type_and_name() {
+        let component = |version: &str| json!({ "type": "file", "bom-ref": "f", "name": "modeling.py", "version": version });
+        let old = BomFixture::new().component(component("1")).build();
+        let new = BomFixture::new().component(component("2")).build();
+
+        let changes = diff(&old, &new);
+        assert_eq!(changes.changed[0].key, "file:modeling.py");
+        assert!(changes.has(ChangeKind::Version));
+    }
+
+    #[test]
+    fn reports_license_changes_regardless_of_order() {
+        let licensed = |licenses: Value| {
+            let mut tuned = model("org/model", "pkg:huggingface/org/model@abc");
+            tuned["licenses"] = licenses;
+            BomFixture::new().component(tuned).build()
+        };
+        let mit_and_llama = licensed(json!([
+            { "license": { "id": "MIT" } },
+            { "license": { "name": "llama3" } }
+        ]));
+        let llama_and_mit = licensed(json!([
+            { "license": { "name": "llama3" } },
+            { "license": { "id": "MIT" } }
+        ]));
+        assert!(diff(&mit_and_llama, &llama_and_mit).is_empty());
+
+        let changes = diff(&mit_and_llama, &licensed(json!([])));
+        assert_eq!(
+            changes.changed[0].licenses,
+            Some(LicenseChange {
+                before: vec!["MIT".to_string(), "llama3".to_string()],
+                after: Vec::new(),
+            })
+        );
+        assert!(
+            changes
+                .to_text()
+                .contains("licenses: MIT, llama3 -> (none)")
+        );
+    }
+
+    #[test]
+    fn reports_model_card_fields_by_path() {
+        let old = aibom("abc", "MIT", "text-generation", "train");
+        let new = aibom("abc", "MIT", "summarization", "train");
+
+        let changes = diff(&old, &new);
+        assert_eq!(
+            changes.changed[0].model_card,
+            [FieldChange {
+                field: "modelCard.modelParameters.task".to_string(),
+                before: Some("text-generation".to_string()),
+                after: Some("summarization".to_string()),
+            }]
+        );
+        let kinds: Vec<ChangeKind> = ChangeKind::ALL
+            .into_iter()
+            .filter(|kind| changes.has(*kind))
+            .collect();
+        assert_eq!(kinds, [ChangeKind::ModelCard]);
+    }
+
+    #[test]
+    fn reports_replaced_datasets_as_component_and_edge_changes() {
+        let old = aibom("abc", "MIT", "text-generation", "train");
+        let new = aibom("abc", "MIT", "text-generation", "train-v2");
+
+        let changes = diff(&old, &new);
+        assert_eq!(changes.added[0].key, "pkg:huggingface-dataset/train-v2");
+        assert_eq!(changes.removed[0].key, "pkg:huggingface-dataset/train");
+        assert_eq!(
+            changes.edges_added,
+            [edge(
+                "pkg:huggingface/org/model",
+                "pkg:huggingface-dataset/train-v2"
+            )]
+        );
+        assert_eq!(
+            changes.edges_removed,
+            [edge(
+                "pkg:huggingface/org/model",
+                "pkg:huggingface-dataset/train"
+            )]
+        );
+        assert!(changes.changed.is_empty());
+    }
+
+    #[test]
+    fn renders_markdown_tables_with_escaped_cells() {
+        let old = aibom("abc", "MIT", "text-generation", "train");
+        let new = aibom("def", "Apache-2.0", "a | b", "train");
+
+        let markdown = diff(&old, &new).to_markdown();
+        for row in [
+            "| `pkg:huggingface/org/model` | version | abc | def |",
+            "| `pkg:huggingface/org/model` | licenses | MIT | Apache-2.0 |",
+            "| `pkg:huggingface/org/model` | modelCard.modelParameters.task | text-generation | a \\| b |",
+        ] {
+            assert!(markdown.contains(row), "missing {:?} in {}", row, markdown);
+        }
+    }
+}
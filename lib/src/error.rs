@@ -0,0 +1,108 @@
+use std::fmt;
+
+/// Errors raised while generating an AIBOM
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenerateError {
+    /// The repository or file does not exist (or is private)
+    NotFound(String),
+    /// The repository is gated and the caller has not been granted access
+    Gated(String),
+    /// The Hub rejected the request because of rate limiting
+    RateLimited(String),
+    /// Transport failure or unexpected HTTP status
+    Network(String),
+    /// A response or file could not be parsed
+    Parse(String),
+    /// A traversal limit stopped processing
+    LimitExceeded(String),
+    /// The rendered BOM does not conform to its CycloneDX schema
+    Invalid(String),
+}
+
+impl GenerateError {
+    /// Classify a failed Hub response. The Hub answers 401 for both missing and
+    /// gated repositories, so its `X-Error-Code` header takes precedence.
+    pub fn from_status(status: u16, error_code: Option<&str>, context: &str) -> Self {
+        let message = format!("{} (status {})", context, status);
+        match (error_code, status) {
+            (Some("RepoNotFound") | Some("EntryNotFound") | Some("RevisionNotFound"), _) => {
+                Self::NotFound(message)
+            }
+            (Some("GatedRepo"), _) => Self::Gated(message),
+            (_, 404) => Self::NotFound(message),
+            (_, 401) | (_, 403) => Self::Gated(message),
+            (_, 429) => Self::RateLimited(message),
+            _ => Self::Network(message),
+        }
+    }
+
+    /// Short machine-readable name of the error kind
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "not-found",
+            Self::Gated(_) => "gated",
+            Self::RateLimited(_) => "rate-limited",
+            Self::Network(_) => "network",
+            Self::Parse(_) => "parse",
+            Self::LimitExceeded(_) => "limit-exceeded",
+            Self::Invalid(_) => "invalid",
+        }
+    }
+
+    /// Detail message without the kind prefix
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotFound(m)
+            | Self::Gated(m)
+            | Self::RateLimited(m)
+            | Self::Network(m)
+            | Self::Parse(m)
+            | Self::LimitExceeded(m)
+            | Self::Invalid(m) => m,
+        }
+    }
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(m) => write!(f, "not found: {}", m),
+            Self::Gated(m) => write!(f, "gated repository: {}", m),
+            Self::RateLimited(m) => write!(f, "rate limited: {}", m),
+            Self::Network(m) => write!(f, "network error: {}", m),
+            Self::Parse(m) => write!(f, "parse error: {}", m),
+            Self::LimitExceeded(m) => write!(f, "limit exceeded: {}", m),
+            Self::Invalid(m) => write!(f, "invalid BOM: {}", m),
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+impl From<reqwest::Error> for GenerateError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_decode() {
+            Self::Parse(e.to_string())
+        } else {
+            Self::Network(e.to_string())
+        }
+    }
+}
+
+impl From<serde_json::Error> for GenerateError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Parse(e.to_string())
+    }
+}
+
+impl From<serde_yaml::Error> for GenerateError {
+    fn from(e: serde_yaml::Error) -> Self {
+        Self::Parse(e.to_string())
+    }
+}
+
+impl From<hf_hub::api::sync::ApiError> for GenerateError {
+    fn from(e: hf_hub::api::sync::ApiError) -> Self {
+        Self::Network(e.to_string())
+    }
+}
@@ -0,0 +1,463 @@
+//! Rewriting AIBOMs for older CycloneDX spec versions.
+//!
+//! Fields a target version does not know are moved into `aibom:downgrade:`
+//! properties where possible, and every change is reported as a
+//! [`DowngradeNote`].
+
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Prefix of properties holding values the target spec version has no field for
+pub const PROPERTY_PREFIX: &str = "aibom:downgrade:";
+
+/// CycloneDX spec versions AIBOMs can be rendered in
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum SpecVersion {
+    /// No `modelCard`, `machine-learning-model` or `data` components
+    #[serde(rename = "1.4")]
+    V1_4,
+    #[serde(rename = "1.5")]
+    V1_5,
+    #[default]
+    #[serde(rename = "1.6")]
+    V1_6,
+}
+
+impl SpecVersion {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::V1_4 => "1.4",
+            Self::V1_5 => "1.5",
+            Self::V1_6 => "1.6",
+        }
+    }
+
+    /// Version named by a `specVersion` value, if supported
+    pub fn parse(version: &str) -> Option<Self> {
+        [Self::V1_4, Self::V1_5, Self::V1_6]
+            .into_iter()
+            .find(|v| v.as_str() == version)
+    }
+}
+
+/// External reference types added in 1.5
+const REFERENCE_TYPES_1_5: &[&str] = &[
+    "distribution-intake",
+    "security-contact",
+    "model-card",
+    "log",
+    "configuration",
+    "evidence",
+    "formulation",
+    "attestation",
+    "threat-model",
+    "adversary-model",
+    "risk-assessment",
+    "vulnerability-assertion",
+    "exploitability-statement",
+    "pentest-report",
+    "static-analysis-report",
+    "dynamic-analysis-report",
+    "runtime-analysis-report",
+    "component-analysis-report",
+    "maturity-report",
+    "certification-report",
+    "codified-infrastructure",
+    "quality-metrics",
+    "poam",
+];
+
+/// External reference types added in 1.6
+const REFERENCE_TYPES_1_6: &[&str] = &[
+    "source-distribution",
+    "electronic-signature",
+    "digital-signature",
+    "rfc-9116",
+];
+
+/// Component types added in 1.5, with the 1.4 type used in their place
+const COMPONENT_TYPES_1_5: &[(&str, &str)] = &[
+    ("machine-learning-model", "library"),
+    ("data", "file"),
+    ("platform", "library"),
+    ("device-driver", "device"),
+];
+
+/// Copy of `aibom` restricted to what `target` can represent, with a note for
+/// every field that was rewritten
+pub fn downgrade(aibom: &AIBOM, target: SpecVersion) -> (AIBOM, Vec<DowngradeNote>) {
+    let mut aibom = aibom.clone();
+    let mut notes = Vec::new();
+    let source = SpecVersion::parse(&aibom.spec_version).unwrap_or_default();
+    if target >= source {
+        return (aibom, notes);
+    }
+
+    let mut downgrader = Downgrader {
+        target,
+        notes: &mut notes,
+    };
+    downgrader.component(&mut aibom.metadata.component);
+    for component in &mut aibom.components {
+        downgrader.component(component);
+    }
+    if let Some(references) = &mut aibom.external_references {
+        downgrader.references("", references);
+    }
+    if target == SpecVersion::V1_4 {
+        // 1.4 lists tools as `{vendor, name, version}`; see `legacy_tools`
+        for tool in &aibom.metadata.tools.components {
+            downgrader.note(&tool.bom_ref, "metadata.tools", "mapped");
+        }
+    }
+    aibom.spec_version = target.as_str().to_string();
+
+    (aibom, notes)
+}
+
+/// `metadata.tools` in the pre-1.5 array form
+pub(crate) fn legacy_tools(tools: &Tools) -> Value {
+    Value::Array(
+        tools
+            .components
+            .iter()
+            .map(|tool| {
+                serde_json::json!({
+                    "vendor": tool.manufacturer.name,
+                    "name": tool.name,
+                    "version": tool.version,
+                })
+            })
+            .collect(),
+    )
+}
+
+struct Downgrader<'a> {
+    target: SpecVersion,
+    notes: &'a mut Vec<DowngradeNote>,
+}
+
+impl Downgrader<'_> {
+    fn note(&mut self, bom_ref: &str, field: &str, action: &str) {
+        self.notes.push(DowngradeNote {
+            bom_ref: bom_ref.to_string(),
+            field: field.to_string(),
+            action: action.to_string(),
+        });
+    }
+
+    fn component(&mut self, component: &mut Component) {
+        let bom_ref = component.bom_ref.clone();
+        let mut mapped = Vec::new();
+
+        // Added in 1.6
+        if let Some(authors) = component.authors.take() {
+            for author in authors {
+                mapped.push(property("authors", author.name));
+            }
+            self.note(&bom_ref, "authors", "mapped");
+        }
+        if let Some(manufacturer) = component.manufacturer.take() {
+            mapped.push(property("manufacturer", manufacturer.name));
+            self.note(&bom_ref, "manufacturer", "mapped");
+        }
+        for license in component.licenses.iter_mut().flatten() {
+            let license = &mut license.license;
+            if let Some(acknowledgement) = license.acknowledgement.take() {
+                let moved = property("license.acknowledgement", acknowledgement);
+                // License properties arrived in 1.5
+                if self.target == SpecVersion::V1_4 {
+                    mapped.push(moved);
+                } else {
+                    license.properties.get_or_insert_with(Vec::new).push(moved);
+                }
+                self.note(&bom_ref, "licenses.acknowledgement", "mapped");
+            }
+            if self.target == SpecVersion::V1_4
+                && let Some(properties) = license.properties.take()
+            {
+                mapped.extend(moved_properties("license.properties", properties));
+                self.note(&bom_ref, "licenses.properties", "mapped");
+            }
+        }
+        if let Some(references) = &mut component.external_references {
+            self.references(&bom_ref, references);
+        }
+
+        if self.target == SpecVersion::V1_4 {
+            if let Some((_, legacy)) = COMPONENT_TYPES_1_5
+                .iter()
+                .find(|(t, _)| *t == component.component_type)
+            {
+                let original = std::mem::replace(&mut component.component_type, legacy.to_string());
+                mapped.push(property("type", original));
+                self.note(&bom_ref, "type", "mapped");
+            }
+            if let Some(mut model_card) = component.model_card.take() {
+                // Flattening would name them by index rather than by property name
+                if let Some(properties) = model_card.properties.take() {
+                    mapped.extend(moved_properties("modelCard.properties", properties));
+                    self.note(&bom_ref, "modelCard.properties", "mapped");
+                }
+                let card = serde_json::to_value(&model_card).unwrap_or_default();
+                flatten(&format!("{}modelCard", PROPERTY_PREFIX), &card, &mut mapped);
+                self.note(&bom_ref, "modelCard", "mapped");
+            }
+        }
+
+        if !mapped.is_empty() {
+            component
+                .properties
+                .get_or_insert_with(Vec::new)
+                .extend(mapped);
+        }
+
+        for child in component.components.iter_mut().flatten() {
+            self.component(child);
+        }
+        if let Some(pedigree) = &mut component.pedigree {
+            for related in [
+                &mut pedigree.ancestors,
+                &mut pedigree.descendants,
+                &mut pedigree.variants,
+            ] {
+                for related in related.iter_mut().flatten() {
+                    self.component(related);
+                }
+            }
+        }
+    }
+
+    /// Replace reference types the target does not know with `other`, keeping
+    /// the original type in the comment
+    fn references(&mut self, bom_ref: &str, references: &mut [ExternalReference]) {
+        for reference in references {
+            let unsupported = REFERENCE_TYPES_1_6.contains(&reference.ref_type.as_str())
+                || (self.target == SpecVersion::V1_4
+                    && REFERENCE_TYPES_1_5.contains(&reference.ref_type.as_str()));
+            if !unsupported {
+                continue;
+            }
+            let original = std::mem::replace(&mut reference.ref_type, "other".to_string());
+            reference.comment = Some(match reference.comment.take() {
+                Some(comment) => format!("{}: {}", original, comment),
+                None => original,
+            });
+            self.note(bom_ref, "externalReferences.type", "mapped");
+        }
+    }
+}
+
+fn property(name: &str, value: String) -> Property {
+    Property {
+        name: format!("{}{}", PROPERTY_PREFIX, name),
+        value,
+    }
+}
+
+/// Properties moved out of `field`, renamed to `aibom:downgrade:<field>.<name>`
+fn moved_properties(field: &str, properties: Vec<Property>) -> impl Iterator<Item = Property> {
+    properties
+        .into_iter()
+        .map(move |p| property(&format!("{}.{}", field, p.name), p.value))
+}
+
+/// Flatten a JSON value into properties named by dotted paths; array elements
+/// are addressed by index
+pub(crate) fn flatten(path: &str, value: &Value, out: &mut Vec<Property>) {
+    match value {
+        Value::Null => {}
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten(&format!("{}.{}", path, key), value, out);
+            }
+        }
+        Value::Array(items) => {
+            for (index, value) in items.iter().enumerate() {
+                flatten(&format!("{}.{}", path, index), value, out);
+            }
+        }
+        Value::String(text) => out.push(Property {
+            name: path.to_string(),
+            value: text.clone(),
+        }),
+        other => out.push(Property {
+            name: path.to_string(),
+            value: other.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::BomFixture;
+    use serde_json::json;
+
+    /// A 1.6 document with one model using fields added in 1.5 and 1.6
+    fn aibom() -> AIBOM {
+        BomFixture::new()
+            .component(json!({
+                "type": "machine-learning-model",
+                "bom-ref": "model",
+                "name": "model",
+                "authors": [{ "name": "org" }],
+                "licenses": [{ "license": { "id": "MIT", "acknowledgement": "declared" } }],
+                "modelCard": {
+                    "modelParameters": {
+                        "task": "text-generation",
+                        "datasets": [{ "ref": "train" }]
+                    },
+                    "properties": [{ "name": "ai.peft.type", "value": "LORA" }]
+                }
+            }))
+            .build()
+    }
+
+    fn properties(component: &Component) -> Vec<(&str, &str)> {
+        component
+            .properties
+            .iter()
+            .flatten()
+            .map(|p| (p.name.as_str(), p.value.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_model_cards_for_1_5() {
+        let aibom = aibom();
+        validate_aibom(&aibom).unwrap();
+
+        let (v1_5, notes) = downgrade(&aibom, SpecVersion::V1_5);
+        validate_aibom(&v1_5).unwrap();
+        assert_eq!(v1_5.spec_version, "1.5");
+        let model = &v1_5.components[0];
+        let card = model.model_card.as_ref().unwrap();
+        assert_eq!(card.properties.as_ref().unwrap()[0].name, "ai.peft.type");
+        assert_eq!(properties(model), [("aibom:downgrade:authors", "org")]);
+        let license = &model.licenses.as_ref().unwrap()[0].license;
+        assert_eq!(license.acknowledgement, None);
+        assert_eq!(
+            license.properties.as_ref().unwrap()[0].name,
+            "aibom:downgrade:license.acknowledgement"
+        );
+        let fields: Vec<&str> = notes.iter().map(|n| n.field.as_str()).collect();
+        assert_eq!(fields, ["authors", "licenses.acknowledgement"]);
+    }
+
+    #[test]
+    fn maps_model_cards_and_new_types_into_properties_for_1_4() {
+        let (v1_4, notes) = downgrade(&aibom(), SpecVersion::V1_4);
+        validate_aibom(&v1_4).unwrap();
+        let model = &v1_4.components[0];
+        assert_eq!(model.component_type, "library");
+        assert!(model.model_card.is_none());
+        let properties = properties(model);
+        for expected in [
+            ("aibom:downgrade:authors", "org"),
+            ("aibom:downgrade:license.acknowledgement", "declared"),
+            ("aibom:downgrade:type", "machine-learning-model"),
+            ("aibom:downgrade:modelCard.properties.ai.peft.type", "LORA"),
+            (
+                "aibom:downgrade:modelCard.modelParameters.task",
+                "text-generation",
+            ),
+            (
+                "aibom:downgrade:modelCard.modelParameters.datasets.0.ref",
+                "train",
+            ),
+        ] {
+            assert!(properties.contains(&expected), "missing {:?}", expected);
+        }
+        let fields: Vec<&str> = notes.iter().map(|n| n.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "authors",
+                "licenses.acknowledgement",
+                "type",
+                "modelCard.properties",
+                "modelCard"
+            ]
+        );
+    }
+
+    #[test]
+    fn moves_license_properties_into_component_properties_for_1_4() {
+        let aibom = BomFixture::new()
+            .component(json!({
+                "type": "library",
+                "bom-ref": "lib",
+                "name": "lib",
+                "licenses": [{ "license": {
+                    "name": "Gated access terms",
+                    "properties": [{ "name": "ai.access.termsSha256", "value": "abc" }]
+                }}]
+            }))
+            .build();
+
+        let (v1_4, notes) = downgrade(&aibom, SpecVersion::V1_4);
+        validate_aibom(&v1_4).unwrap();
+        let component = &v1_4.components[0];
+        assert!(
+            component.licenses.as_ref().unwrap()[0]
+                .license
+                .properties
+                .is_none()
+        );
+        assert_eq!(
+            properties(component),
+            [(
+                "aibom:downgrade:license.properties.ai.access.termsSha256",
+                "abc"
+            )]
+        );
+        assert_eq!(notes[0].field, "licenses.properties");
+    }
+
+    #[test]
+    fn rewrites_unknown_reference_types_as_other() {
+        let aibom = BomFixture::new()
+            .set(
+                "externalReferences",
+                json!([
+                    { "type": "model-card", "url": "https://example.com/card" },
+                    { "type": "rfc-9116", "url": "https://example.com/security.txt", "comment": "contact" }
+                ]),
+            )
+            .build();
+
+        let (v1_5, _) = downgrade(&aibom, SpecVersion::V1_5);
+        let references = v1_5.external_references.unwrap();
+        assert_eq!(references[0].ref_type, "model-card");
+        assert_eq!(references[1].ref_type, "other");
+        assert_eq!(references[1].comment.as_deref(), Some("rfc-9116: contact"));
+
+        let (v1_4, notes) = downgrade(&aibom, SpecVersion::V1_4);
+        let references = v1_4.external_references.unwrap();
+        assert_eq!(references[0].ref_type, "other");
+        assert_eq!(references[0].comment.as_deref(), Some("model-card"));
+        assert_eq!(notes.len(), 2);
+    }
+
+    #[test]
+    fn changes_only_the_version_of_an_empty_document() {
+        let (v1_4, notes) = downgrade(&BomFixture::new().build(), SpecVersion::V1_4);
+        validate_aibom(&v1_4).unwrap();
+        assert_eq!(v1_4.spec_version, "1.4");
+        assert!(v1_4.metadata.component.properties.is_none());
+        assert!(notes.is_empty());
+    }
+
+    #[test]
+    fn never_upgrades() {
+        let legacy = BomFixture::new()
+            .spec_version("1.4")
+            .component(json!({ "type": "library", "bom-ref": "lib", "name": "lib" }))
+            .build();
+
+        let (unchanged, notes) = downgrade(&legacy, SpecVersion::V1_6);
+        assert_eq!(unchanged.spec_version, "1.4");
+        assert!(notes.is_empty());
+    }
+}
@@ -0,0 +1,81 @@
+pub mod downgrade;
+pub mod protobuf;
+pub mod spdx3;
+pub mod xml;
+
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Document formats an AIBOM can be rendered in
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    /// CycloneDX JSON
+    #[default]
+    CyclonedxJson,
+    /// CycloneDX XML
+    CyclonedxXml,
+    /// SPDX 3.0 JSON-LD with the AI and Dataset profiles
+    Spdx3Json,
+}
+
+impl OutputFormat {
+    /// IANA media type of documents in this format
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::CyclonedxJson => "application/vnd.cyclonedx+json",
+            Self::CyclonedxXml => "application/vnd.cyclonedx+xml",
+            Self::Spdx3Json => "application/spdx+json",
+        }
+    }
+
+    /// Format whose media type is `media_type`, ignoring parameters such as
+    /// `; version=1.6`
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let essence = media_type.split(';').next()?.trim();
+        [Self::CyclonedxJson, Self::CyclonedxXml, Self::Spdx3Json]
+            .into_iter()
+            .find(|format| format.media_type().eq_ignore_ascii_case(essence))
+    }
+
+    /// Whether this is a CycloneDX format, and so subject to
+    /// [`OutputOptions::spec_version`](crate::OutputOptions::spec_version)
+    pub fn is_cyclonedx(self) -> bool {
+        matches!(self, Self::CyclonedxJson | Self::CyclonedxXml)
+    }
+
+    /// Whether documents in this format are JSON
+    pub fn is_json(self) -> bool {
+        !matches!(self, Self::CyclonedxXml)
+    }
+}
+
+/// Convert an AIBOM to the JSON document of the given format. Non-JSON
+/// formats are returned as a string holding the rendered document.
+pub fn to_json_value(aibom: &AIBOM, format: OutputFormat) -> Result<Value, GenerateError> {
+    match format {
+        OutputFormat::CyclonedxJson => {
+            let mut document = serde_json::to_value(aibom)?;
+            if aibom.spec_version == SpecVersion::V1_4.as_str() {
+                document["metadata"]["tools"] = downgrade::legacy_tools(&aibom.metadata.tools);
+            }
+            Ok(document)
+        }
+        OutputFormat::CyclonedxXml => Ok(Value::String(xml::to_xml(aibom, true))),
+        OutputFormat::Spdx3Json => Ok(spdx3::to_spdx3(aibom)),
+    }
+}
+
+/// Render an AIBOM in the given format
+pub fn render(aibom: &AIBOM, format: OutputFormat, pretty: bool) -> Result<String, GenerateError> {
+    if format == OutputFormat::CyclonedxXml {
+        return Ok(xml::to_xml(aibom, pretty));
+    }
+    let document = to_json_value(aibom, format)?;
+    let output = if pretty {
+        serde_json::to_string_pretty(&document)?
+    } else {
+        serde_json::to_string(&document)?
+    };
+    Ok(output)
+}
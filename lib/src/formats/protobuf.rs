@@ -0,0 +1,995 @@
+//! CycloneDX 1.6 Protocol Buffers encoding.
+//!
+//! The messages in [`pb`] mirror `bom-1.6.proto` (package `cyclonedx.v1_6`),
+//! restricted to the fields an AIBOM uses. Field numbers are those of the
+//! official schema, so documents can be read by any CycloneDX protobuf
+//! consumer. Enumerations are kept as raw `int32` values and mapped to their
+//! JSON spellings through the tables below.
+
+use crate::*;
+use chrono::{DateTime, SecondsFormat, Utc};
+use prost::Message;
+
+/// Encode an AIBOM as a CycloneDX protobuf `Bom` message
+pub fn to_protobuf(aibom: &AIBOM) -> Vec<u8> {
+    bom_to_proto(aibom).encode_to_vec()
+}
+
+/// Decode a CycloneDX protobuf `Bom` message
+pub fn from_protobuf(bytes: &[u8]) -> Result<AIBOM, GenerateError> {
+    let bom = pb::Bom::decode(bytes)
+        .map_err(|e| GenerateError::Parse(format!("Invalid CycloneDX protobuf: {}", e)))?;
+    Ok(bom_from_proto(bom))
+}
+
+/// `Classification` values of `bom-1.6.proto`
+const CLASSIFICATIONS: &[(&str, i32)] = &[
+    ("application", 1),
+    ("framework", 2),
+    ("library", 3),
+    ("operating-system", 4),
+    ("device", 5),
+    ("file", 6),
+    ("container", 7),
+    ("firmware", 8),
+    ("device-driver", 9),
+    ("platform", 10),
+    ("machine-learning-model", 11),
+    ("data", 12),
+    ("cryptographic-asset", 13),
+];
+
+/// `HashAlg` values of `bom-1.6.proto`
+const HASH_ALGORITHMS: &[(&str, i32)] = &[
+    ("MD5", 1),
+    ("SHA-1", 2),
+    ("SHA-256", 3),
+    ("SHA-384", 4),
+    ("SHA-512", 5),
+    ("SHA3-256", 6),
+    ("SHA3-384", 7),
+    ("SHA3-512", 8),
+    ("BLAKE2b-256", 9),
+    ("BLAKE2b-384", 10),
+    ("BLAKE2b-512", 11),
+    ("BLAKE3", 12),
+];
+
+/// `ExternalReferenceType` values of `bom-1.6.proto`; unknown types become `other`
+const EXTERNAL_REFERENCE_TYPES: &[(&str, i32)] = &[
+    ("other", 0),
+    ("vcs", 1),
+    ("issue-tracker", 2),
+    ("website", 3),
+    ("advisories", 4),
+    ("bom", 5),
+    ("mailing-list", 6),
+    ("social", 7),
+    ("chat", 8),
+    ("documentation", 9),
+    ("support", 10),
+    ("distribution", 11),
+    ("license", 12),
+    ("build-meta", 13),
+    ("build-system", 14),
+    ("release-notes", 15),
+    ("security-contact", 16),
+    ("model-card", 17),
+    ("log", 18),
+    ("configuration", 19),
+    ("evidence", 20),
+    ("formulation", 21),
+    ("attestation", 22),
+    ("threat-model", 23),
+    ("adversary-model", 24),
+    ("risk-assessment", 25),
+    ("vulnerability-assertion", 26),
+    ("exploitability-statement", 27),
+    ("pentest-report", 28),
+    ("static-analysis-report", 29),
+    ("dynamic-analysis-report", 30),
+    ("runtime-analysis-report", 31),
+    ("component-analysis-report", 32),
+    ("maturity-report", 33),
+    ("certification-report", 34),
+    ("quality-metrics", 35),
+    ("codified-infrastructure", 36),
+    ("poam", 37),
+    ("distribution-intake", 38),
+    ("electronic-signature", 39),
+    ("digital-signature", 40),
+    ("rfc-9116", 41),
+];
+
+/// `LicenseAcknowledgementEnumeration` values of `bom-1.6.proto`
+const LICENSE_ACKNOWLEDGEMENTS: &[(&str, i32)] = &[("declared", 1), ("concluded", 2)];
+
+fn enum_value(table: &[(&str, i32)], name: &str) -> i32 {
+    table
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map_or(0, |(_, v)| *v)
+}
+
+fn enum_name(table: &[(&str, i32)], value: i32, fallback: &str) -> String {
+    table
+        .iter()
+        .find(|(_, v)| *v == value)
+        .map_or(fallback, |(n, _)| n)
+        .to_string()
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn non_empty_vec<T>(values: Vec<T>) -> Option<Vec<T>> {
+    (!values.is_empty()).then_some(values)
+}
+
+fn bom_to_proto(aibom: &AIBOM) -> pb::Bom {
+    pb::Bom {
+        spec_version: aibom.spec_version.clone(),
+        version: Some(aibom.version as i32),
+        serial_number: Some(aibom.serial_number.clone()),
+        metadata: Some(metadata_to_proto(&aibom.metadata)),
+        components: aibom.components.iter().map(component_to_proto).collect(),
+        external_references: references_to_proto(aibom.external_references.as_deref()),
+        dependencies: aibom
+            .dependencies
+            .iter()
+            .map(|dependency| pb::Dependency {
+                r#ref: dependency.reference.clone(),
+                dependencies: dependency
+                    .depends_on
+                    .iter()
+                    .map(|target| pb::Dependency {
+                        r#ref: target.clone(),
+                        dependencies: Vec::new(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+fn bom_from_proto(bom: pb::Bom) -> AIBOM {
+    let metadata = bom.metadata.unwrap_or_default();
+    AIBOM {
+        bom_format: "CycloneDX".to_string(),
+        spec_version: bom.spec_version,
+        serial_number: bom.serial_number.unwrap_or_default(),
+        version: bom.version.unwrap_or(1).max(0) as u32,
+        metadata: metadata_from_proto(metadata),
+        components: bom
+            .components
+            .into_iter()
+            .map(component_from_proto)
+            .collect(),
+        dependencies: bom
+            .dependencies
+            .into_iter()
+            .map(|dependency| Dependency {
+                reference: dependency.r#ref,
+                depends_on: dependency
+                    .dependencies
+                    .into_iter()
+                    .map(|target| target.r#ref)
+                    .collect(),
+            })
+            .collect(),
+        external_references: references_from_proto(bom.external_references),
+        signature: None,
+    }
+}
+
+fn metadata_to_proto(metadata: &Metadata) -> pb::Metadata {
+    let timestamp = DateTime::parse_from_rfc3339(&metadata.timestamp)
+        .ok()
+        .map(|time| prost_types::Timestamp {
+            seconds: time.timestamp(),
+            nanos: time.timestamp_subsec_nanos() as i32,
+        });
+    let tools = metadata
+        .tools
+        .components
+        .iter()
+        .map(|tool| pb::Component {
+            r#type: enum_value(CLASSIFICATIONS, &tool.component_type),
+            bom_ref: non_empty(&tool.bom_ref),
+            name: tool.name.clone(),
+            version: tool.version.clone(),
+            manufacturer: Some(organization_to_proto(&tool.manufacturer)),
+            ..Default::default()
+        })
+        .collect();
+
+    pb::Metadata {
+        timestamp,
+        tools: Some(pb::Tool { components: tools }),
+        component: Some(component_to_proto(&metadata.component)),
+        properties: properties_to_proto(metadata.properties.as_deref()),
+    }
+}
+
+fn metadata_from_proto(metadata: pb::Metadata) -> Metadata {
+    let timestamp = metadata
+        .timestamp
+        .and_then(|t| DateTime::<Utc>::from_timestamp(t.seconds, t.nanos.max(0) as u32))
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::AutoSi, false))
+        .unwrap_or_default();
+    let tools = metadata
+        .tools
+        .unwrap_or_default()
+        .components
+        .into_iter()
+        .map(|tool| ToolComponent {
+            bom_ref: tool.bom_ref.unwrap_or_default(),
+            manufacturer: tool
+                .manufacturer
+                .map(organization_from_proto)
+                .unwrap_or_else(|| Organization {
+                    name: String::new(),
+                    url: None,
+                }),
+            name: tool.name,
+            component_type: enum_name(CLASSIFICATIONS, tool.r#type, "application"),
+            version: tool.version,
+        })
+        .collect();
+
+    Metadata {
+        timestamp,
+        tools: Tools { components: tools },
+        component: component_from_proto(metadata.component.unwrap_or_default()),
+        properties: properties_from_proto(metadata.properties),
+    }
+}
+
+fn component_to_proto(component: &Component) -> pb::Component {
+    pb::Component {
+        r#type: enum_value(CLASSIFICATIONS, &component.component_type),
+        bom_ref: non_empty(&component.bom_ref),
+        supplier: component.supplier.as_ref().map(organization_to_proto),
+        publisher: component.publisher.clone(),
+        group: component.group.clone(),
+        name: component.name.clone(),
+        version: component.version.clone().unwrap_or_default(),
+        description: component.description.clone(),
+        hashes: component
+            .hashes
+            .iter()
+            .flatten()
+            .map(|hash| pb::Hash {
+                alg: enum_value(HASH_ALGORITHMS, &hash.alg),
+                value: hash.content.clone(),
+            })
+            .collect(),
+        licenses: component
+            .licenses
+            .iter()
+            .flatten()
+            .map(|license| license_to_proto(&license.license))
+            .collect(),
+        copyright: component.copyright.clone(),
+        purl: component.purl.clone(),
+        pedigree: component.pedigree.as_ref().map(|pedigree| pb::Pedigree {
+            ancestors: components_to_proto(pedigree.ancestors.as_deref()),
+            descendants: components_to_proto(pedigree.descendants.as_deref()),
+            variants: components_to_proto(pedigree.variants.as_deref()),
+            notes: pedigree.notes.clone(),
+        }),
+        external_references: references_to_proto(component.external_references.as_deref()),
+        properties: properties_to_proto(component.properties.as_deref()),
+        components: components_to_proto(component.components.as_deref()),
+        model_card: component.model_card.as_ref().map(model_card_to_proto),
+        manufacturer: component.manufacturer.as_ref().map(organization_to_proto),
+        authors: component
+            .authors
+            .iter()
+            .flatten()
+            .map(|author| pb::OrganizationalContact {
+                name: Some(author.name.clone()),
+            })
+            .collect(),
+    }
+}
+
+fn component_from_proto(component: pb::Component) -> Component {
+    Component {
+        component_type: enum_name(CLASSIFICATIONS, component.r#type, "library"),
+        bom_ref: component.bom_ref.unwrap_or_default(),
+        name: component.name,
+        version: non_empty(&component.version),
+        description: component.description,
+        group: component.group,
+        publisher: component.publisher,
+        supplier: component.supplier.map(organization_from_proto),
+        manufacturer: component.manufacturer.map(organization_from_proto),
+        authors: non_empty_vec(
+            component
+                .authors
+                .into_iter()
+                .map(|author| Author {
+                    name: author.name.unwrap_or_default(),
+                })
+                .collect(),
+        ),
+        copyright: component.copyright,
+        hashes: non_empty_vec(
+            component
+                .hashes
+                .into_iter()
+                .map(|hash| Hash {
+                    alg: enum_name(HASH_ALGORITHMS, hash.alg, "SHA-256"),
+                    content: hash.value,
+                })
+                .collect(),
+        ),
+        licenses: non_empty_vec(
+            component
+                .licenses
+                .into_iter()
+                .filter_map(license_from_proto)
+                .collect(),
+        ),
+        external_references: references_from_proto(component.external_references),
+        purl: component.purl,
+        pedigree: component.pedigree.map(|pedigree| Pedigree {
+            ancestors: components_from_proto(pedigree.ancestors),
+            descendants: components_from_proto(pedigree.descendants),
+            variants: components_from_proto(pedigree.variants),
+            notes: pedigree.notes,
+        }),
+        model_card: component.model_card.map(model_card_from_proto),
+        properties: properties_from_proto(component.properties),
+        components: components_from_proto(component.components),
+        signature: None,
+    }
+}
+
+fn components_to_proto(components: Option<&[Component]>) -> Vec<pb::Component> {
+    components
+        .unwrap_or_default()
+        .iter()
+        .map(component_to_proto)
+        .collect()
+}
+
+fn components_from_proto(components: Vec<pb::Component>) -> Option<Vec<Component>> {
+    non_empty_vec(components.into_iter().map(component_from_proto).collect())
+}
+
+fn organization_to_proto(organization: &Organization) -> pb::OrganizationalEntity {
+    pb::OrganizationalEntity {
+        name: Some(organization.name.clone()),
+        url: organization.url.clone().unwrap_or_default(),
+    }
+}
+
+fn organization_from_proto(organization: pb::OrganizationalEntity) -> Organization {
+    Organization {
+        name: organization.name.unwrap_or_default(),
+        url: non_empty_vec(organization.url),
+    }
+}
+
+fn license_to_proto(license: &LicenseInfo) -> pb::LicenseChoice {
+    let choice = match (&license.id, &license.name) {
+        (Some(id), _) => Some(pb::license::License::Id(id.clone())),
+        (None, Some(name)) => Some(pb::license::License::Name(name.clone())),
+        (None, None) => None,
+    };
+    pb::LicenseChoice {
+        choice: Some(pb::license_choice::Choice::License(pb::License {
+            license: choice,
+            text: license.text.as_ref().map(|text| pb::AttachedText {
+                content_type: text.content_type.clone(),
+                encoding: text.encoding.clone(),
+                value: text.content.clone(),
+            }),
+            url: license.url.clone(),
+            properties: properties_to_proto(license.properties.as_deref()),
+            acknowledgement: license
+                .acknowledgement
+                .as_deref()
+                .map(|a| enum_value(LICENSE_ACKNOWLEDGEMENTS, a)),
+        })),
+    }
+}
+
+fn license_from_proto(choice: pb::LicenseChoice) -> Option<License> {
+    let license = match choice.choice? {
+        pb::license_choice::Choice::License(license) => license,
+        // Expressions are not modelled by `LicenseInfo`; keep them as a name
+        pb::license_choice::Choice::Expression(expression) => pb::License {
+            license: Some(pb::license::License::Name(expression)),
+            ..Default::default()
+        },
+    };
+    let (id, name) = match license.license {
+        Some(pb::license::License::Id(id)) => (Some(id), None),
+        Some(pb::license::License::Name(name)) => (None, Some(name)),
+        None => (None, None),
+    };
+    Some(License {
+        license: LicenseInfo {
+            id,
+            name,
+            url: license.url,
+            text: license.text.map(|text| AttachedText {
+                content_type: text.content_type,
+                encoding: text.encoding,
+                content: text.value,
+            }),
+            acknowledgement: license
+                .acknowledgement
+                .filter(|a| *a != 0)
+                .map(|a| enum_name(LICENSE_ACKNOWLEDGEMENTS, a, "declared")),
+            properties: properties_from_proto(license.properties),
+        },
+    })
+}
+
+fn model_card_to_proto(model_card: &ModelCard) -> pb::ModelCard {
+    let io = |data: &Option<Vec<InputOutputData>>| -> Vec<pb::InputOutputParameters> {
+        data.iter()
+            .flatten()
+            .map(|entry| pb::InputOutputParameters {
+                format: Some(entry.format.clone()),
+            })
+            .collect()
+    };
+
+    pb::ModelCard {
+        model_parameters: model_card.model_parameters.as_ref().map(|parameters| {
+            pb::ModelParameters {
+                task: parameters.task.clone(),
+                architecture_family: parameters.architecture_family.clone(),
+                model_architecture: parameters.model_architecture.clone(),
+                datasets: parameters
+                    .datasets
+                    .iter()
+                    .flatten()
+                    .map(|dataset| pb::Datasets {
+                        choice: Some(pb::datasets::Choice::Ref(dataset.reference.clone())),
+                    })
+                    .collect(),
+                inputs: io(&parameters.inputs),
+                outputs: io(&parameters.outputs),
+            }
+        }),
+        quantitative_analysis: model_card.quantitative_analysis.as_ref().map(|analysis| {
+            pb::QuantitativeAnalysis {
+                performance_metrics: analysis
+                    .performance_metrics
+                    .iter()
+                    .flatten()
+                    .map(|metric| pb::PerformanceMetric {
+                        r#type: Some(metric.metric_type.clone()),
+                        value: Some(metric.value.clone()),
+                    })
+                    .collect(),
+                graphics: analysis
+                    .graphics
+                    .as_ref()
+                    .map(|graphics| pb::GraphicsCollection {
+                        description: graphics
+                            .get("description")
+                            .and_then(|d| d.as_str())
+                            .map(str::to_string),
+                    }),
+            }
+        }),
+        properties: properties_to_proto(Some(&model_card.properties_with_extensions())),
+    }
+}
+
+fn model_card_from_proto(model_card: pb::ModelCard) -> ModelCard {
+    let io = |data: Vec<pb::InputOutputParameters>| {
+        non_empty_vec(
+            data.into_iter()
+                .map(|entry| InputOutputData {
+                    format: entry.format.unwrap_or_default(),
+                })
+                .collect(),
+        )
+    };
+
+    // `modelParameters.embedding` travels as `ai.embedding.*` properties
+    let mut properties = properties_from_proto(model_card.properties);
+    let embedding = properties
+        .as_deref()
+        .filter(|_| model_card.model_parameters.is_some())
+        .and_then(EmbeddingInfo::from_properties);
+    if embedding.is_some()
+        && let Some(list) = &mut properties
+    {
+        list.retain(|p| !p.name.starts_with("ai.embedding."));
+        if list.is_empty() {
+            properties = None;
+        }
+    }
+
+    ModelCard {
+        model_parameters: model_card
+            .model_parameters
+            .map(|parameters| ModelParameters {
+                architecture_family: parameters.architecture_family,
+                model_architecture: parameters.model_architecture,
+                task: parameters.task,
+                datasets: non_empty_vec(
+                    parameters
+                        .datasets
+                        .into_iter()
+                        .filter_map(|dataset| match dataset.choice? {
+                            pb::datasets::Choice::Ref(reference) => {
+                                Some(DatasetReference { reference })
+                            }
+                        })
+                        .collect(),
+                ),
+                inputs: io(parameters.inputs),
+                outputs: io(parameters.outputs),
+                embedding,
+            }),
+        properties,
+        quantitative_analysis: model_card.quantitative_analysis.map(|analysis| {
+            QuantitativeAnalysis {
+                graphics: analysis
+                    .graphics
+                    .map(|graphics| match graphics.description {
+                        Some(description) => serde_json::json!({ "description": description }),
+                        None => serde_json::json!({}),
+                    }),
+                performance_metrics: non_empty_vec(
+                    analysis
+                        .performance_metrics
+                        .into_iter()
+                        .map(|metric| PerformanceMetric {
+                            metric_type: metric.r#type.unwrap_or_default(),
+                            value: metric.value.unwrap_or_default(),
+                        })
+                        .collect(),
+                ),
+            }
+        }),
+    }
+}
+
+fn references_to_proto(references: Option<&[ExternalReference]>) -> Vec<pb::ExternalReference> {
+    references
+        .unwrap_or_default()
+        .iter()
+        .map(|reference| pb::ExternalReference {
+            r#type: enum_value(EXTERNAL_REFERENCE_TYPES, &reference.ref_type),
+            url: reference.url.clone(),
+            comment: reference.comment.clone(),
+        })
+        .collect()
+}
+
+fn references_from_proto(references: Vec<pb::ExternalReference>) -> Option<Vec<ExternalReference>> {
+    non_empty_vec(
+        references
+            .into_iter()
+            .map(|reference| ExternalReference {
+                ref_type: enum_name(EXTERNAL_REFERENCE_TYPES, reference.r#type, "other"),
+                url: reference.url,
+                comment: reference.comment,
+            })
+            .collect(),
+    )
+}
+
+fn properties_to_proto(properties: Option<&[Property]>) -> Vec<pb::Property> {
+    properties
+        .unwrap_or_default()
+        .iter()
+        .map(|property| pb::Property {
+            name: property.name.clone(),
+            value: Some(property.value.clone()),
+        })
+        .collect()
+}
+
+fn properties_from_proto(properties: Vec<pb::Property>) -> Option<Vec<Property>> {
+    non_empty_vec(
+        properties
+            .into_iter()
+            .map(|property| Property {
+                name: property.name,
+                value: property.value.unwrap_or_default(),
+            })
+            .collect(),
+    )
+}
+
+/// Messages of `bom-1.6.proto` used by AIBOMs
+pub mod pb {
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Bom {
+        #[prost(string, tag = "1")]
+        pub spec_version: String,
+        #[prost(int32, optional, tag = "2")]
+        pub version: Option<i32>,
+        #[prost(string, optional, tag = "3")]
+        pub serial_number: Option<String>,
+        #[prost(message, optional, tag = "4")]
+        pub metadata: Option<Metadata>,
+        #[prost(message, repeated, tag = "5")]
+        pub components: Vec<Component>,
+        #[prost(message, repeated, tag = "7")]
+        pub external_references: Vec<ExternalReference>,
+        #[prost(message, repeated, tag = "8")]
+        pub dependencies: Vec<Dependency>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Metadata {
+        #[prost(message, optional, tag = "1")]
+        pub timestamp: Option<::prost_types::Timestamp>,
+        #[prost(message, optional, tag = "2")]
+        pub tools: Option<Tool>,
+        #[prost(message, optional, tag = "4")]
+        pub component: Option<Component>,
+        #[prost(message, repeated, tag = "8")]
+        pub properties: Vec<Property>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Tool {
+        #[prost(message, repeated, tag = "6")]
+        pub components: Vec<Component>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Component {
+        /// `Classification`
+        #[prost(int32, tag = "1")]
+        pub r#type: i32,
+        #[prost(string, optional, tag = "3")]
+        pub bom_ref: Option<String>,
+        #[prost(message, optional, tag = "4")]
+        pub supplier: Option<OrganizationalEntity>,
+        #[prost(string, optional, tag = "6")]
+        pub publisher: Option<String>,
+        #[prost(string, optional, tag = "7")]
+        pub group: Option<String>,
+        #[prost(string, tag = "8")]
+        pub name: String,
+        #[prost(string, tag = "9")]
+        pub version: String,
+        #[prost(string, optional, tag = "10")]
+        pub description: Option<String>,
+        #[prost(message, repeated, tag = "12")]
+        pub hashes: Vec<Hash>,
+        #[prost(message, repeated, tag = "13")]
+        pub licenses: Vec<LicenseChoice>,
+        #[prost(string, optional, tag = "14")]
+        pub copyright: Option<String>,
+        #[prost(string, optional, tag = "16")]
+        pub purl: Option<String>,
+        #[prost(message, optional, tag = "19")]
+        pub pedigree: Option<Pedigree>,
+        #[prost(message, repeated, tag = "20")]
+        pub external_references: Vec<ExternalReference>,
+        #[prost(message, repeated, tag = "21")]
+        pub properties: Vec<Property>,
+        #[prost(message, repeated, tag = "22")]
+        pub components: Vec<Component>,
+        #[prost(message, optional, tag = "25")]
+        pub model_card: Option<ModelCard>,
+        #[prost(message, optional, tag = "28")]
+        pub manufacturer: Option<OrganizationalEntity>,
+        #[prost(message, repeated, tag = "29")]
+        pub authors: Vec<OrganizationalContact>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct OrganizationalEntity {
+        #[prost(string, optional, tag = "1")]
+        pub name: Option<String>,
+        #[prost(string, repeated, tag = "2")]
+        pub url: Vec<String>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct OrganizationalContact {
+        #[prost(string, optional, tag = "1")]
+        pub name: Option<String>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Hash {
+        /// `HashAlg`
+        #[prost(int32, tag = "1")]
+        pub alg: i32,
+        #[prost(string, tag = "2")]
+        pub value: String,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct LicenseChoice {
+        #[prost(oneof = "license_choice::Choice", tags = "1, 2")]
+        pub choice: Option<license_choice::Choice>,
+    }
+
+    pub mod license_choice {
+        #[derive(Clone, PartialEq, ::prost::Oneof)]
+        pub enum Choice {
+            #[prost(message, tag = "1")]
+            License(super::License),
+            #[prost(string, tag = "2")]
+            Expression(String),
+        }
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct License {
+        #[prost(oneof = "license::License", tags = "1, 2")]
+        pub license: Option<license::License>,
+        #[prost(message, optional, tag = "3")]
+        pub text: Option<AttachedText>,
+        #[prost(string, optional, tag = "4")]
+        pub url: Option<String>,
+        #[prost(message, repeated, tag = "7")]
+        pub properties: Vec<Property>,
+        /// `LicenseAcknowledgementEnumeration`
+        #[prost(int32, optional, tag = "8")]
+        pub acknowledgement: Option<i32>,
+    }
+
+    pub mod license {
+        #[derive(Clone, PartialEq, ::prost::Oneof)]
+        pub enum License {
+            #[prost(string, tag = "1")]
+            Id(String),
+            #[prost(string, tag = "2")]
+            Name(String),
+        }
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct AttachedText {
+        #[prost(string, optional, tag = "1")]
+        pub content_type: Option<String>,
+        #[prost(string, optional, tag = "2")]
+        pub encoding: Option<String>,
+        #[prost(string, tag = "3")]
+        pub value: String,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Pedigree {
+        #[prost(message, repeated, tag = "1")]
+        pub ancestors: Vec<Component>,
+        #[prost(message, repeated, tag = "2")]
+        pub descendants: Vec<Component>,
+        #[prost(message, repeated, tag = "3")]
+        pub variants: Vec<Component>,
+        #[prost(string, optional, tag = "6")]
+        pub notes: Option<String>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ExternalReference {
+        /// `ExternalReferenceType`
+        #[prost(int32, tag = "1")]
+        pub r#type: i32,
+        #[prost(string, tag = "2")]
+        pub url: String,
+        #[prost(string, optional, tag = "3")]
+        pub comment: Option<String>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Property {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(string, optional, tag = "2")]
+        pub value: Option<String>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Dependency {
+        #[prost(string, tag = "1")]
+        pub r#ref: String,
+        #[prost(message, repeated, tag = "2")]
+        pub dependencies: Vec<Dependency>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ModelCard {
+        #[prost(message, optional, tag = "2")]
+        pub model_parameters: Option<ModelParameters>,
+        #[prost(message, optional, tag = "3")]
+        pub quantitative_analysis: Option<QuantitativeAnalysis>,
+        #[prost(message, repeated, tag = "5")]
+        pub properties: Vec<Property>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ModelParameters {
+        #[prost(string, optional, tag = "2")]
+        pub task: Option<String>,
+        #[prost(string, optional, tag = "3")]
+        pub architecture_family: Option<String>,
+        #[prost(string, optional, tag = "4")]
+        pub model_architecture: Option<String>,
+        #[prost(message, repeated, tag = "5")]
+        pub datasets: Vec<Datasets>,
+        #[prost(message, repeated, tag = "6")]
+        pub inputs: Vec<InputOutputParameters>,
+        #[prost(message, repeated, tag = "7")]
+        pub outputs: Vec<InputOutputParameters>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Datasets {
+        #[prost(oneof = "datasets::Choice", tags = "2")]
+        pub choice: Option<datasets::Choice>,
+    }
+
+    pub mod datasets {
+        #[derive(Clone, PartialEq, ::prost::Oneof)]
+        pub enum Choice {
+            #[prost(string, tag = "2")]
+            Ref(String),
+        }
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct InputOutputParameters {
+        #[prost(string, optional, tag = "1")]
+        pub format: Option<String>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct QuantitativeAnalysis {
+        #[prost(message, repeated, tag = "1")]
+        pub performance_metrics: Vec<PerformanceMetric>,
+        #[prost(message, optional, tag = "2")]
+        pub graphics: Option<GraphicsCollection>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct PerformanceMetric {
+        #[prost(string, optional, tag = "1")]
+        pub r#type: Option<String>,
+        #[prost(string, optional, tag = "2")]
+        pub value: Option<String>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GraphicsCollection {
+        #[prost(string, optional, tag = "1")]
+        pub description: Option<String>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{BomFixture, dataset, model};
+    use serde_json::json;
+
+    fn round_trip(document: Value) -> Value {
+        let aibom: AIBOM = serde_json::from_value(document).unwrap();
+        serde_json::to_value(from_protobuf(&to_protobuf(&aibom)).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn round_trips_every_mapped_field() {
+        let mut tuned = model("tuned", "pkg:huggingface/org/tuned@1.0");
+        tuned["version"] = json!("1.0");
+        tuned["supplier"] = json!({ "name": "org", "url": ["https://huggingface.co/org"] });
+        tuned["authors"] = json!([{ "name": "org" }]);
+        tuned["hashes"] = json!([{
+            "alg": "SHA-256",
+            "content": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        }]);
+        tuned["licenses"] = json!([
+            { "license": { "id": "Apache-2.0", "acknowledgement": "declared" } },
+            { "license": {
+                "name": "custom",
+                "text": { "contentType": "text/plain", "content": "Do good" }
+            }}
+        ]);
+        tuned["externalReferences"] =
+            json!([{ "type": "website", "url": "https://huggingface.co/org/tuned" }]);
+        tuned["pedigree"] = json!({ "ancestors": [model("base", "pkg:huggingface/org/base@1.0")] });
+        tuned["modelCard"] = json!({
+            "modelParameters": {
+                "architectureFamily": "llama",
+                "task": "text-generation",
+                "datasets": [{ "ref": "pkg:huggingface-dataset/org/train@1.0" }],
+                "inputs": [{ "format": "text" }],
+                "outputs": [{ "format": "text" }]
+            },
+            "properties": [{ "name": "ai.peft.type", "value": "LORA" }],
+            "quantitativeAnalysis": {
+                "graphics": {},
+                "performanceMetrics": [{ "type": "accuracy", "value": "0.9" }]
+            }
+        });
+        tuned["properties"] = json!([{ "name": "ai.task", "value": "text-generation" }]);
+        tuned["components"] = json!([{
+            "type": "file",
+            "bom-ref": "pkg:huggingface/org/tuned@1.0#modeling.py",
+            "name": "modeling.py"
+        }]);
+        let mut train = dataset("train", "pkg:huggingface-dataset/org/train@1.0");
+        train["description"] = json!("Training dataset");
+        let document = BomFixture::new()
+            .metadata("timestamp", json!("2024-05-01T12:00:00.123456789+00:00"))
+            .metadata(
+                "tools",
+                json!({ "components": [{
+                    "bom-ref": "pkg:generic/rust-aibom-generator@1.0.0",
+                    "manufacturer": { "name": "Rust AIBOM Generator" },
+                    "name": "rust-aibom-generator",
+                    "type": "application",
+                    "version": "1.0"
+                }]}),
+            )
+            .metadata(
+                "properties",
+                json!([{ "name": "aibom:truncated", "value": "false" }]),
+            )
+            .component(tuned)
+            .component(train)
+            .dependency(
+                "pkg:huggingface/org/tuned@1.0",
+                &["pkg:huggingface-dataset/org/train@1.0"],
+            )
+            .dependency("pkg:huggingface-dataset/org/train@1.0", &[])
+            .set(
+                "externalReferences",
+                json!([{
+                    "type": "distribution",
+                    "url": "https://huggingface.co/org/tuned",
+                    "comment": "Hub"
+                }]),
+            )
+            .to_value();
+
+        assert_eq!(round_trip(document.clone()), document);
+        let aibom: AIBOM = serde_json::from_value(document).unwrap();
+        assert!(to_protobuf(&aibom).len() < serde_json::to_vec(&aibom).unwrap().len());
+    }
+
+    #[test]
+    fn round_trips_an_empty_document() {
+        let document = BomFixture::new()
+            .metadata("timestamp", json!("2024-05-01T12:00:00+00:00"))
+            .to_value();
+        assert_eq!(round_trip(document.clone()), document);
+    }
+
+    #[test]
+    fn round_trips_embedding_parameters_through_properties() {
+        let mut embedder = model("embedder", "pkg:huggingface/org/embedder@1.0");
+        embedder["modelCard"] = json!({
+            "modelParameters": {
+                "outputs": [{ "format": "embedding" }],
+                "embedding": { "poolingMode": "mean", "dimension": 384, "normalized": true }
+            },
+            "properties": [{ "name": "ai.sentenceTransformers.module", "value": "0_Transformer" }]
+        });
+        let document = BomFixture::new()
+            .metadata("timestamp", json!("2024-05-01T12:00:00+00:00"))
+            .component(embedder)
+            .to_value();
+
+        assert_eq!(round_trip(document.clone()), document);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert!(matches!(
+            from_protobuf(&[0x0a, 0xff]),
+            Err(GenerateError::Parse(_))
+        ));
+    }
+}
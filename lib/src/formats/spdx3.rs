@@ -0,0 +1,739 @@
+//! SPDX 3.0 JSON-LD serialization using the AI and Dataset profiles.
+//!
+//! Models become `ai_AIPackage` elements, datasets `dataset_DatasetPackage`,
+//! code files `software_File` and everything else `software_Package`. Lineage
+//! from pedigrees is expressed as `ancestorOf`, training and evaluation data as
+//! `trainedOn`/`testedOn`, and the remaining BOM dependencies as `dependsOn`.
+
+use crate::*;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+
+const CONTEXT: &str = "https://spdx.org/rdf/3.0.1/spdx-context.jsonld";
+const SPEC_VERSION: &str = "3.0.1";
+const CREATION_INFO: &str = "_:creationinfo";
+
+/// Convert an AIBOM to an SPDX 3.0 JSON-LD document
+pub fn to_spdx3(aibom: &AIBOM) -> Value {
+    let mut writer = Spdx3Writer::new(aibom);
+    writer.write();
+    json!({
+        "@context": CONTEXT,
+        "@graph": writer.graph,
+    })
+}
+
+struct Spdx3Writer<'a> {
+    aibom: &'a AIBOM,
+    namespace: String,
+    graph: Vec<Value>,
+    next_id: usize,
+    /// bom-refs and purls of written packages, mapped to their spdxId
+    ids: HashMap<String, String>,
+    /// Supplier names mapped to Organization spdxIds
+    agents: HashMap<String, String>,
+    /// License expressions mapped to their element spdxIds
+    licenses: HashMap<String, String>,
+    /// (from, relationshipType) with the collected targets, in insertion order
+    relationships: Vec<(String, &'static str, Vec<String>)>,
+}
+
+impl<'a> Spdx3Writer<'a> {
+    fn new(aibom: &'a AIBOM) -> Self {
+        Self {
+            aibom,
+            namespace: aibom.serial_number.clone(),
+            graph: Vec::new(),
+            next_id: 0,
+            ids: HashMap::new(),
+            agents: HashMap::new(),
+            licenses: HashMap::new(),
+            relationships: Vec::new(),
+        }
+    }
+
+    fn write(&mut self) {
+        let aibom = self.aibom;
+        let metadata = &aibom.metadata;
+        let tool = metadata.tools.components.first();
+        let creator_name = tool
+            .map(|t| t.manufacturer.name.clone())
+            .unwrap_or_else(|| "Rust AIBOM Generator".to_string());
+        let creator = self.new_id("Organization");
+        let tool_id = tool.map(|_| self.new_id("Tool"));
+
+        // CreationInfo is a blank node shared by every element
+        let mut creation_info = json!({
+            "type": "CreationInfo",
+            "@id": CREATION_INFO,
+            "specVersion": SPEC_VERSION,
+            "created": spdx_timestamp(&metadata.timestamp),
+            "createdBy": [creator.clone()],
+        });
+        if let Some(tool_id) = &tool_id {
+            creation_info["createdUsing"] = json!([tool_id]);
+        }
+        self.graph.push(creation_info);
+        self.push_element(json!({
+            "type": "Organization",
+            "spdxId": creator,
+            "name": creator_name,
+        }));
+        if let (Some(tool), Some(tool_id)) = (tool, tool_id) {
+            self.push_element(json!({
+                "type": "Tool",
+                "spdxId": tool_id,
+                "name": format!("{}-{}", tool.name, tool.version),
+            }));
+        }
+
+        let application = self.write_package(&metadata.component);
+
+        for component in &aibom.components {
+            self.write_component_tree(component);
+        }
+        for component in &aibom.components {
+            self.write_lineage(component);
+        }
+        self.write_dependencies();
+
+        // The application depends on every model nothing else depends on
+        let targets: HashSet<&String> = aibom
+            .dependencies
+            .iter()
+            .flat_map(|dependency| &dependency.depends_on)
+            .collect();
+        let roots: Vec<String> = aibom
+            .components
+            .iter()
+            .filter(|c| c.component_type == "machine-learning-model")
+            .filter(|c| !targets.contains(&c.bom_ref))
+            .filter_map(|c| self.ids.get(&c.bom_ref).cloned())
+            .collect();
+        for root in roots {
+            self.relate(&application, "dependsOn", &root);
+        }
+
+        let relationships = std::mem::take(&mut self.relationships);
+        for (from, relationship_type, to) in relationships {
+            let id = self.new_id("Relationship");
+            self.push_element(json!({
+                "type": "Relationship",
+                "spdxId": id,
+                "from": from,
+                "relationshipType": relationship_type,
+                "to": to,
+            }));
+        }
+
+        let elements: Vec<Value> = self
+            .graph
+            .iter()
+            .filter_map(|element| element.get("spdxId").cloned())
+            .collect();
+        let sbom = self.new_id("Sbom");
+        self.push_element(json!({
+            "type": "software_Sbom",
+            "spdxId": sbom.clone(),
+            "name": format!("AIBOM for {}", metadata.component.name),
+            "software_sbomType": ["analyzed"],
+            "rootElement": [application],
+            "element": elements,
+        }));
+        let document = self.new_id("Document");
+        self.push_element(json!({
+            "type": "SpdxDocument",
+            "spdxId": document,
+            "name": metadata.component.name.clone(),
+            "profileConformance": ["core", "software", "simpleLicensing", "ai", "dataset"],
+            "rootElement": [sbom.clone()],
+            "element": [sbom],
+        }));
+    }
+
+    /// Write a component and its nested components, linked with `contains`
+    fn write_component_tree(&mut self, component: &Component) -> String {
+        let id = self.write_package(component);
+        for child in component.components.iter().flatten() {
+            let child_id = self.write_component_tree(child);
+            self.relate(&id, "contains", &child_id);
+        }
+        id
+    }
+
+    /// Express pedigree ancestors and variants as `ancestorOf` the component,
+    /// and descendants as the component being `ancestorOf` them
+    fn write_lineage(&mut self, component: &Component) {
+        let Some(id) = self.lookup(component) else {
+            return;
+        };
+        let Some(pedigree) = &component.pedigree else {
+            return;
+        };
+        for ancestor in pedigree
+            .ancestors
+            .iter()
+            .chain(&pedigree.variants)
+            .flatten()
+        {
+            let ancestor_id = self.write_package(ancestor);
+            self.relate(&ancestor_id, "ancestorOf", &id);
+        }
+        for descendant in pedigree.descendants.iter().flatten() {
+            let descendant_id = self.write_package(descendant);
+            self.relate(&id, "ancestorOf", &descendant_id);
+            self.write_lineage(descendant);
+        }
+    }
+
+    /// Classify BOM dependency edges as trainedOn, testedOn or dependsOn;
+    /// edges already expressed as lineage are not repeated
+    fn write_dependencies(&mut self) {
+        let aibom = self.aibom;
+        let components: HashMap<&str, &Component> = aibom
+            .components
+            .iter()
+            .map(|c| (c.bom_ref.as_str(), c))
+            .collect();
+
+        for dependency in &aibom.dependencies {
+            let Some(from) = self.ids.get(&dependency.reference).cloned() else {
+                continue;
+            };
+            let component = components.get(dependency.reference.as_str());
+            let model_card = component.and_then(|c| c.model_card.as_ref());
+            let trained_on: Vec<&str> = model_card
+                .and_then(|card| card.model_parameters.as_ref())
+                .and_then(|parameters| parameters.datasets.as_ref())
+                .into_iter()
+                .flatten()
+                .map(|dataset| dataset.reference.as_str())
+                .collect();
+            let tested_on: Vec<&str> = model_card
+                .and_then(|card| card.properties.as_ref())
+                .into_iter()
+                .flatten()
+                .filter(|p| p.name == "ai.evaluation.dataset")
+                .map(|p| p.value.as_str())
+                .collect();
+
+            for target in &dependency.depends_on {
+                let Some(to) = self.ids.get(target).cloned() else {
+                    continue;
+                };
+                let relationship_type = if trained_on.contains(&target.as_str()) {
+                    "trainedOn"
+                } else if tested_on.contains(&target.as_str()) {
+                    "testedOn"
+                } else if self.is_related(&to, "ancestorOf", &from) {
+                    continue;
+                } else {
+                    "dependsOn"
+                };
+                self.relate(&from, relationship_type, &to);
+            }
+        }
+    }
+
+    /// Write a single component as an SPDX element, reusing an existing element
+    /// for the same bom-ref or purl
+    fn write_package(&mut self, component: &Component) -> String {
+        if let Some(id) = self.lookup(component) {
+            return id;
+        }
+
+        let (element_type, prefix, purpose) = match component.component_type.as_str() {
+            "machine-learning-model" => ("ai_AIPackage", "AIPackage", "model"),
+            "data" => ("dataset_DatasetPackage", "DatasetPackage", "data"),
+            "file" => ("software_File", "File", "source"),
+            "application" => ("software_Package", "Package", "application"),
+            _ => ("software_Package", "Package", "library"),
+        };
+        let id = self.new_id(prefix);
+        if !component.bom_ref.is_empty() {
+            self.ids.insert(component.bom_ref.clone(), id.clone());
+        }
+        if let Some(purl) = &component.purl {
+            self.ids.insert(purl.clone(), id.clone());
+        }
+
+        let mut element = json!({
+            "type": element_type,
+            "spdxId": id.clone(),
+            "name": component.name.clone(),
+            "software_primaryPurpose": purpose,
+        });
+        if let Some(description) = &component.description {
+            element["description"] = json!(description);
+        }
+        if element_type != "software_File" {
+            if let Some(version) = &component.version {
+                element["software_packageVersion"] = json!(version);
+            }
+            if let Some(purl) = &component.purl {
+                element["software_packageUrl"] = json!(purl);
+            }
+        }
+        if let Some(location) = component
+            .external_references
+            .iter()
+            .flatten()
+            .find(|r| r.ref_type == "distribution" || r.ref_type == "website")
+        {
+            let key = if element_type == "software_File" {
+                "software_homePage"
+            } else {
+                "software_downloadLocation"
+            };
+            element[key] = json!(location.url);
+        }
+        if let Some(copyright) = component
+            .copyright
+            .as_ref()
+            .filter(|c| c.as_str() != "NOASSERTION")
+        {
+            element["software_copyrightText"] = json!(copyright);
+        }
+        if let Some(supplier) = &component.supplier {
+            let supplier_id = self.agent(&supplier.name);
+            element["suppliedBy"] = json!(supplier_id);
+        }
+        let hashes: Vec<Value> = component
+            .hashes
+            .iter()
+            .flatten()
+            .map(|hash| match hash_algorithm(&hash.alg) {
+                Some(algorithm) => json!({
+                    "type": "Hash",
+                    "algorithm": algorithm,
+                    "hashValue": hash.content,
+                }),
+                // SPDX has no Streebog; keep the CycloneDX name as a comment
+                None => json!({
+                    "type": "Hash",
+                    "algorithm": "other",
+                    "hashValue": hash.content,
+                    "comment": hash.alg,
+                }),
+            })
+            .collect();
+        if !hashes.is_empty() {
+            element["verifiedUsing"] = json!(hashes);
+        }
+
+        match element_type {
+            "ai_AIPackage" => self.add_model_fields(&mut element, component),
+            "dataset_DatasetPackage" => element["dataset_datasetType"] = json!(["noAssertion"]),
+            _ => {}
+        }
+
+        self.push_element(element);
+
+        if let Some(expression) = license_expression(component) {
+            let license_id = self.license(&expression);
+            self.relate(&id, "hasDeclaredLicense", &license_id);
+        }
+
+        id
+    }
+
+    /// Map the CycloneDX model card onto AI profile properties
+    fn add_model_fields(&self, element: &mut Value, component: &Component) {
+        let Some(model_card) = &component.model_card else {
+            return;
+        };
+
+        if let Some(parameters) = &model_card.model_parameters {
+            let mut types: Vec<&String> = Vec::new();
+            for model_type in [
+                &parameters.model_architecture,
+                &parameters.architecture_family,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                if !types.contains(&model_type) {
+                    types.push(model_type);
+                }
+            }
+            if !types.is_empty() {
+                element["ai_typeOfModel"] = json!(types);
+            }
+            if let Some(task) = &parameters.task {
+                element["ai_domain"] = json!([task]);
+            }
+        }
+
+        let metrics: Vec<Value> = model_card
+            .quantitative_analysis
+            .iter()
+            .flat_map(|analysis| analysis.performance_metrics.iter().flatten())
+            .map(|metric| dictionary_entry(&metric.metric_type, &metric.value))
+            .collect();
+        if !metrics.is_empty() {
+            element["ai_metric"] = json!(metrics);
+        }
+
+        // Adapter, quantization, merge and embedding settings describe how the
+        // model was produced; relations and dataset links become relationships
+        let hyperparameters: Vec<Value> = model_card
+            .properties_with_extensions()
+            .iter()
+            .filter(|p| p.name.starts_with("ai."))
+            .filter(|p| {
+                !matches!(
+                    p.name.as_str(),
+                    "ai.model.relation" | "ai.evaluation.dataset"
+                )
+            })
+            .map(|p| dictionary_entry(&p.name, &p.value))
+            .collect();
+        if !hyperparameters.is_empty() {
+            element["ai_hyperparameter"] = json!(hyperparameters);
+        }
+
+        if let Some(relation) = model_card
+            .properties
+            .iter()
+            .flatten()
+            .find(|p| p.name == "ai.model.relation")
+        {
+            element["ai_informationAboutTraining"] =
+                json!(format!("Derived from its ancestors by {}", relation.value));
+        }
+    }
+
+    fn lookup(&self, component: &Component) -> Option<String> {
+        self.ids
+            .get(&component.bom_ref)
+            .or_else(|| component.purl.as_ref().and_then(|purl| self.ids.get(purl)))
+            .cloned()
+    }
+
+    /// Organization element for a supplier, written once per name
+    fn agent(&mut self, name: &str) -> String {
+        if let Some(id) = self.agents.get(name) {
+            return id.clone();
+        }
+        let id = self.new_id("Organization");
+        self.push_element(json!({
+            "type": "Organization",
+            "spdxId": id.clone(),
+            "name": name,
+        }));
+        self.agents.insert(name.to_string(), id.clone());
+        id
+    }
+
+    /// License expression element, written once per expression
+    fn license(&mut self, expression: &str) -> String {
+        if let Some(id) = self.licenses.get(expression) {
+            return id.clone();
+        }
+        let id = self.new_id("LicenseExpression");
+        self.push_element(json!({
+            "type": "simplelicensing_LicenseExpression",
+            "spdxId": id.clone(),
+            "simplelicensing_licenseExpression": expression,
+        }));
+        self.licenses.insert(expression.to_string(), id.clone());
+        id
+    }
+
+    fn relate(&mut self, from: &str, relationship_type: &'static str, to: &str) {
+        match self
+            .relationships
+            .iter_mut()
+            .find(|(f, t, _)| f == from && *t == relationship_type)
+        {
+            Some((_, _, targets)) => {
+                if !targets.iter().any(|t| t == to) {
+                    targets.push(to.to_string());
+                }
+            }
+            None => {
+                self.relationships.push((
+                    from.to_string(),
+                    relationship_type,
+                    vec![to.to_string()],
+                ));
+            }
+        }
+    }
+
+    fn is_related(&self, from: &str, relationship_type: &str, to: &str) -> bool {
+        self.relationships.iter().any(|(f, t, targets)| {
+            f == from && *t == relationship_type && targets.iter().any(|x| x == to)
+        })
+    }
+
+    fn new_id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{}#SPDXRef-{}-{}", self.namespace, prefix, self.next_id)
+    }
+
+    fn push_element(&mut self, mut element: Value) {
+        element["creationInfo"] = json!(CREATION_INFO);
+        self.graph.push(element);
+    }
+}
+
+/// Combine a component's licenses into one SPDX expression, turning names that
+/// are not SPDX identifiers into `LicenseRef-` references
+fn license_expression(component: &Component) -> Option<String> {
+    let terms: Vec<String> = component
+        .licenses
+        .iter()
+        .flatten()
+        .filter_map(|license| {
+            let info = &license.license;
+            info.id.clone().or_else(|| {
+                info.name.as_ref().map(|name| {
+                    let reference: String = name
+                        .chars()
+                        .map(|c| {
+                            if c.is_ascii_alphanumeric() || c == '.' {
+                                c
+                            } else {
+                                '-'
+                            }
+                        })
+                        .collect();
+                    format!("LicenseRef-{}", reference)
+                })
+            })
+        })
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" AND "))
+}
+
+/// SPDX `HashAlgorithm` for a CycloneDX `hash-alg` name
+fn hash_algorithm(alg: &str) -> Option<&'static str> {
+    let algorithm = match alg {
+        "MD5" => "md5",
+        "SHA-1" => "sha1",
+        "SHA-256" => "sha256",
+        "SHA-384" => "sha384",
+        "SHA-512" => "sha512",
+        "SHA3-256" => "sha3_256",
+        "SHA3-384" => "sha3_384",
+        "SHA3-512" => "sha3_512",
+        "BLAKE2b-256" => "blake2b256",
+        "BLAKE2b-384" => "blake2b384",
+        "BLAKE2b-512" => "blake2b512",
+        "BLAKE3" => "blake3",
+        _ => return None,
+    };
+    Some(algorithm)
+}
+
+fn dictionary_entry(key: &str, value: &str) -> Value {
+    json!({
+        "type": "DictionaryEntry",
+        "key": key,
+        "value": value,
+    })
+}
+
+/// SPDX 3 requires UTC timestamps without fractional seconds
+fn spdx_timestamp(timestamp: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|t| {
+            t.with_timezone(&chrono::Utc)
+                .format("%Y-%m-%dT%H:%M:%SZ")
+                .to_string()
+        })
+        .unwrap_or_else(|_| timestamp.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{BomFixture, dataset, model};
+
+    fn graph(aibom: &AIBOM) -> Vec<Value> {
+        to_spdx3(aibom)["@graph"].as_array().unwrap().clone()
+    }
+
+    fn id_of(graph: &[Value], name: &str, element_type: &str) -> Value {
+        graph
+            .iter()
+            .find(|e| e["name"] == name && e["type"] == element_type)
+            .map(|e| e["spdxId"].clone())
+            .unwrap()
+    }
+
+    fn relationship(graph: &[Value], from: &Value, relationship_type: &str) -> Option<Value> {
+        graph
+            .iter()
+            .find(|e| e["from"] == *from && e["relationshipType"] == relationship_type)
+            .map(|e| e["to"].clone())
+    }
+
+    fn license_expression_of(graph: &[Value], name: &str) -> Value {
+        let license = relationship(
+            graph,
+            &id_of(graph, name, "ai_AIPackage"),
+            "hasDeclaredLicense",
+        )
+        .unwrap();
+        graph
+            .iter()
+            .find(|e| e["spdxId"] == license[0])
+            .map(|e| e["simplelicensing_licenseExpression"].clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn maps_lineage_and_datasets_to_relationships() {
+        let mut tuned = model("tuned", "pkg:huggingface/org/tuned@1.0");
+        tuned["pedigree"] = json!({ "ancestors": [{
+            "type": "machine-learning-model",
+            "name": "base",
+            "purl": "pkg:huggingface/org/base@1.0"
+        }]});
+        tuned["modelCard"] = json!({
+            "modelParameters": {
+                "task": "text-generation",
+                "datasets": [{ "ref": "pkg:huggingface-dataset/org/train@1.0" }]
+            },
+            "properties": [
+                { "name": "ai.evaluation.dataset", "value": "pkg:huggingface-dataset/org/eval@1.0" }
+            ]
+        });
+        let aibom = BomFixture::new()
+            .component(dataset("train", "pkg:huggingface-dataset/org/train@1.0"))
+            .component(dataset("eval", "pkg:huggingface-dataset/org/eval@1.0"))
+            .component(tuned)
+            .component(model("base", "pkg:huggingface/org/base@1.0"))
+            .dependency(
+                "pkg:huggingface/org/tuned@1.0",
+                &[
+                    "pkg:huggingface-dataset/org/train@1.0",
+                    "pkg:huggingface-dataset/org/eval@1.0",
+                    "pkg:huggingface/org/base@1.0",
+                ],
+            )
+            .build();
+
+        let graph = graph(&aibom);
+        let tuned = id_of(&graph, "tuned", "ai_AIPackage");
+        let base = id_of(&graph, "base", "ai_AIPackage");
+        assert_eq!(
+            relationship(&graph, &tuned, "trainedOn"),
+            Some(json!([id_of(&graph, "train", "dataset_DatasetPackage")]))
+        );
+        assert_eq!(
+            relationship(&graph, &tuned, "testedOn"),
+            Some(json!([id_of(&graph, "eval", "dataset_DatasetPackage")]))
+        );
+        assert_eq!(
+            relationship(&graph, &base, "ancestorOf"),
+            Some(json!([tuned.clone()]))
+        );
+        // Lineage is not repeated as a plain dependency
+        assert_eq!(relationship(&graph, &tuned, "dependsOn"), None);
+        assert_eq!(
+            relationship(
+                &graph,
+                &id_of(&graph, "app", "software_Package"),
+                "dependsOn"
+            ),
+            Some(json!([tuned]))
+        );
+    }
+
+    #[test]
+    fn records_creation_info_in_utc_without_fractional_seconds() {
+        let aibom = BomFixture::new()
+            .metadata("timestamp", json!("2024-05-01T14:00:00.123456+02:00"))
+            .metadata(
+                "tools",
+                json!({ "components": [{
+                    "type": "application",
+                    "bom-ref": "pkg:generic/rust-aibom-generator@1.0.0",
+                    "manufacturer": { "name": "Rust AIBOM Generator" },
+                    "name": "rust-aibom-generator",
+                    "version": "1.0"
+                }]}),
+            )
+            .build();
+
+        let graph = graph(&aibom);
+        assert_eq!(graph[0]["created"], "2024-05-01T12:00:00Z");
+        assert_eq!(
+            graph[0]["createdBy"],
+            json!([id_of(&graph, "Rust AIBOM Generator", "Organization")])
+        );
+        assert_eq!(
+            graph[0]["createdUsing"],
+            json!([id_of(&graph, "rust-aibom-generator-1.0", "Tool")])
+        );
+    }
+
+    #[test]
+    fn writes_non_spdx_license_names_as_license_refs() {
+        let mut tuned = model("tuned", "pkg:huggingface/org/tuned@1.0");
+        tuned["licenses"] = json!([
+            { "license": { "id": "Apache-2.0" } },
+            { "license": { "name": "Llama 3 Community" } }
+        ]);
+        let aibom = BomFixture::new().component(tuned).build();
+
+        assert_eq!(
+            license_expression_of(&graph(&aibom), "tuned"),
+            "Apache-2.0 AND LicenseRef-Llama-3-Community"
+        );
+    }
+
+    #[test]
+    fn describes_an_empty_document() {
+        let graph = graph(&BomFixture::new().build());
+
+        let types: Vec<&str> = graph.iter().map(|e| e["type"].as_str().unwrap()).collect();
+        assert_eq!(
+            types,
+            [
+                "CreationInfo",
+                "Organization",
+                "software_Package",
+                "software_Sbom",
+                "SpdxDocument"
+            ]
+        );
+        let sbom = &graph[3];
+        assert_eq!(
+            sbom["rootElement"],
+            json!([id_of(&graph, "app", "software_Package")])
+        );
+        assert_eq!(graph[4]["rootElement"], json!([sbom["spdxId"]]));
+    }
+
+    #[test]
+    fn maps_hash_algorithms_to_spdx_names() {
+        let mut tuned = model("tuned", "pkg:huggingface/org/tuned@1.0");
+        tuned["hashes"] = json!([
+            { "alg": "SHA-256", "content": "aa" },
+            { "alg": "SHA3-256", "content": "bb" },
+            { "alg": "BLAKE2b-512", "content": "cc" },
+            { "alg": "Streebog-256", "content": "dd" }
+        ]);
+        let graph = graph(&BomFixture::new().component(tuned).build());
+
+        let tuned = graph.iter().find(|e| e["type"] == "ai_AIPackage").unwrap();
+        assert_eq!(
+            tuned["verifiedUsing"],
+            json!([
+                { "type": "Hash", "algorithm": "sha256", "hashValue": "aa" },
+                { "type": "Hash", "algorithm": "sha3_256", "hashValue": "bb" },
+                { "type": "Hash", "algorithm": "blake2b512", "hashValue": "cc" },
+                {
+                    "type": "Hash",
+                    "algorithm": "other",
+                    "hashValue": "dd",
+                    "comment": "Streebog-256"
+                }
+            ])
+        );
+    }
+}
@@ -0,0 +1,490 @@
+//! CycloneDX XML serialization.
+//!
+//! Elements are written in the order the 1.6 XSD sequences require, which
+//! differs from the JSON field order in a few places (e.g. `licenses` comes
+//! before `copyright`, `modelCard` after nested `components`). Older
+//! versions share that order; fields they lack are removed beforehand by
+//! [`downgrade`](super::downgrade::downgrade).
+
+use crate::*;
+
+/// Convert an AIBOM to a CycloneDX XML document of its `specVersion`
+pub fn to_xml(aibom: &AIBOM, pretty: bool) -> String {
+    let mut xml = XmlWriter::new(pretty);
+    xml.declaration();
+
+    let namespace = format!("http://cyclonedx.org/schema/bom/{}", aibom.spec_version);
+
+    let version = aibom.version.to_string();
+    xml.open(
+        "bom",
+        &[
+            ("xmlns", &namespace),
+            ("serialNumber", &aibom.serial_number),
+            ("version", &version),
+        ],
+    );
+    let legacy_tools = aibom.spec_version == SpecVersion::V1_4.as_str();
+    write_metadata(&mut xml, &aibom.metadata, legacy_tools);
+    if !aibom.components.is_empty() {
+        xml.open("components", &[]);
+        for component in &aibom.components {
+            write_component(&mut xml, component);
+        }
+        xml.close("components");
+    }
+    write_external_references(&mut xml, aibom.external_references.as_deref());
+    if !aibom.dependencies.is_empty() {
+        xml.open("dependencies", &[]);
+        for dependency in &aibom.dependencies {
+            if dependency.depends_on.is_empty() {
+                xml.empty("dependency", &[("ref", &dependency.reference)]);
+                continue;
+            }
+            xml.open("dependency", &[("ref", &dependency.reference)]);
+            for target in &dependency.depends_on {
+                xml.empty("dependency", &[("ref", target)]);
+            }
+            xml.close("dependency");
+        }
+        xml.close("dependencies");
+    }
+    xml.close("bom");
+
+    xml.finish()
+}
+
+fn write_metadata(xml: &mut XmlWriter, metadata: &Metadata, legacy_tools: bool) {
+    xml.open("metadata", &[]);
+    xml.leaf("timestamp", &[], &metadata.timestamp);
+    if legacy_tools && !metadata.tools.components.is_empty() {
+        xml.open("tools", &[]);
+        for tool in &metadata.tools.components {
+            xml.open("tool", &[]);
+            xml.leaf("vendor", &[], &tool.manufacturer.name);
+            xml.leaf("name", &[], &tool.name);
+            xml.leaf("version", &[], &tool.version);
+            xml.close("tool");
+        }
+        xml.close("tools");
+    } else if !metadata.tools.components.is_empty() {
+        xml.open("tools", &[]);
+        xml.open("components", &[]);
+        for tool in &metadata.tools.components {
+            xml.open(
+                "component",
+                &[("type", &tool.component_type), ("bom-ref", &tool.bom_ref)],
+            );
+            write_organization(xml, "manufacturer", &tool.manufacturer);
+            xml.leaf("name", &[], &tool.name);
+            xml.leaf("version", &[], &tool.version);
+            xml.close("component");
+        }
+        xml.close("components");
+        xml.close("tools");
+    }
+    write_component(xml, &metadata.component);
+    write_properties(xml, metadata.properties.as_deref());
+    xml.close("metadata");
+}
+
+fn write_component(xml: &mut XmlWriter, component: &Component) {
+    let mut attributes = vec![("type", component.component_type.as_str())];
+    if !component.bom_ref.is_empty() {
+        attributes.push(("bom-ref", &component.bom_ref));
+    }
+    xml.open("component", &attributes);
+
+    if let Some(supplier) = &component.supplier {
+        write_organization(xml, "supplier", supplier);
+    }
+    if let Some(manufacturer) = &component.manufacturer {
+        write_organization(xml, "manufacturer", manufacturer);
+    }
+    if let Some(authors) = component.authors.as_ref().filter(|a| !a.is_empty()) {
+        xml.open("authors", &[]);
+        for author in authors {
+            xml.open("author", &[]);
+            xml.leaf("name", &[], &author.name);
+            xml.close("author");
+        }
+        xml.close("authors");
+    }
+    xml.optional_leaf("publisher", component.publisher.as_deref());
+    xml.optional_leaf("group", component.group.as_deref());
+    xml.leaf("name", &[], &component.name);
+    xml.optional_leaf("version", component.version.as_deref());
+    xml.optional_leaf("description", component.description.as_deref());
+    if let Some(hashes) = component.hashes.as_ref().filter(|h| !h.is_empty()) {
+        xml.open("hashes", &[]);
+        for hash in hashes {
+            xml.leaf("hash", &[("alg", &hash.alg)], &hash.content);
+        }
+        xml.close("hashes");
+    }
+    if let Some(licenses) = component.licenses.as_ref().filter(|l| !l.is_empty()) {
+        xml.open("licenses", &[]);
+        for license in licenses {
+            write_license(xml, &license.license);
+        }
+        xml.close("licenses");
+    }
+    xml.optional_leaf("copyright", component.copyright.as_deref());
+    xml.optional_leaf("purl", component.purl.as_deref());
+    if let Some(pedigree) = &component.pedigree {
+        write_pedigree(xml, pedigree);
+    }
+    write_external_references(xml, component.external_references.as_deref());
+    write_properties(xml, component.properties.as_deref());
+    if let Some(children) = component.components.as_ref().filter(|c| !c.is_empty()) {
+        xml.open("components", &[]);
+        for child in children {
+            write_component(xml, child);
+        }
+        xml.close("components");
+    }
+    if let Some(model_card) = &component.model_card {
+        write_model_card(xml, model_card);
+    }
+
+    xml.close("component");
+}
+
+fn write_organization(xml: &mut XmlWriter, element: &str, organization: &Organization) {
+    xml.open(element, &[]);
+    xml.leaf("name", &[], &organization.name);
+    for url in organization.url.iter().flatten() {
+        xml.leaf("url", &[], url);
+    }
+    xml.close(element);
+}
+
+fn write_license(xml: &mut XmlWriter, license: &LicenseInfo) {
+    let mut attributes = Vec::new();
+    if let Some(acknowledgement) = &license.acknowledgement {
+        attributes.push(("acknowledgement", acknowledgement.as_str()));
+    }
+    xml.open("license", &attributes);
+    match (&license.id, &license.name) {
+        (Some(id), _) => xml.leaf("id", &[], id),
+        (None, Some(name)) => xml.leaf("name", &[], name),
+        (None, None) => xml.leaf("name", &[], "NOASSERTION"),
+    }
+    if let Some(text) = &license.text {
+        let mut attributes = Vec::new();
+        if let Some(content_type) = &text.content_type {
+            attributes.push(("content-type", content_type.as_str()));
+        }
+        if let Some(encoding) = &text.encoding {
+            attributes.push(("encoding", encoding.as_str()));
+        }
+        xml.leaf("text", &attributes, &text.content);
+    }
+    xml.optional_leaf("url", license.url.as_deref());
+    write_properties(xml, license.properties.as_deref());
+    xml.close("license");
+}
+
+fn write_pedigree(xml: &mut XmlWriter, pedigree: &Pedigree) {
+    xml.open("pedigree", &[]);
+    for (element, components) in [
+        ("ancestors", &pedigree.ancestors),
+        ("descendants", &pedigree.descendants),
+        ("variants", &pedigree.variants),
+    ] {
+        if let Some(components) = components.as_ref().filter(|c| !c.is_empty()) {
+            xml.open(element, &[]);
+            for component in components {
+                write_component(xml, component);
+            }
+            xml.close(element);
+        }
+    }
+    xml.optional_leaf("notes", pedigree.notes.as_deref());
+    xml.close("pedigree");
+}
+
+fn write_model_card(xml: &mut XmlWriter, model_card: &ModelCard) {
+    xml.open("modelCard", &[]);
+    if let Some(parameters) = &model_card.model_parameters {
+        xml.open("modelParameters", &[]);
+        xml.optional_leaf("task", parameters.task.as_deref());
+        xml.optional_leaf(
+            "architectureFamily",
+            parameters.architecture_family.as_deref(),
+        );
+        xml.optional_leaf(
+            "modelArchitecture",
+            parameters.model_architecture.as_deref(),
+        );
+        if let Some(datasets) = parameters.datasets.as_ref().filter(|d| !d.is_empty()) {
+            xml.open("datasets", &[]);
+            for dataset in datasets {
+                xml.leaf("ref", &[], &dataset.reference);
+            }
+            xml.close("datasets");
+        }
+        for (list, item, data) in [
+            ("inputs", "input", &parameters.inputs),
+            ("outputs", "output", &parameters.outputs),
+        ] {
+            if let Some(data) = data.as_ref().filter(|d| !d.is_empty()) {
+                xml.open(list, &[]);
+                for entry in data {
+                    xml.open(item, &[]);
+                    xml.leaf("format", &[], &entry.format);
+                    xml.close(item);
+                }
+                xml.close(list);
+            }
+        }
+        xml.close("modelParameters");
+    }
+    if let Some(analysis) = &model_card.quantitative_analysis {
+        xml.open("quantitativeAnalysis", &[]);
+        if let Some(metrics) = analysis
+            .performance_metrics
+            .as_ref()
+            .filter(|m| !m.is_empty())
+        {
+            xml.open("performanceMetrics", &[]);
+            for metric in metrics {
+                xml.open("performanceMetric", &[]);
+                xml.leaf("type", &[], &metric.metric_type);
+                xml.leaf("value", &[], &metric.value);
+                xml.close("performanceMetric");
+            }
+            xml.close("performanceMetrics");
+        }
+        if let Some(graphics) = &analysis.graphics {
+            match graphics.get("description").and_then(|d| d.as_str()) {
+                Some(description) => {
+                    xml.open("graphics", &[]);
+                    xml.leaf("description", &[], description);
+                    xml.close("graphics");
+                }
+                None => xml.empty("graphics", &[]),
+            }
+        }
+        xml.close("quantitativeAnalysis");
+    }
+    let properties = model_card.properties_with_extensions();
+    write_properties(xml, Some(&properties));
+    xml.close("modelCard");
+}
+
+fn write_external_references(xml: &mut XmlWriter, references: Option<&[ExternalReference]>) {
+    let Some(references) = references.filter(|r| !r.is_empty()) else {
+        return;
+    };
+    xml.open("externalReferences", &[]);
+    for reference in references {
+        xml.open("reference", &[("type", &reference.ref_type)]);
+        xml.leaf("url", &[], &reference.url);
+        xml.optional_leaf("comment", reference.comment.as_deref());
+        xml.close("reference");
+    }
+    xml.close("externalReferences");
+}
+
+fn write_properties(xml: &mut XmlWriter, properties: Option<&[Property]>) {
+    let Some(properties) = properties.filter(|p| !p.is_empty()) else {
+        return;
+    };
+    xml.open("properties", &[]);
+    for property in properties {
+        xml.leaf("property", &[("name", &property.name)], &property.value);
+    }
+    xml.close("properties");
+}
+
+/// Minimal streaming XML writer with escaping and optional indentation
+struct XmlWriter {
+    out: String,
+    depth: usize,
+    pretty: bool,
+}
+
+impl XmlWriter {
+    fn new(pretty: bool) -> Self {
+        Self {
+            out: String::new(),
+            depth: 0,
+            pretty,
+        }
+    }
+
+    fn declaration(&mut self) {
+        self.out
+            .push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        self.newline();
+    }
+
+    fn open(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.start_tag(name, attributes, false);
+        self.depth += 1;
+        self.newline();
+    }
+
+    fn close(&mut self, name: &str) {
+        self.depth -= 1;
+        self.indent();
+        self.out.push_str("</");
+        self.out.push_str(name);
+        self.out.push('>');
+        self.newline();
+    }
+
+    fn empty(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.start_tag(name, attributes, true);
+        self.newline();
+    }
+
+    fn leaf(&mut self, name: &str, attributes: &[(&str, &str)], text: &str) {
+        self.start_tag(name, attributes, false);
+        self.out.push_str(&escape(text));
+        self.out.push_str("</");
+        self.out.push_str(name);
+        self.out.push('>');
+        self.newline();
+    }
+
+    fn optional_leaf(&mut self, name: &str, text: Option<&str>) {
+        if let Some(text) = text {
+            self.leaf(name, &[], text);
+        }
+    }
+
+    fn start_tag(&mut self, name: &str, attributes: &[(&str, &str)], self_closing: bool) {
+        self.indent();
+        self.out.push('<');
+        self.out.push_str(name);
+        for (key, value) in attributes {
+            self.out.push(' ');
+            self.out.push_str(key);
+            self.out.push_str("=\"");
+            self.out.push_str(&escape(value));
+            self.out.push('"');
+        }
+        self.out.push_str(if self_closing { "/>" } else { ">" });
+    }
+
+    fn indent(&mut self) {
+        if self.pretty {
+            self.out.push_str(&"  ".repeat(self.depth));
+        }
+    }
+
+    fn newline(&mut self) {
+        if self.pretty {
+            self.out.push('\n');
+        }
+    }
+
+    fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// Escape text and attribute values, dropping characters XML 1.0 cannot represent
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{BomFixture, model};
+    use serde_json::json;
+
+    #[test]
+    fn follows_schema_element_order() {
+        let mut tuned = model("model", "pkg:huggingface/org/model@1.0");
+        tuned["licenses"] = json!([{ "license": { "id": "MIT" } }]);
+        tuned["copyright"] = json!("Org");
+        tuned["properties"] = json!([{ "name": "ai.task", "value": "text-generation" }]);
+        tuned["modelCard"] = json!({ "modelParameters": { "task": "text-generation" } });
+        let aibom = BomFixture::new()
+            .component(tuned)
+            .dependency("pkg:huggingface/org/model@1.0", &[])
+            .build();
+
+        let xml = to_xml(&aibom, false);
+        assert!(xml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?><bom xmlns="http://cyclonedx.org/schema/bom/1.6""#));
+        let position = |needle: &str| xml.find(needle).unwrap();
+        assert!(position("<licenses>") < position("<copyright>"));
+        assert!(position("<purl>") < position("<properties>"));
+        assert!(position("<properties>") < position("<modelCard>"));
+        assert!(position("<components>") < position("<dependencies>"));
+    }
+
+    #[test]
+    fn escapes_text_and_drops_invalid_characters() {
+        let mut tuned = model("model", "pkg:huggingface/org/model@1.0?file=\"a\"");
+        tuned["description"] = json!("Q&A <model>\u{1}");
+        let xml = to_xml(&BomFixture::new().component(tuned).build(), false);
+
+        assert!(xml.contains("<description>Q&amp;A &lt;model&gt;</description>"));
+        assert!(xml.contains(r#"bom-ref="pkg:huggingface/org/model@1.0?file=&quot;a&quot;""#));
+    }
+
+    #[test]
+    fn writes_empty_elements_self_closing() {
+        let mut tuned = model("model", "pkg:huggingface/org/model@1.0");
+        tuned["modelCard"] = json!({ "quantitativeAnalysis": { "graphics": {} } });
+        let aibom = BomFixture::new()
+            .component(tuned)
+            .dependency("pkg:huggingface/org/model@1.0", &[])
+            .build();
+
+        let xml = to_xml(&aibom, false);
+        assert!(xml.contains("<graphics/>"));
+        assert!(xml.contains(
+            r#"<dependencies><dependency ref="pkg:huggingface/org/model@1.0"/></dependencies>"#
+        ));
+    }
+
+    #[test]
+    fn omits_empty_components_and_dependencies() {
+        assert_eq!(
+            to_xml(&BomFixture::new().build(), false),
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.6" serialNumber="urn:uuid:00000000-0000-4000-8000-000000000000" version="1">"#,
+                r#"<metadata><timestamp>2024-05-01T12:00:00Z</timestamp>"#,
+                r#"<component type="application" bom-ref="app"><name>app</name></component>"#,
+                r#"</metadata></bom>"#
+            )
+        );
+    }
+
+    #[test]
+    fn writes_tool_elements_for_1_4() {
+        let aibom = BomFixture::new()
+            .spec_version("1.4")
+            .metadata(
+                "tools",
+                json!([{ "vendor": "Acme", "name": "bom-tool", "version": "2.1" }]),
+            )
+            .build();
+
+        let xml = to_xml(&aibom, false);
+        assert!(xml.contains(r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.4""#));
+        assert!(xml.contains(
+            "<tools><tool><vendor>Acme</vendor><name>bom-tool</name><version>2.1</version></tool></tools>"
+        ));
+    }
+}
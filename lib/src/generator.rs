@@ -1,189 +1,1401 @@
 use crate::component_generator::ComponentGenerator;
+use crate::descendant_finder::DescendantFinder;
 use crate::model_analyzer::ModelAnalyzer;
 use crate::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{debug, info, info_span, warn};
 
-/// Main AIBOM Generator that orchestrates the generation process
+/// A model waiting to be processed
+struct QueuedModel {
+    model_id: String,
+    /// Model whose dependency list led here
+    parent: Option<String>,
+    /// Relation of `parent` to this model
+    relation: Option<String>,
+    depth: usize,
+}
+
+/// Main AIBOM Generator that orchestrates the generation process.
+///
+/// Holds only configuration and shared clients, so one instance can run any
+/// number of generations (including concurrently); every run starts from
+/// fresh state while reusing the Hub client's response cache.
 pub struct AIBOMGenerator {
-    api: hf_hub::api::sync::Api,
+    config: GeneratorConfig,
+    hub: HubClient,
     component_generator: ComponentGenerator,
     model_analyzer: ModelAnalyzer,
+    observer: Option<Arc<dyn ProgressObserver>>,
+    renderer: Renderer,
+}
+
+/// State of a single generation run
+struct GenerationRun<'a> {
+    generator: &'a AIBOMGenerator,
+    /// Revision of the main model, if not `main`
+    revision: Option<String>,
+    started: Instant,
     processed_models: HashSet<String>,
     components: Vec<Component>,
-    dependencies: HashMap<String, Vec<String>>,
+    dependencies: HashMap<String, Vec<DependencyReference>>,
+    report: GenerationReport,
 }
 
 impl AIBOMGenerator {
-    /// Create a new AIBOM Generator instance
-    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
-        let api = hf_hub::api::sync::Api::new()?;
-        let component_generator = ComponentGenerator::new();
-        let model_analyzer = ModelAnalyzer::new();
-
-        Ok(Self {
-            api,
-            component_generator,
-            model_analyzer,
+    /// Create a new AIBOM Generator instance with the default configuration
+    pub fn new() -> Result<Self, GenerateError> {
+        Self::builder().build()
+    }
+
+    /// Start configuring a generator
+    pub fn builder() -> GeneratorBuilder {
+        GeneratorBuilder::default()
+    }
+
+    pub(crate) fn from_parts(
+        config: GeneratorConfig,
+        hub: HubClient,
+        observer: Option<Arc<dyn ProgressObserver>>,
+        renderer: Renderer,
+    ) -> Self {
+        Self {
+            component_generator: ComponentGenerator::with_hub(
+                hub.clone(),
+                config.enrichers.license_files,
+            ),
+            model_analyzer: ModelAnalyzer::with_hub(hub.clone()),
+            config,
+            hub,
+            observer,
+            renderer,
+        }
+    }
+
+    pub fn config(&self) -> &GeneratorConfig {
+        &self.config
+    }
+
+    /// The Hub client, for sharing with other generators
+    pub fn hub_client(&self) -> &HubClient {
+        &self.hub
+    }
+
+    /// Get model information from HuggingFace API
+    pub fn get_model_info(&self, model_id: &str) -> Result<ModelInfo, GenerateError> {
+        self.get_model_info_at_revision(model_id, None)
+    }
+
+    /// Get model information as of a branch, tag or commit; `None` means `main`
+    pub fn get_model_info_at_revision(
+        &self,
+        model_id: &str,
+        revision: Option<&str>,
+    ) -> Result<ModelInfo, GenerateError> {
+        let mut model_info = self
+            .model_analyzer
+            .fetch_model_info_at_revision(model_id, revision)?;
+        self.model_analyzer
+            .load_repo_configs(&mut model_info, &self.config.enrichers);
+        Ok(model_info)
+    }
+
+    /// Generate complete AIBOM for a given model
+    pub fn generate_aibom(&self, main_model_id: &str) -> Result<AIBOM, GenerateError> {
+        self.generate_aibom_with_report(main_model_id)
+            .map(|(aibom, _report)| aibom)
+    }
+
+    /// Generate complete AIBOM for a given model, along with a report of the
+    /// dependencies that failed or were skipped
+    pub fn generate_aibom_with_report(
+        &self,
+        main_model_id: &str,
+    ) -> Result<(AIBOM, GenerationReport), GenerateError> {
+        self.generate_aibom_at_revision(main_model_id, None)
+    }
+
+    /// Like [`generate_aibom_with_report`](Self::generate_aibom_with_report),
+    /// reading the main model as of a branch, tag or commit. The revision
+    /// becomes the model component's version and the version of its purl and
+    /// bom-ref; lineage is resolved from the dependencies' `main` branches.
+    pub fn generate_aibom_at_revision(
+        &self,
+        main_model_id: &str,
+        revision: Option<&str>,
+    ) -> Result<(AIBOM, GenerationReport), GenerateError> {
+        let mut run = GenerationRun {
+            generator: self,
+            revision: revision.map(str::to_string),
+            started: Instant::now(),
             processed_models: HashSet::new(),
             components: Vec::new(),
             dependencies: HashMap::new(),
-        })
+            report: GenerationReport::default(),
+        };
+
+        // Process main model and all dependencies (no relation for main model)
+        run.process_model_lineage(main_model_id)?;
+
+        // Generate dependency list with simplified structure. Dependencies that
+        // failed or were skipped without a placeholder have no component, and
+        // edges to them would dangle.
+        let known: HashSet<String> = run.components.iter().map(|c| c.bom_ref.clone()).collect();
+        let mut dependencies = Vec::new();
+        for (model_ref, deps) in &run.dependencies {
+            let deps: Vec<&DependencyReference> = deps
+                .iter()
+                .filter(|d| known.contains(&d.reference))
+                .collect();
+            if deps.is_empty() {
+                continue;
+            }
+            // CycloneDX dependencies cannot carry properties, so the relation of
+            // each edge is recorded on the depending component
+            let relations: Vec<Property> = deps
+                .iter()
+                .filter_map(|d| {
+                    d.relation.as_ref().map(|relation| Property {
+                        name: format!("aibom:relation:{}", d.reference),
+                        value: relation.clone(),
+                    })
+                })
+                .collect();
+            if !relations.is_empty()
+                && let Some(component) = run.components.iter_mut().find(|c| c.bom_ref == *model_ref)
+            {
+                let properties = component.properties.get_or_insert_with(Vec::new);
+                for relation in relations {
+                    if !properties
+                        .iter()
+                        .any(|p| p.name == relation.name && p.value == relation.value)
+                    {
+                        properties.push(relation);
+                    }
+                }
+            }
+            // A model can reach the same dependency through several relations
+            let mut seen = HashSet::new();
+            dependencies.push(Dependency {
+                reference: model_ref.clone(),
+                depends_on: deps
+                    .iter()
+                    .filter(|d| seen.insert(d.reference.as_str()))
+                    .map(|d| d.reference.clone())
+                    .collect(),
+            });
+        }
+
+        let aibom = self.build_aibom(
+            main_model_id,
+            run.components,
+            dependencies,
+            &run.report.skipped,
+        );
+        Ok((aibom, run.report))
     }
 
-    /// Get model information from HuggingFace API
-    pub fn get_model_info(&self, model_id: &str) -> Result<ModelInfo, Box<dyn std::error::Error>> {
-        let _repo = self.api.model(model_id.to_string());
-        self.model_analyzer.fetch_model_info_from_hf_api(model_id)
+    /// Generate an AIBOM rooted at a base model whose pedigree lists every model
+    /// derived from it, along with a flat report of the same descendants
+    pub fn generate_descendants_aibom(
+        &self,
+        base_model_id: &str,
+        max_depth: usize,
+        max_descendants: usize,
+    ) -> Result<(AIBOM, DescendantReport), GenerateError> {
+        let report = DescendantFinder::with_hub(self.hub.clone(), max_depth, max_descendants)
+            .find_descendants(base_model_id)?;
+
+        let model_info = self.get_model_info(base_model_id)?;
+        let mut base_component = self
+            .component_generator
+            .model_info_to_component(&model_info, None);
+        let descendants = self.descendant_components(base_model_id, &report);
+        if !descendants.is_empty() {
+            base_component.pedigree = Some(Pedigree {
+                descendants: Some(descendants),
+                ..Default::default()
+            });
+        }
+
+        let aibom = self.build_aibom(base_model_id, vec![base_component], Vec::new(), &[]);
+        Ok((aibom, report))
+    }
+
+    /// Generate an AIBOM describing a dataset repository
+    pub fn generate_dataset_aibom(&self, dataset_id: &str) -> Result<AIBOM, GenerateError> {
+        let dataset_info = self.model_analyzer.fetch_dataset_info(dataset_id)?;
+        let component = self
+            .component_generator
+            .create_dataset_info_component(&dataset_info);
+        let dependencies = vec![Dependency {
+            reference: component.bom_ref.clone(),
+            depends_on: Vec::new(),
+        }];
+
+        let mut aibom = self.build_aibom(dataset_id, vec![component], dependencies, &[]);
+        aibom.external_references = Some(vec![ExternalReference {
+            ref_type: "distribution".to_string(),
+            url: format!("https://huggingface.co/datasets/{}", dataset_id),
+            comment: None,
+        }]);
+        Ok(aibom)
     }
 
-    /// Process a model and its dependencies recursively
-    pub fn process_model_recursively(
+    /// Generate an AIBOM for a Space: the Space application depends on the
+    /// models and datasets it declares, each model with its full lineage.
+    /// Models that cannot be generated are listed in the report.
+    pub fn generate_space_aibom(
+        &self,
+        space_id: &str,
+    ) -> Result<(AIBOM, GenerationReport), GenerateError> {
+        let space_info = self.model_analyzer.fetch_space_info(space_id)?;
+
+        let mut report = GenerationReport::default();
+        let mut model_aiboms = Vec::new();
+        for model_id in &space_info.models {
+            match self.generate_aibom_with_report(model_id) {
+                Ok((aibom, model_report)) => {
+                    report.failures.extend(model_report.failures);
+                    report.skipped.extend(model_report.skipped);
+                    model_aiboms.push(aibom);
+                }
+                Err(e) => report.failures.push(NodeIssue {
+                    model_id: model_id.clone(),
+                    parent: Some(space_id.to_string()),
+                    depth: 1,
+                    kind: e.kind().to_string(),
+                    message: e.message().to_string(),
+                }),
+            }
+        }
+
+        let space = self.component_generator.create_space_component(&space_info);
+        let space_ref = space.bom_ref.clone();
+        let mut aibom = merge::merge(space, &model_aiboms, false);
+        for dataset_id in &space_info.datasets {
+            let mut dataset = self
+                .component_generator
+                .create_dataset_component(dataset_id, None);
+            dataset.description = Some("Dataset used by the Space".to_string());
+            let dataset_ref = dataset.bom_ref.clone();
+            if !aibom.components.iter().any(|c| c.bom_ref == dataset_ref) {
+                aibom.components.push(dataset);
+                aibom.dependencies.push(Dependency {
+                    reference: dataset_ref.clone(),
+                    depends_on: Vec::new(),
+                });
+            }
+            if let Some(dependency) = aibom
+                .dependencies
+                .iter_mut()
+                .find(|d| d.reference == space_ref)
+                && !dependency.depends_on.contains(&dataset_ref)
+            {
+                dependency.depends_on.push(dataset_ref);
+            }
+        }
+        aibom.external_references = Some(vec![ExternalReference {
+            ref_type: "distribution".to_string(),
+            url: format!("https://huggingface.co/spaces/{}", space_id),
+            comment: None,
+        }]);
+        Ok((aibom, report))
+    }
+
+    /// Generate an AIBOM for a repository of any kind
+    pub fn generate_repo_aibom(
+        &self,
+        kind: RepoKind,
+        repo_id: &str,
+    ) -> Result<(AIBOM, GenerationReport), GenerateError> {
+        match kind {
+            RepoKind::Model => self.generate_aibom_with_report(repo_id),
+            RepoKind::Dataset => Ok((
+                self.generate_dataset_aibom(repo_id)?,
+                GenerationReport::default(),
+            )),
+            RepoKind::Space => self.generate_space_aibom(repo_id),
+        }
+    }
+
+    /// List up to `max_results` repositories of `kind` owned by `author`
+    pub fn list_author_repos(
+        &self,
+        kind: RepoKind,
+        author: &str,
+        max_results: usize,
+    ) -> Result<Vec<ModelSummary>, GenerateError> {
+        self.model_analyzer
+            .fetch_repo_list(kind, &[("author", author)], max_results)
+    }
+
+    /// Build the nested descendant components of `parent` from a flat report
+    fn descendant_components(&self, parent: &str, report: &DescendantReport) -> Vec<Component> {
+        report
+            .descendants
+            .iter()
+            .filter(|entry| entry.parent == parent)
+            .map(|entry| {
+                let edge = DependencyReference {
+                    reference: format!("pkg:huggingface/{}@1.0", entry.model_id),
+                    relation: entry.relation.clone(),
+                    scope: None,
+                    properties: None,
+                };
+                let mut component = self
+                    .component_generator
+                    .create_lineage_reference(&entry.model_id, &edge);
+                component.bom_ref = edge.reference;
+
+                let children = self.descendant_components(&entry.model_id, report);
+                if !children.is_empty() {
+                    component.pedigree = Some(Pedigree {
+                        descendants: Some(children),
+                        ..Default::default()
+                    });
+                }
+                component
+            })
+            .collect()
+    }
+
+    /// Wrap components and dependencies in a CycloneDX document for the given model
+    fn build_aibom(
+        &self,
+        main_model_id: &str,
+        components: Vec<Component>,
+        dependencies: Vec<Dependency>,
+        skipped: &[NodeIssue],
+    ) -> AIBOM {
+        let (main_org, _main_name) = self
+            .model_analyzer
+            .extract_organization_from_model_id(main_model_id);
+
+        // Create main application component
+        let main_component = self
+            .component_generator
+            .create_main_application_component(main_model_id);
+
+        let mut properties = vec![
+            Property {
+                name: "primaryPurpose".to_string(),
+                value: "text-generation".to_string(),
+            },
+            Property {
+                name: "suppliedBy".to_string(),
+                value: main_org,
+            },
+        ];
+
+        // Record where traversal was cut short so consumers know the BOM is partial
+        if !skipped.is_empty() {
+            properties.push(Property {
+                name: "aibom:truncated".to_string(),
+                value: "true".to_string(),
+            });
+            for skipped in skipped {
+                properties.push(Property {
+                    name: "aibom:truncatedAt".to_string(),
+                    value: format!(
+                        "{} (depth {}, {})",
+                        skipped.model_id, skipped.depth, skipped.message
+                    ),
+                });
+            }
+        }
+
+        // Generate RFC-4122 compliant UUID
+        let uuid = uuid::Uuid::new_v4();
+
+        AIBOM {
+            bom_format: "CycloneDX".to_string(),
+            spec_version: "1.6".to_string(),
+            serial_number: format!("urn:uuid:{}", uuid),
+            version: 1,
+            metadata: Metadata {
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                tools: generator_tools(),
+                component: main_component,
+                properties: Some(properties),
+            },
+            components,
+            dependencies,
+            external_references: Some(vec![ExternalReference {
+                ref_type: "distribution".to_string(),
+                url: format!("https://huggingface.co/{}", main_model_id),
+                comment: None,
+            }]),
+            signature: None,
+        }
+    }
+
+    /// Serialize an AIBOM according to the configured output options
+    pub fn render(&self, aibom: &AIBOM) -> Result<String, GenerateError> {
+        self.renderer.render(aibom)
+    }
+
+    /// Like [`render`](Self::render), also listing the fields the configured
+    /// spec version could not represent; see [`Renderer::render_with_notes`]
+    pub fn render_with_notes(
+        &self,
+        aibom: &AIBOM,
+    ) -> Result<(String, Vec<DowngradeNote>), GenerateError> {
+        self.renderer.render_with_notes(aibom)
+    }
+}
+
+impl GenerationRun<'_> {
+    fn emit(&self, event: ProgressEvent) {
+        if let Some(observer) = &self.generator.observer {
+            observer.on_event(&event);
+        }
+    }
+
+    fn add_component(&mut self, component: Component) {
+        self.components.push(component);
+        let component = self.components.last().unwrap();
+        self.emit(ProgressEvent::ComponentAdded {
+            bom_ref: component.bom_ref.clone(),
+            name: component.name.clone(),
+            total: self.components.len(),
+        });
+    }
+
+    /// Process a model and its lineage breadth-first from an explicit work queue,
+    /// stopping at the configured traversal limits
+    fn process_model_lineage(&mut self, main_model_id: &str) -> Result<(), GenerateError> {
+        let mut queue = VecDeque::from([QueuedModel {
+            model_id: main_model_id.to_string(),
+            parent: None,
+            relation: None,
+            depth: 0,
+        }]);
+
+        while let Some(queued) = queue.pop_front() {
+            if self.processed_models.contains(&queued.model_id) {
+                continue;
+            }
+
+            // The main model is always processed, whatever the limits
+            let limit_hit = self
+                .generator
+                .config
+                .limits
+                .max_duration
+                .filter(|max| self.started.elapsed() >= *max)
+                .map(|max| format!("max-duration of {}s reached", max.as_secs()))
+                .or_else(|| {
+                    self.generator
+                        .config
+                        .limits
+                        .max_components
+                        .filter(|max| self.components.len() >= *max)
+                        .map(|max| format!("max-components of {} reached", max))
+                })
+                .filter(|_| queued.depth > 0);
+            if let Some(reason) = limit_hit {
+                // Everything still queued is dropped along with this model
+                warn!("Traversal stopped at {} ({})", queued.model_id, reason);
+                self.emit(ProgressEvent::Warning {
+                    model_id: Some(queued.model_id.clone()),
+                    message: format!("traversal stopped: {}", reason),
+                });
+                for queued in std::iter::once(queued).chain(queue.drain(..)) {
+                    self.record_skipped(&queued, &reason);
+                }
+                break;
+            }
+
+            let model_id = queued.model_id.clone();
+            let _span = info_span!("model", model_id = %model_id, depth = queued.depth).entered();
+            match self.process_model(&model_id, queued.relation.clone(), queued.depth) {
+                Ok(next) => queue.extend(next),
+                // The main model must succeed; dependency failures only lose that branch
+                Err(e) if queued.depth == 0 => return Err(e),
+                Err(e) => {
+                    warn!("Failed to process dependency {}: {}", model_id, e);
+                    self.emit(ProgressEvent::Warning {
+                        model_id: Some(model_id.clone()),
+                        message: e.to_string(),
+                    });
+                    self.report.failures.push(NodeIssue {
+                        model_id: model_id.clone(),
+                        parent: queued.parent.clone(),
+                        depth: queued.depth,
+                        kind: e.kind().to_string(),
+                        message: e.message().to_string(),
+                    });
+                    if self.generator.config.annotate_failures {
+                        self.add_component(
+                            self.generator
+                                .component_generator
+                                .create_unresolved_component(&model_id, e.kind(), e.message()),
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record a node that was not visited because a traversal limit was reached.
+    /// Without a placeholder, edges to it are dropped when the BOM is built.
+    fn record_skipped(&mut self, queued: &QueuedModel, reason: &str) {
+        if self.processed_models.contains(&queued.model_id)
+            || self
+                .report
+                .skipped
+                .iter()
+                .any(|s| s.model_id == queued.model_id)
+        {
+            return;
+        }
+
+        let error = GenerateError::LimitExceeded(reason.to_string());
+        self.emit(ProgressEvent::Warning {
+            model_id: Some(queued.model_id.clone()),
+            message: format!("skipped: {}", reason),
+        });
+        self.report.skipped.push(NodeIssue {
+            model_id: queued.model_id.clone(),
+            parent: queued.parent.clone(),
+            depth: queued.depth,
+            kind: error.kind().to_string(),
+            message: error.message().to_string(),
+        });
+        if self.generator.config.annotate_failures {
+            self.add_component(
+                self.generator
+                    .component_generator
+                    .create_unresolved_component(&queued.model_id, error.kind(), error.message()),
+            );
+        }
+    }
+
+    /// Process a single model, returning the dependencies still to be visited
+    fn process_model(
         &mut self,
         model_id: &str,
         relation: Option<String>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        if self.processed_models.contains(model_id) {
-            return Ok(());
-        }
-
-        println!("Processing model: {}", model_id);
+        depth: usize,
+    ) -> Result<Vec<QueuedModel>, GenerateError> {
+        info!("Processing model: {}", model_id);
         self.processed_models.insert(model_id.to_string());
 
-        let model_info = self.get_model_info(model_id)?;
-        let dependencies = self.model_analyzer.extract_dependencies(&model_info);
+        self.emit(ProgressEvent::FetchStarted {
+            model_id: model_id.to_string(),
+            depth,
+        });
+        let revision = self.revision.as_deref().filter(|_| depth == 0);
+        let model_info = self
+            .generator
+            .get_model_info_at_revision(model_id, revision)?;
+        let dependencies = self
+            .generator
+            .model_analyzer
+            .extract_dependencies(&model_info);
+        for (dep_id, dep_relation) in &dependencies {
+            self.emit(ProgressEvent::DependencyFound {
+                model_id: model_id.to_string(),
+                dependency: dep_id.clone(),
+                relation: dep_relation.clone(),
+            });
+        }
 
         // Separate datasets from model dependencies
         let mut dataset_dependencies = Vec::new();
         let mut model_dependencies = Vec::new();
 
         for (dep_id, relation) in &dependencies {
-            if relation.as_ref().map(|r| r.as_str()) == Some("train") {
+            if matches!(relation.as_deref(), Some("train") | Some("test")) {
                 dataset_dependencies.push((dep_id.clone(), relation.clone()));
             } else {
                 model_dependencies.push((dep_id.clone(), relation.clone()));
             }
         }
 
-        // Process dataset dependencies - only add to dependency list, no relation stored
+        // Process dataset dependencies - training and evaluation inputs, not part of the model lineage
         let mut processed_dependencies = Vec::new();
-        for (dataset_id, _) in dataset_dependencies {
+        let mut training_datasets = Vec::new();
+        let mut evaluation_datasets = Vec::new();
+        for (dataset_id, dataset_relation) in dataset_dependencies {
             let dataset_component = self
+                .generator
                 .component_generator
-                .create_dataset_component(&dataset_id);
+                .create_dataset_component(&dataset_id, dataset_relation.as_deref());
             let dep_purl = dataset_component.purl.clone().unwrap();
 
-            // Add dataset component to components list
-            self.components.push(dataset_component);
-            processed_dependencies.push(dep_purl);
-            println!("Added dataset component: {}", dataset_id);
+            // Add dataset component to components list, once per dataset
+            if !self.components.iter().any(|c| c.bom_ref == dep_purl) {
+                self.add_component(dataset_component);
+            }
+            match dataset_relation.as_deref() {
+                Some("test") => evaluation_datasets.push(dep_purl.clone()),
+                _ => training_datasets.push(dep_purl.clone()),
+            }
+            processed_dependencies.push(DependencyReference {
+                reference: dep_purl,
+                relation: dataset_relation,
+                scope: None,
+                properties: None,
+            });
+            debug!("Added dataset component: {}", dataset_id);
         }
 
-        // Process model dependencies and store their relations
+        // Process custom code files executed with trust_remote_code
+        for code_file in &model_info.remote_code {
+            let code_component = self
+                .generator
+                .component_generator
+                .create_remote_code_component(code_file);
+            let code_ref = code_component.bom_ref.clone();
+
+            self.emit(ProgressEvent::DependencyFound {
+                model_id: model_id.to_string(),
+                dependency: format!("{}/{}", code_file.repo, code_file.path),
+                relation: None,
+            });
+            if !self.components.iter().any(|c| c.bom_ref == code_ref) {
+                self.add_component(code_component);
+            }
+            processed_dependencies.push(DependencyReference {
+                reference: code_ref,
+                relation: None,
+                scope: Some("required".to_string()),
+                properties: None,
+            });
+        }
+
+        // Queue model dependencies and store their relations
+        let mut lineage = Vec::new();
+        let mut next = Vec::new();
         for (dep_model, dep_relation) in model_dependencies {
             if !self.processed_models.contains(&dep_model) {
-                match self.process_model_recursively(&dep_model, dep_relation.clone()) {
-                    Ok(_) => {
-                        let dep_purl = format!("pkg:huggingface/{}@1.0", dep_model);
-                        processed_dependencies.push(dep_purl);
-                    }
-                    Err(e) => {
-                        println!("Warning: Failed to process dependency {}: {}", dep_model, e);
-                        let dep_purl = format!("pkg:huggingface/{}@1.0", dep_model);
-                        processed_dependencies.push(dep_purl);
+                let queued = QueuedModel {
+                    model_id: dep_model.clone(),
+                    parent: Some(model_id.to_string()),
+                    relation: dep_relation.clone(),
+                    depth: depth + 1,
+                };
+                match self
+                    .generator
+                    .config
+                    .limits
+                    .max_depth
+                    .filter(|max| depth >= *max)
+                {
+                    Some(max) => {
+                        warn!("Not following {} beyond depth {}", dep_model, depth);
+                        self.record_skipped(&queued, &format!("max-depth of {} reached", max));
                     }
+                    None => next.push(queued),
                 }
-            } else {
-                let dep_purl = format!("pkg:huggingface/{}@1.0", dep_model);
-                processed_dependencies.push(dep_purl);
             }
+
+            let edge_properties = model_info
+                .merge_config
+                .as_ref()
+                .filter(|_| dep_relation.as_deref() == Some("merged"))
+                .map(|merge_config| {
+                    self.generator
+                        .component_generator
+                        .merge_edge_properties(merge_config, &dep_model)
+                })
+                .filter(|properties| !properties.is_empty());
+            let edge = DependencyReference {
+                reference: format!("pkg:huggingface/{}@1.0", dep_model),
+                relation: dep_relation,
+                scope: None,
+                properties: edge_properties,
+            };
+            processed_dependencies.push(edge.clone());
+            lineage.push((dep_model, edge));
         }
 
-        // Create component with relation information
-        let component = self
+        // Create component with relation information and its pedigree
+        let mut component = self
+            .generator
             .component_generator
             .model_info_to_component(&model_info, relation);
+        component.pedigree = self.generator.component_generator.create_pedigree(&lineage);
+        if let Some(model_card) = &mut component.model_card {
+            if let Some(model_parameters) = &mut model_card.model_parameters
+                && !training_datasets.is_empty()
+            {
+                model_parameters.datasets = Some(
+                    training_datasets
+                        .into_iter()
+                        .map(|reference| DatasetReference { reference })
+                        .collect(),
+                );
+            }
+            // CycloneDX has no slot for evaluation data, so reference it by property
+            if !evaluation_datasets.is_empty() {
+                model_card.properties.get_or_insert_with(Vec::new).extend(
+                    evaluation_datasets.into_iter().map(|reference| Property {
+                        name: "ai.evaluation.dataset".to_string(),
+                        value: reference,
+                    }),
+                );
+            }
+        }
         let bom_ref = component.bom_ref.clone();
 
-        self.components.push(component);
+        self.add_component(component);
 
-        // Record dependencies (simplified structure)
+        // Record dependencies with their relations
         if !processed_dependencies.is_empty() {
             self.dependencies.insert(bom_ref, processed_dependencies);
         }
 
-        Ok(())
+        Ok(next)
     }
+}
 
-    /// Generate complete AIBOM for a given model
-    pub fn generate_aibom(
-        &mut self,
-        main_model_id: &str,
-    ) -> Result<AIBOM, Box<dyn std::error::Error>> {
-        // Process main model and all dependencies (no relation for main model)
-        self.process_model_recursively(main_model_id, None)?;
+/// `metadata.tools` entry identifying this generator
+pub(crate) fn generator_tools() -> Tools {
+    Tools {
+        components: vec![ToolComponent {
+            bom_ref: "pkg:generic/rust-aibom-generator@1.0.0".to_string(),
+            manufacturer: Organization {
+                name: "Rust AIBOM Generator".to_string(),
+                url: None,
+            },
+            name: "rust-aibom-generator".to_string(),
+            component_type: "application".to_string(),
+            version: "1.0".to_string(),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// `api/models/{id}` response for a model with the given card data
+    fn model(id: &str, card_data: Value) -> (&str, String) {
+        let body = json!({ "modelId": id, "tags": [], "cardData": card_data });
+        (id, body.to_string())
+    }
+
+    /// Builder for a generator reading only `models` from the Hub
+    fn generator(models: Vec<(&str, String)>) -> GeneratorBuilder {
+        let paths: Vec<String> = models
+            .iter()
+            .map(|(id, _)| format!("api/models/{}", id))
+            .collect();
+        let hub = HubClient::stub(
+            paths
+                .iter()
+                .map(String::as_str)
+                .zip(models.into_iter().map(|(_, body)| body)),
+        );
+        AIBOMGenerator::builder().hub_client(hub)
+    }
+
+    fn limits(
+        max_depth: Option<usize>,
+        max_components: Option<usize>,
+        max_duration: Option<Duration>,
+    ) -> TraversalLimits {
+        TraversalLimits {
+            max_depth,
+            max_components,
+            max_duration,
+        }
+    }
+
+    fn bom_refs(aibom: &AIBOM) -> Vec<&str> {
+        aibom
+            .components
+            .iter()
+            .map(|c| c.bom_ref.as_str())
+            .collect()
+    }
+
+    fn truncated_at(aibom: &AIBOM) -> Vec<&str> {
+        aibom
+            .metadata
+            .properties
+            .iter()
+            .flatten()
+            .filter(|p| p.name == "aibom:truncatedAt")
+            .map(|p| p.value.as_str())
+            .collect()
+    }
+
+    fn dangling(aibom: &AIBOM) -> Vec<String> {
+        lint(aibom)
+            .findings
+            .into_iter()
+            .filter(|finding| finding.rule == "dangling-dependency")
+            .map(|finding| finding.message)
+            .collect()
+    }
+
+    #[test]
+    fn failed_dependency_leaves_no_dangling_edge() {
+        let models = vec![
+            model(
+                "org/main",
+                json!({ "base_model": ["org/base", "org/missing"] }),
+            ),
+            model("org/base", json!({})),
+        ];
+
+        let (aibom, report) = generator(models.clone())
+            .build()
+            .unwrap()
+            .generate_aibom_with_report("org/main")
+            .unwrap();
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].model_id, "org/missing");
+        assert_eq!(report.failures[0].kind, "not-found");
+        assert!(dangling(&aibom).is_empty(), "{:?}", dangling(&aibom));
+        assert_eq!(aibom.dependencies.len(), 1);
+        assert_eq!(
+            aibom.dependencies[0].depends_on,
+            vec!["pkg:huggingface/org/base@1.0".to_string()]
+        );
+
+        // With placeholders the edge is kept and points at the placeholder
+        let (aibom, _) = generator(models)
+            .annotate_failures(true)
+            .build()
+            .unwrap()
+            .generate_aibom_with_report("org/main")
+            .unwrap();
+        assert!(dangling(&aibom).is_empty());
+        assert_eq!(aibom.dependencies[0].depends_on.len(), 2);
+    }
+
+    #[test]
+    fn visits_lineage_breadth_first_once_per_model() {
+        // a -> b, c; b -> d; c -> d
+        let models = vec![
+            model("org/a", json!({ "base_model": ["org/b", "org/c"] })),
+            model("org/b", json!({ "base_model": "org/d" })),
+            model("org/c", json!({ "base_model": "org/d" })),
+            model("org/d", json!({})),
+        ];
+        let (aibom, report) = generator(models)
+            .build()
+            .unwrap()
+            .generate_aibom_with_report("org/a")
+            .unwrap();
+
+        assert!(report.is_complete());
+        assert_eq!(
+            bom_refs(&aibom),
+            vec![
+                "pkg:huggingface/org/a@1.0",
+                "pkg:huggingface/org/b@1.0",
+                "pkg:huggingface/org/c@1.0",
+                "pkg:huggingface/org/d@1.0",
+            ]
+        );
+        assert_eq!(aibom.dependencies.len(), 3);
+        assert!(truncated_at(&aibom).is_empty());
+    }
+
+    #[test]
+    fn stops_at_max_depth() {
+        // a -> b -> c -> d
+        let models = vec![
+            model("org/a", json!({ "base_model": "org/b" })),
+            model("org/b", json!({ "base_model": "org/c" })),
+            model("org/c", json!({ "base_model": "org/d" })),
+            model("org/d", json!({})),
+        ];
+        let (aibom, report) = generator(models.clone())
+            .limits(limits(Some(1), None, None))
+            .build()
+            .unwrap()
+            .generate_aibom_with_report("org/a")
+            .unwrap();
+
+        assert_eq!(
+            bom_refs(&aibom),
+            vec!["pkg:huggingface/org/a@1.0", "pkg:huggingface/org/b@1.0"]
+        );
+        assert_eq!(report.skipped.len(), 1);
+        let skipped = &report.skipped[0];
+        assert_eq!(skipped.model_id, "org/c");
+        assert_eq!(skipped.parent.as_deref(), Some("org/b"));
+        assert_eq!(skipped.depth, 2);
+        assert_eq!(skipped.kind, "limit-exceeded");
+        assert!(dangling(&aibom).is_empty());
+        assert_eq!(aibom.dependencies.len(), 1);
+        assert_eq!(
+            truncated_at(&aibom),
+            vec!["org/c (depth 2, max-depth of 1 reached)"]
+        );
+        assert!(
+            aibom
+                .metadata
+                .properties
+                .iter()
+                .flatten()
+                .any(|p| p.name == "aibom:truncated" && p.value == "true")
+        );
+
+        // A placeholder keeps the edge to the skipped model
+        let (aibom, _) = generator(models)
+            .limits(limits(Some(1), None, None))
+            .annotate_failures(true)
+            .build()
+            .unwrap()
+            .generate_aibom_with_report("org/a")
+            .unwrap();
+        assert_eq!(aibom.components.len(), 3);
+        assert_eq!(aibom.dependencies.len(), 2);
+        assert!(dangling(&aibom).is_empty());
+    }
+
+    #[test]
+    fn stops_at_max_components() {
+        let models = vec![
+            model(
+                "org/a",
+                json!({ "base_model": ["org/b", "org/c", "org/d"] }),
+            ),
+            model("org/b", json!({})),
+            model("org/c", json!({})),
+            model("org/d", json!({})),
+        ];
+        let (aibom, report) = generator(models)
+            .limits(limits(None, Some(2), None))
+            .build()
+            .unwrap()
+            .generate_aibom_with_report("org/a")
+            .unwrap();
+
+        assert_eq!(aibom.components.len(), 2);
+        let skipped: Vec<_> = report.skipped.iter().map(|s| s.model_id.as_str()).collect();
+        assert_eq!(skipped, vec!["org/c", "org/d"]);
+        assert!(
+            report
+                .skipped
+                .iter()
+                .all(|s| s.message == "max-components of 2 reached")
+        );
+        assert_eq!(truncated_at(&aibom).len(), 2);
+        assert!(dangling(&aibom).is_empty());
+    }
+
+    #[test]
+    fn stops_at_max_duration_after_main_model() {
+        let models = vec![
+            model("org/a", json!({ "base_model": "org/b" })),
+            model("org/b", json!({})),
+        ];
+        let (aibom, report) = generator(models)
+            .limits(limits(None, None, Some(Duration::ZERO)))
+            .build()
+            .unwrap()
+            .generate_aibom_with_report("org/a")
+            .unwrap();
+
+        assert_eq!(bom_refs(&aibom), vec!["pkg:huggingface/org/a@1.0"]);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].message, "max-duration of 0s reached");
+        assert!(aibom.dependencies.is_empty());
+    }
+
+    #[test]
+    fn versions_revision_and_its_remote_code_by_the_revision() {
+        let config = json!({
+            "auto_map": {
+                "AutoConfig": "configuration_a.AConfig",
+                "AutoModel": "org/shared--modeling_shared.SharedModel"
+            }
+        });
+        let info = json!({
+            "modelId": "org/a",
+            "tags": [],
+            "siblings": [{ "rfilename": "config.json" }]
+        });
+        let hub = HubClient::stub([
+            ("api/models/org/a/revision/refs%2Fpr%2F1", info.to_string()),
+            (
+                "org/a/resolve/refs%2Fpr%2F1/config.json",
+                config.to_string(),
+            ),
+            (
+                "org/a/resolve/refs%2Fpr%2F1/configuration_a.py",
+                "a".to_string(),
+            ),
+            (
+                "org/shared/resolve/main/modeling_shared.py",
+                "s".to_string(),
+            ),
+        ]);
+        let generator = AIBOMGenerator::builder().hub_client(hub).build().unwrap();
+        let (aibom, report) = generator
+            .generate_aibom_at_revision("org/a", Some("refs/pr/1"))
+            .unwrap();
 
-        // Generate dependency list with simplified structure
-        let dependencies: Vec<Dependency> = self
+        assert!(report.is_complete());
+        let model = &aibom.components[2];
+        assert_eq!(model.bom_ref, "pkg:huggingface/org/a@refs%2Fpr%2F1");
+        assert_eq!(model.version.as_deref(), Some("refs/pr/1"));
+
+        let local = &aibom.components[0];
+        assert_eq!(
+            local.bom_ref,
+            "pkg:huggingface/org/a@refs%2Fpr%2F1#configuration_a.py"
+        );
+        assert_eq!(
+            local.external_references.as_ref().unwrap()[0].url,
+            "https://huggingface.co/org/a/blob/refs%2Fpr%2F1/configuration_a.py"
+        );
+        assert!(local.hashes.is_some());
+
+        // Code from another repository still comes from its main branch
+        let shared = &aibom.components[1];
+        assert_eq!(
+            shared.bom_ref,
+            "pkg:huggingface/org/shared@1.0#modeling_shared.py"
+        );
+        assert_eq!(
+            shared.external_references.as_ref().unwrap()[0].url,
+            "https://huggingface.co/org/shared/blob/main/modeling_shared.py"
+        );
+        assert!(shared.hashes.is_some());
+        assert!(dangling(&aibom).is_empty());
+    }
+
+    #[test]
+    fn records_edge_relations_on_the_depending_component() {
+        let models = vec![
+            model(
+                "org/main",
+                json!({
+                    "base_model": "org/base",
+                    "base_model_relation": "finetune",
+                    "datasets": ["org/data"]
+                }),
+            ),
+            model("org/base", json!({})),
+        ];
+        let (aibom, _) = generator(models)
+            .build()
+            .unwrap()
+            .generate_aibom_with_report("org/main")
+            .unwrap();
+
+        let main = aibom
+            .components
+            .iter()
+            .find(|c| c.bom_ref == "pkg:huggingface/org/main@1.0")
+            .unwrap();
+        let relations: Vec<(&str, &str)> = main
+            .properties
+            .iter()
+            .flatten()
+            .filter(|p| p.name.starts_with("aibom:relation:"))
+            .map(|p| (p.name.as_str(), p.value.as_str()))
+            .collect();
+        assert_eq!(
+            relations,
+            vec![
+                (
+                    "aibom:relation:pkg:huggingface-dataset/org/data@1.0",
+                    "train"
+                ),
+                ("aibom:relation:pkg:huggingface/org/base@1.0", "finetune"),
+            ]
+        );
+        // Every relation names a target of the component's dependsOn
+        let depends_on = &aibom
             .dependencies
             .iter()
-            .map(|(model_ref, deps)| Dependency {
-                reference: model_ref.clone(),
-                depends_on: deps.clone(), // Now just Vec<String>
+            .find(|d| d.reference == main.bom_ref)
+            .unwrap()
+            .depends_on;
+        assert!(relations.iter().all(|(name, _)| {
+            depends_on.contains(&name.trim_start_matches("aibom:relation:").to_string())
+        }));
+    }
+
+    #[test]
+    fn records_gating_terms_as_license_acknowledgement() {
+        let body = json!({
+            "modelId": "org/gated",
+            "tags": [],
+            "license": "Apache-2.0",
+            "gated": "auto",
+            "cardData": { "extra_gated_prompt": "Do not misuse this model." }
+        });
+        let hub = HubClient::stub([("api/models/org/gated", body.to_string())]);
+        let generator = AIBOMGenerator::builder().hub_client(hub).build().unwrap();
+        let aibom = generator.generate_aibom("org/gated").unwrap();
+
+        let model = &aibom.components[0];
+        let property = |name: &str| {
+            model
+                .properties
+                .iter()
+                .flatten()
+                .find(|p| p.name == name)
+                .map(|p| p.value.as_str())
+        };
+        assert_eq!(property("ai.access.gated"), Some("auto"));
+        assert_eq!(
+            property("ai.access.terms"),
+            Some("Do not misuse this model.")
+        );
+        let terms_sha256 = property("ai.access.termsSha256").unwrap();
+
+        let licenses = model.licenses.as_ref().unwrap();
+        assert_eq!(licenses.len(), 2);
+        assert_eq!(licenses[0].license.id.as_deref(), Some("Apache-2.0"));
+        let terms = &licenses[1].license;
+        assert_eq!(terms.id, None);
+        assert_eq!(
+            terms.name.as_deref(),
+            Some("Gated access terms for org/gated")
+        );
+        assert_eq!(terms.acknowledgement.as_deref(), Some("declared"));
+        assert_eq!(
+            terms.text.as_ref().map(|text| text.content.as_str()),
+            Some("Do not misuse this model.")
+        );
+        let terms_properties = terms.properties.as_ref().unwrap();
+        assert_eq!(terms_properties[0].name, "ai.access.termsSha256");
+        assert_eq!(terms_properties[0].value, terms_sha256);
+    }
+
+    #[test]
+    fn nests_descendants_under_their_parents() {
+        let entry = |model_id: &str, parent: &str, depth, relation: Option<&str>| DescendantEntry {
+            model_id: model_id.to_string(),
+            parent: parent.to_string(),
+            depth,
+            relation: relation.map(str::to_string),
+        };
+        // base -> a -> b, base -> c; org/x hangs off a model outside the tree
+        let report = DescendantReport {
+            base_model: "org/base".to_string(),
+            max_depth: 2,
+            descendants: vec![
+                entry("org/a", "org/base", 1, Some("finetuned")),
+                entry("org/c", "org/base", 1, None),
+                entry("org/b", "org/a", 2, Some("quantized")),
+                entry("org/x", "org/other", 2, None),
+            ],
+            truncated: false,
+        };
+
+        let generator = generator(Vec::new()).build().unwrap();
+        let top = generator.descendant_components("org/base", &report);
+        let refs: Vec<&str> = top.iter().map(|c| c.bom_ref.as_str()).collect();
+        assert_eq!(
+            refs,
+            vec!["pkg:huggingface/org/a@1.0", "pkg:huggingface/org/c@1.0"]
+        );
+
+        let relation = |component: &Component| {
+            component
+                .properties
+                .iter()
+                .flatten()
+                .find(|p| p.name == "ai.model.relation")
+                .map(|p| p.value.clone())
+        };
+        assert_eq!(relation(&top[0]).as_deref(), Some("finetuned"));
+        assert_eq!(relation(&top[1]), None);
+        assert!(top[1].pedigree.is_none());
+
+        let nested = top[0]
+            .pedigree
+            .as_ref()
+            .and_then(|pedigree| pedigree.descendants.as_ref())
+            .unwrap();
+        assert_eq!(nested.len(), 1);
+        assert_eq!(nested[0].bom_ref, "pkg:huggingface/org/b@1.0");
+        assert_eq!(nested[0].group.as_deref(), Some("org"));
+        assert_eq!(nested[0].name, "b");
+        assert_eq!(relation(&nested[0]).as_deref(), Some("quantized"));
+        assert!(nested[0].pedigree.is_none());
+    }
+
+    /// Observer recording every event it receives
+    fn recorder() -> (Arc<dyn ProgressObserver>, Arc<Mutex<Vec<ProgressEvent>>>) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let observer = move |event: &ProgressEvent| sink.lock().unwrap().push(event.clone());
+        (Arc::new(observer), events)
+    }
+
+    #[test]
+    fn reports_progress_in_traversal_order() {
+        let models = vec![
+            model(
+                "org/main",
+                json!({ "base_model": ["org/base", "org/missing"] }),
+            ),
+            model("org/base", json!({})),
+        ];
+        let (observer, events) = recorder();
+        generator(models)
+            .observer(observer)
+            .build()
+            .unwrap()
+            .generate_aibom("org/main")
+            .unwrap();
+
+        let fetch = |model_id: &str, depth| ProgressEvent::FetchStarted {
+            model_id: model_id.to_string(),
+            depth,
+        };
+        let found = |dependency: &str| ProgressEvent::DependencyFound {
+            model_id: "org/main".to_string(),
+            dependency: dependency.to_string(),
+            relation: Some("merged".to_string()),
+        };
+        let added = |name: &str, total| ProgressEvent::ComponentAdded {
+            bom_ref: format!("pkg:huggingface/org/{}@1.0", name),
+            name: name.to_string(),
+            total,
+        };
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                fetch("org/main", 0),
+                found("org/base"),
+                found("org/missing"),
+                added("main", 1),
+                fetch("org/base", 1),
+                added("base", 2),
+                fetch("org/missing", 1),
+                ProgressEvent::Warning {
+                    model_id: Some("org/missing".to_string()),
+                    message: "not found: model org/missing (status 404)".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn warns_once_per_node_dropped_at_a_limit() {
+        let models = vec![
+            model("org/main", json!({ "base_model": ["org/a", "org/b"] })),
+            model("org/a", json!({})),
+            model("org/b", json!({})),
+        ];
+        let (observer, events) = recorder();
+        generator(models)
+            .limits(limits(None, Some(1), None))
+            .observer(observer)
+            .build()
+            .unwrap()
+            .generate_aibom("org/main")
+            .unwrap();
+
+        let warnings: Vec<(Option<String>, String)> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                ProgressEvent::Warning { model_id, message } => {
+                    Some((model_id.clone(), message.clone()))
+                }
+                _ => None,
             })
             .collect();
+        let warning =
+            |model_id: &str, message: &str| (Some(model_id.to_string()), message.to_string());
+        assert_eq!(
+            warnings,
+            vec![
+                warning("org/a", "traversal stopped: max-components of 1 reached"),
+                warning("org/a", "skipped: max-components of 1 reached"),
+                warning("org/b", "skipped: max-components of 1 reached"),
+            ]
+        );
+    }
 
-        let (main_org, _main_name) = self
-            .model_analyzer
-            .extract_organization_from_model_id(main_model_id);
+    #[test]
+    fn runs_on_one_generator_do_not_share_state() {
+        let models = vec![
+            model("org/a", json!({ "base_model": ["org/b", "org/missing"] })),
+            model("org/b", json!({})),
+            model("org/c", json!({ "base_model": "org/b" })),
+        ];
+        let generator = generator(models).build().unwrap();
 
-        // Create main application component
-        let main_component = self
-            .component_generator
-            .create_main_application_component(main_model_id);
+        let (first, first_report) = generator.generate_aibom_with_report("org/a").unwrap();
+        let (second, second_report) = generator.generate_aibom_with_report("org/c").unwrap();
+        assert_eq!(first_report.failures.len(), 1);
+        assert!(second_report.is_complete());
+        assert_eq!(
+            bom_refs(&second),
+            vec!["pkg:huggingface/org/c@1.0", "pkg:huggingface/org/b@1.0"]
+        );
+        assert_eq!(second.dependencies.len(), 1);
+        assert_eq!(
+            second.dependencies[0].reference,
+            "pkg:huggingface/org/c@1.0"
+        );
 
-        // Generate RFC-4122 compliant UUID
-        let uuid = uuid::Uuid::new_v4();
+        // Concurrent runs see the same results as sequential ones
+        let (a, c) = std::thread::scope(|scope| {
+            let a = scope.spawn(|| generator.generate_aibom("org/a").unwrap());
+            let c = scope.spawn(|| generator.generate_aibom("org/c").unwrap());
+            (a.join().unwrap(), c.join().unwrap())
+        });
+        assert_eq!(bom_refs(&a), bom_refs(&first));
+        assert_eq!(bom_refs(&c), bom_refs(&second));
+    }
 
-        let aibom = AIBOM {
-            bom_format: "CycloneDX".to_string(),
-            spec_version: "1.6".to_string(),
-            serial_number: format!("urn:uuid:{}", uuid),
-            version: 1,
-            metadata: Metadata {
-                timestamp: chrono::Utc::now().to_rfc3339(),
-                tools: Tools {
-                    components: vec![ToolComponent {
-                        bom_ref: "pkg:generic/rust-aibom-generator@1.0.0".to_string(),
-                        manufacturer: Organization {
-                            name: "Rust AIBOM Generator".to_string(),
-                            url: None,
-                        },
-                        name: "rust-aibom-generator".to_string(),
-                        component_type: "application".to_string(),
-                        version: "1.0".to_string(),
-                    }],
-                },
-                component: main_component,
-                properties: Some(vec![
-                    Property {
-                        name: "primaryPurpose".to_string(),
-                        value: "text-generation".to_string(),
-                    },
-                    Property {
-                        name: "suppliedBy".to_string(),
-                        value: main_org,
-                    },
-                ]),
-            },
-            components: self.components.clone(),
-            dependencies,
-            external_references: Some(vec![ExternalReference {
-                ref_type: "distribution".to_string(),
-                url: format!("https://huggingface.co/{}", main_model_id),
-                comment: None,
-            }]),
+    #[test]
+    fn signer_requires_cyclonedx_json_output() {
+        use p256::pkcs8::{EncodePrivateKey, LineEnding};
+
+        let key = ed25519_dalek::SigningKey::from_bytes(&[7; 32])
+            .to_pkcs8_pem(LineEnding::LF)
+            .unwrap();
+        let signer = Signer::new(SigningKey::from_pem(&key).unwrap());
+        let output = |format| OutputOptions {
+            format,
+            ..Default::default()
         };
 
-        Ok(aibom)
+        let rejected = generator(Vec::new())
+            .output(output(OutputFormat::CyclonedxXml))
+            .signer(signer.clone())
+            .build();
+        assert!(matches!(rejected, Err(GenerateError::Invalid(_))));
+
+        let generator = generator(vec![model("org/a", json!({}))])
+            .output(output(OutputFormat::CyclonedxJson))
+            .signer(signer)
+            .build()
+            .unwrap();
+        let aibom = generator.generate_aibom("org/a").unwrap();
+        let rendered: Value = serde_json::from_str(&generator.render(&aibom).unwrap()).unwrap();
+        assert_eq!(rendered["signature"]["algorithm"], "Ed25519");
+    }
+
+    #[test]
+    fn generators_can_share_a_hub_client() {
+        let first = generator(vec![model("org/a", json!({}))]).build().unwrap();
+        let second = AIBOMGenerator::builder()
+            .hub_client(first.hub_client().clone())
+            .annotate_failures(true)
+            .build()
+            .unwrap();
+
+        let aibom = second.generate_aibom("org/a").unwrap();
+        assert_eq!(bom_refs(&aibom), vec!["pkg:huggingface/org/a@1.0"]);
+        assert!(second.config().annotate_failures);
+        assert!(!first.config().annotate_failures);
     }
 }
@@ -0,0 +1,160 @@
+use crate::*;
+use reqwest::blocking::{Client, Response};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Upper bound on cached responses before stale entries are evicted
+const MAX_CACHE_ENTRIES: usize = 10_000;
+
+/// Connection settings for the Hugging Face Hub
+#[derive(Debug, Clone)]
+pub struct HubConfig {
+    /// Base URL of the Hub, `HF_ENDPOINT` or `https://huggingface.co` by default
+    pub endpoint: String,
+    /// Access token for private or gated repositories, `HF_TOKEN` by default
+    pub token: Option<String>,
+    /// Per-request timeout
+    pub timeout: Duration,
+    /// How long successful responses are reused; `None` disables caching
+    pub cache_ttl: Option<Duration>,
+}
+
+impl Default for HubConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: std::env::var("HF_ENDPOINT")
+                .unwrap_or_else(|_| "https://huggingface.co".to_string()),
+            token: std::env::var("HF_TOKEN").ok().filter(|t| !t.is_empty()),
+            timeout: Duration::from_secs(30),
+            cache_ttl: Some(Duration::from_secs(3600)),
+        }
+    }
+}
+
+/// HTTP client for the Hub. Clones share the connection pool and a cache of
+/// successful responses, so one client can serve many generation runs.
+#[derive(Clone)]
+pub struct HubClient {
+    endpoint: String,
+    token: Option<String>,
+    client: Client,
+    cache_ttl: Option<Duration>,
+    cache: Arc<Mutex<HashMap<String, (Instant, String)>>>,
+    /// Canned responses by path; any other path is not found
+    #[cfg(test)]
+    stub: Option<Arc<HashMap<String, String>>>,
+}
+
+impl Default for HubClient {
+    fn default() -> Self {
+        Self::new(&HubConfig::default()).expect("failed to initialize HTTP client")
+    }
+}
+
+impl HubClient {
+    pub fn new(config: &HubConfig) -> Result<Self, GenerateError> {
+        let client = Client::builder().timeout(config.timeout).build()?;
+
+        Ok(Self {
+            endpoint: config.endpoint.trim_end_matches('/').to_string(),
+            token: config.token.clone(),
+            client,
+            cache_ttl: config.cache_ttl,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            #[cfg(test)]
+            stub: None,
+        })
+    }
+
+    /// Client answering `get_text` from canned `(path, body)` pairs instead of the network
+    #[cfg(test)]
+    pub(crate) fn stub<'a>(responses: impl IntoIterator<Item = (&'a str, String)>) -> Self {
+        let mut client = Self::new(&HubConfig {
+            endpoint: "http://hub.invalid".to_string(),
+            token: None,
+            timeout: Duration::from_secs(1),
+            cache_ttl: None,
+        })
+        .unwrap();
+        client.stub = Some(Arc::new(
+            responses
+                .into_iter()
+                .map(|(path, body)| (path.to_string(), body))
+                .collect(),
+        ));
+        client
+    }
+
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Absolute URL for a path on the Hub
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.endpoint, path.trim_start_matches('/'))
+    }
+
+    /// GET a URL, turning unsuccessful responses into classified errors
+    pub fn get(&self, url: &str, context: &str) -> Result<Response, GenerateError> {
+        let mut request = self
+            .client
+            .get(url)
+            .header("User-Agent", "rust-aibom-generator/1.0");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send()?;
+
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let error_code = response
+            .headers()
+            .get("x-error-code")
+            .and_then(|code| code.to_str().ok());
+        Err(GenerateError::from_status(
+            response.status().as_u16(),
+            error_code,
+            context,
+        ))
+    }
+
+    /// GET a path on the Hub as text, answering repeated requests from the cache
+    pub fn get_text(&self, path: &str, context: &str) -> Result<String, GenerateError> {
+        #[cfg(test)]
+        if let Some(stub) = &self.stub {
+            return stub
+                .get(path.trim_start_matches('/'))
+                .cloned()
+                .ok_or_else(|| GenerateError::from_status(404, None, context));
+        }
+
+        let url = self.url(path);
+        let Some(ttl) = self.cache_ttl else {
+            return Ok(self.get(&url, context)?.text()?);
+        };
+
+        if let Some((fetched, cached)) = self.cache.lock().unwrap().get(&url)
+            && fetched.elapsed() < ttl
+        {
+            return Ok(cached.clone());
+        }
+
+        let text = self.get(&url, context)?.text()?;
+        let mut cache = self.cache.lock().unwrap();
+        if cache.len() >= MAX_CACHE_ENTRIES {
+            cache.retain(|_, (fetched, _)| fetched.elapsed() < ttl);
+            if cache.len() >= MAX_CACHE_ENTRIES {
+                cache.clear();
+            }
+        }
+        cache.insert(url, (Instant::now(), text.clone()));
+        Ok(text)
+    }
+
+    /// Drop all cached responses
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+}
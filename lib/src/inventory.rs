@@ -0,0 +1,373 @@
+//! Aggregate view over the AIBOMs of many repositories, e.g. everything an
+//! organization publishes on the Hub.
+
+use crate::diff::purl_key;
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Outcome for one scanned repository
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryRepo {
+    pub id: String,
+    pub kind: RepoKind,
+    /// Where the repository's AIBOM was written, if anywhere
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<usize>,
+    /// Licenses found anywhere in the repository's AIBOM
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub licenses: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A gated model some scanned repositories depend on
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GatedDependency {
+    /// Approval mode, or `inaccessible` when the scan had no access to it
+    pub status: String,
+    pub used_by: Vec<String>,
+}
+
+/// A repository, or a node in its lineage, that could not be processed
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryFailure {
+    /// Scanned repository
+    pub repo: String,
+    /// The repository itself or the dependency that failed
+    pub id: String,
+    pub kind: String,
+    pub message: String,
+}
+
+/// Licenses, upstream models, gated dependencies and failures across the
+/// AIBOMs of many repositories
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Inventory {
+    pub author: String,
+    pub generated_at: String,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub repos: Vec<InventoryRepo>,
+    /// Scanned repositories per license
+    pub licenses: BTreeMap<String, Vec<String>>,
+    /// Scanned repositories per Hub model they build on or use
+    pub base_models: BTreeMap<String, Vec<String>>,
+    pub gated_dependencies: BTreeMap<String, GatedDependency>,
+    pub failures: Vec<InventoryFailure>,
+}
+
+impl Inventory {
+    pub fn new(author: &str) -> Self {
+        Self {
+            author: author.to_string(),
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            total: 0,
+            succeeded: 0,
+            failed: 0,
+            repos: Vec::new(),
+            licenses: BTreeMap::new(),
+            base_models: BTreeMap::new(),
+            gated_dependencies: BTreeMap::new(),
+            failures: Vec::new(),
+        }
+    }
+
+    /// Record the AIBOM generated for repository `id`; returns its entry so
+    /// the caller can fill in `file`
+    pub fn add(
+        &mut self,
+        id: &str,
+        kind: RepoKind,
+        aibom: &AIBOM,
+        report: &GenerationReport,
+    ) -> &mut InventoryRepo {
+        let own_key = format!("pkg:huggingface/{}", id);
+        let mut licenses = Vec::new();
+
+        for component in all_components(aibom) {
+            for license in component.licenses.iter().flatten() {
+                if let Some(license) = license
+                    .license
+                    .id
+                    .as_ref()
+                    .or(license.license.name.as_ref())
+                    && !licenses.contains(license)
+                {
+                    licenses.push(license.clone());
+                }
+            }
+
+            let Some(key) = component.purl.as_deref().map(purl_key) else {
+                continue;
+            };
+            let Some(model_id) = key.strip_prefix("pkg:huggingface/") else {
+                continue;
+            };
+            if key == own_key || component.component_type != "machine-learning-model" {
+                continue;
+            }
+            insert_user(&mut self.base_models, model_id, id);
+            let gated = component
+                .properties
+                .iter()
+                .flatten()
+                .find(|p| p.name == "ai.access.gated");
+            if let Some(gated) = gated {
+                self.gated(model_id, &gated.value, id);
+            }
+        }
+
+        for issue in report.failures.iter() {
+            if issue.kind == GenerateError::Gated(String::new()).kind() {
+                self.gated(&issue.model_id, "inaccessible", id);
+            }
+            self.failures.push(InventoryFailure {
+                repo: id.to_string(),
+                id: issue.model_id.clone(),
+                kind: issue.kind.clone(),
+                message: issue.message.clone(),
+            });
+        }
+
+        licenses.sort();
+        for license in &licenses {
+            insert_user(&mut self.licenses, license, id);
+        }
+        self.total += 1;
+        self.succeeded += 1;
+        self.repos.push(InventoryRepo {
+            id: id.to_string(),
+            kind,
+            file: None,
+            components: Some(aibom.components.len()),
+            licenses,
+            error_kind: None,
+            error: None,
+        });
+        self.repos.last_mut().unwrap()
+    }
+
+    /// Record a repository whose AIBOM could not be generated
+    pub fn add_failure(&mut self, id: &str, kind: RepoKind, error_kind: &str, message: &str) {
+        self.total += 1;
+        self.failed += 1;
+        self.repos.push(InventoryRepo {
+            id: id.to_string(),
+            kind,
+            file: None,
+            components: None,
+            licenses: Vec::new(),
+            error_kind: Some(error_kind.to_string()),
+            error: Some(message.to_string()),
+        });
+        self.failures.push(InventoryFailure {
+            repo: id.to_string(),
+            id: id.to_string(),
+            kind: error_kind.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    fn gated(&mut self, model_id: &str, status: &str, user: &str) {
+        let entry = self
+            .gated_dependencies
+            .entry(model_id.to_string())
+            .or_insert_with(|| GatedDependency {
+                status: status.to_string(),
+                used_by: Vec::new(),
+            });
+        if !entry.used_by.iter().any(|u| u == user) {
+            entry.used_by.push(user.to_string());
+        }
+    }
+}
+
+fn insert_user(map: &mut BTreeMap<String, Vec<String>>, key: &str, user: &str) {
+    let users = map.entry(key.to_string()).or_default();
+    if !users.iter().any(|u| u == user) {
+        users.push(user.to_string());
+    }
+}
+
+/// Metadata component, components and their nested components
+fn all_components(aibom: &AIBOM) -> Vec<&Component> {
+    fn walk<'a>(component: &'a Component, out: &mut Vec<&'a Component>) {
+        out.push(component);
+        for child in component.components.iter().flatten() {
+            walk(child, out);
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(&aibom.metadata.component, &mut out);
+    for component in &aibom.components {
+        walk(component, &mut out);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{BomFixture, dataset, model};
+    use serde_json::json;
+
+    fn licensed(mut component: Value, license: Value) -> Value {
+        component["licenses"] = json!([{ "license": license }]);
+        component
+    }
+
+    /// `org/tuned`, built on `meta/base` whose access is gated manually
+    fn tuned() -> AIBOM {
+        let mut base = licensed(
+            model("base", "pkg:huggingface/meta/base@1.0"),
+            json!({ "name": "llama3" }),
+        );
+        base["properties"] = json!([{ "name": "ai.access.gated", "value": "manual" }]);
+        BomFixture::new()
+            .component(licensed(
+                model("tuned", "pkg:huggingface/org/tuned@1.0"),
+                json!({ "id": "Apache-2.0" }),
+            ))
+            .component(base)
+            .build()
+    }
+
+    #[test]
+    fn aggregates_licenses_per_repo_and_across_repos() {
+        let other = BomFixture::new()
+            .component(licensed(
+                model("other", "pkg:huggingface/org/other@1.0"),
+                json!({ "id": "Apache-2.0" }),
+            ))
+            .build();
+
+        let mut inventory = Inventory::new("org");
+        inventory.add(
+            "org/tuned",
+            RepoKind::Model,
+            &tuned(),
+            &GenerationReport::default(),
+        );
+        inventory.add(
+            "org/other",
+            RepoKind::Model,
+            &other,
+            &GenerationReport::default(),
+        );
+
+        assert_eq!(inventory.repos[0].licenses, ["Apache-2.0", "llama3"]);
+        assert_eq!(inventory.licenses["Apache-2.0"], ["org/tuned", "org/other"]);
+        assert_eq!(inventory.licenses["llama3"], ["org/tuned"]);
+    }
+
+    #[test]
+    fn lists_hub_models_other_than_the_repo_as_base_models() {
+        let mut aibom = tuned();
+        aibom.components.extend(
+            [
+                dataset("train", "pkg:huggingface-dataset/org/train@1.0"),
+                model("local", "pkg:generic/local@1.0"),
+            ]
+            .map(|c| serde_json::from_value(c).unwrap()),
+        );
+
+        let mut inventory = Inventory::new("org");
+        inventory.add(
+            "org/tuned",
+            RepoKind::Model,
+            &aibom,
+            &GenerationReport::default(),
+        );
+
+        assert_eq!(
+            inventory.base_models.keys().collect::<Vec<_>>(),
+            ["meta/base"]
+        );
+        assert_eq!(inventory.base_models["meta/base"], ["org/tuned"]);
+    }
+
+    #[test]
+    fn records_gated_dependencies_including_inaccessible_ones() {
+        let report = GenerationReport {
+            failures: vec![NodeIssue {
+                model_id: "meta/private".to_string(),
+                parent: Some("org/tuned".to_string()),
+                depth: 1,
+                kind: "gated".to_string(),
+                message: "no access".to_string(),
+            }],
+            ..Default::default()
+        };
+
+        let mut inventory = Inventory::new("org");
+        inventory.add("org/tuned", RepoKind::Model, &tuned(), &report);
+
+        assert_eq!(inventory.gated_dependencies["meta/base"].status, "manual");
+        assert_eq!(
+            inventory.gated_dependencies["meta/private"].status,
+            "inaccessible"
+        );
+        assert_eq!(
+            inventory.gated_dependencies["meta/private"].used_by,
+            ["org/tuned"]
+        );
+        assert_eq!(inventory.failures[0].repo, "org/tuned");
+        assert_eq!(inventory.failures[0].id, "meta/private");
+        // A lineage failure does not fail the scanned repository
+        assert_eq!((inventory.succeeded, inventory.failed), (1, 0));
+    }
+
+    #[test]
+    fn records_repos_that_could_not_be_generated() {
+        let mut inventory = Inventory::new("org");
+        inventory
+            .add(
+                "org/tuned",
+                RepoKind::Model,
+                &tuned(),
+                &GenerationReport::default(),
+            )
+            .file = Some("org__tuned.json".to_string());
+        inventory.add_failure("org/broken", RepoKind::Dataset, "not-found", "gone");
+
+        assert_eq!(
+            (inventory.total, inventory.succeeded, inventory.failed),
+            (2, 1, 1)
+        );
+        assert_eq!(inventory.repos[0].file.as_deref(), Some("org__tuned.json"));
+        let broken = &inventory.repos[1];
+        assert_eq!(broken.error_kind.as_deref(), Some("not-found"));
+        assert_eq!(broken.components, None);
+        let failed: Vec<&str> = inventory.failures.iter().map(|f| f.id.as_str()).collect();
+        assert_eq!(failed, ["org/broken"]);
+    }
+
+    #[test]
+    fn counts_an_empty_bom_as_a_repo_without_findings() {
+        let mut inventory = Inventory::new("org");
+        let repo = inventory.add(
+            "org/empty",
+            RepoKind::Space,
+            &BomFixture::new().build(),
+            &GenerationReport::default(),
+        );
+        assert_eq!(repo.components, Some(0));
+        assert!(repo.licenses.is_empty());
+
+        assert_eq!((inventory.total, inventory.succeeded), (1, 1));
+        assert!(inventory.licenses.is_empty());
+        assert!(inventory.base_models.is_empty());
+        assert!(inventory.gated_dependencies.is_empty());
+    }
+}
@@ -1,12 +1,42 @@
-pub mod models;
+pub mod component_generator;
+pub mod config;
+pub mod descendant_finder;
+pub mod diff;
+pub mod error;
+pub mod formats;
 pub mod generator;
-pub mod model_analyzer;
+pub mod hub_client;
+pub mod inventory;
 pub mod license_handler;
-pub mod component_generator;
+pub mod lint;
+pub mod merge;
+pub mod model_analyzer;
+pub mod models;
+pub mod progress;
+pub mod renderer;
+pub mod signing;
+#[cfg(test)]
+mod test_fixtures;
+pub mod validation;
+pub mod verification;
 
-pub use models::*;
+pub use config::*;
+pub use diff::*;
+pub use error::*;
+pub use formats::OutputFormat;
+pub use formats::downgrade::SpecVersion;
 pub use generator::*;
+pub use hub_client::*;
+pub use inventory::*;
+pub use lint::*;
+pub use merge::*;
+pub use models::*;
+pub use progress::*;
+pub use renderer::*;
 pub use serde_json::Value;
+pub use signing::*;
+pub use validation::*;
+pub use verification::*;
 
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
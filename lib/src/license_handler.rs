@@ -1,38 +1,33 @@
+use crate::model_analyzer::encode_revision;
 use crate::*;
 
-pub struct LicenseHandler;
+pub struct LicenseHandler {
+    /// Client used to look for LICENSE files; `None` disables the lookup
+    hub: Option<HubClient>,
+}
+
+impl Default for LicenseHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl LicenseHandler {
     pub fn new() -> Self {
-        Self
+        Self::with_hub(Some(HubClient::default()))
     }
 
-    pub fn normalize_license(&self, license: &str, model_info: &ModelInfo) -> Option<LicenseInfo> {
-        // Try different normalization strategies to find a valid SPDX license ID
-        let variations = [
-            license.to_string(),                      // Original
-            license.to_lowercase(),                   // Lowercase
-            license.to_uppercase(),                   // Uppercase
-            license.to_lowercase().replace(" ", "-"), // Lowercase with dashes
-            license.to_lowercase().replace("-", " "), // Lowercase with spaces
-            license.replace(" ", "-"),                // Original with dashes
-            license.replace("-", " "),                // Original with spaces
-        ];
+    pub fn with_hub(hub: Option<HubClient>) -> Self {
+        Self { hub }
+    }
 
-        // Check each variation against SPDX license IDs
-        for variant in &variations {
-            if let Some(spdx_license) = spdx::license_id(variant) {
-                return Some(LicenseInfo {
-                    id: Some(spdx_license.name.to_string()),
-                    name: None, // For SPDX licenses, only keep id
-                    url: Some(format!("https://spdx.org/licenses/{}", spdx_license.name)),
-                    text: None,
-                });
-            }
+    pub fn normalize_license(&self, license: &str, model_info: &ModelInfo) -> Option<LicenseInfo> {
+        if let Some(spdx_license) = self.spdx_license(license) {
+            return Some(spdx_license);
         }
 
         // If not found in SPDX, try to find LICENSE file URL from HuggingFace repo
-        if let Some(license_url) = self.find_license_file_url(&model_info.model_id) {
+        if let Some(license_url) = self.find_license_file_url(model_info) {
             // Try to get license name from card_data.license_name first, then model_info.license, fallback to original license string
             let license_name = model_info
                 .card_data
@@ -48,6 +43,8 @@ impl LicenseHandler {
                 name: Some(license_name),
                 url: Some(license_url),
                 text: None,
+                acknowledgement: None,
+                properties: None,
             });
         }
 
@@ -55,14 +52,52 @@ impl LicenseHandler {
         None
     }
 
-    fn find_license_file_url(&self, model_id: &str) -> Option<String> {
-        use reqwest::blocking::Client;
-        use std::time::Duration;
+    /// License of a dataset or Space: the SPDX license if `license` names one,
+    /// otherwise `license` as a license name
+    pub fn declared_license(&self, license: &str) -> LicenseInfo {
+        self.spdx_license(license).unwrap_or_else(|| LicenseInfo {
+            id: None,
+            name: Some(license.to_string()),
+            url: None,
+            text: None,
+            acknowledgement: None,
+            properties: None,
+        })
+    }
 
-        let client = Client::builder()
-            .timeout(Duration::from_secs(10))
-            .build()
-            .ok()?;
+    /// SPDX license named by `license`, tolerating case and space/dash differences
+    fn spdx_license(&self, license: &str) -> Option<LicenseInfo> {
+        // Try different normalization strategies to find a valid SPDX license ID
+        let variations = [
+            license.to_string(),                      // Original
+            license.to_lowercase(),                   // Lowercase
+            license.to_uppercase(),                   // Uppercase
+            license.to_lowercase().replace(" ", "-"), // Lowercase with dashes
+            license.to_lowercase().replace("-", " "), // Lowercase with spaces
+            license.replace(" ", "-"),                // Original with dashes
+            license.replace("-", " "),                // Original with spaces
+        ];
+
+        // Check each variation against SPDX license IDs
+        for variant in &variations {
+            if let Some(spdx_license) = spdx::license_id(variant) {
+                return Some(LicenseInfo {
+                    id: Some(spdx_license.name.to_string()),
+                    name: None, // For SPDX licenses, only keep id
+                    url: Some(format!("https://spdx.org/licenses/{}", spdx_license.name)),
+                    text: None,
+                    acknowledgement: None,
+                    properties: None,
+                });
+            }
+        }
+
+        None
+    }
+
+    fn find_license_file_url(&self, model_info: &ModelInfo) -> Option<String> {
+        let hub = self.hub.as_ref()?;
+        let revision = encode_revision(model_info.revision.as_deref().unwrap_or("main"));
 
         // Try common LICENSE file names
         let license_files = [
@@ -73,23 +108,9 @@ impl LicenseHandler {
             "license.txt",
         ];
 
-        for filename in &license_files {
-            let url = format!(
-                "https://huggingface.co/{}/resolve/main/{}",
-                model_id, filename
-            );
-
-            if let Ok(response) = client
-                .get(&url)
-                .header("User-Agent", "rust-aibom-generator/1.0")
-                .send()
-            {
-                if response.status().is_success() {
-                    return Some(url);
-                }
-            }
-        }
-
-        None
+        license_files.iter().find_map(|filename| {
+            let path = format!("{}/resolve/{}/{}", model_info.model_id, revision, filename);
+            hub.get_text(&path, filename).ok().map(|_| hub.url(&path))
+        })
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,597 @@
+//! Semantic checks on AIBOMs that schema validation cannot express: graph
+//! integrity, duplicates, missing provenance data and placeholder values.
+
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// How serious a lint finding is
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+
+    pub fn parse(severity: &str) -> Option<Self> {
+        [Self::Info, Self::Warning, Self::Error]
+            .into_iter()
+            .find(|s| s.as_str().eq_ignore_ascii_case(severity))
+    }
+}
+
+/// A rule violation
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LintFinding {
+    pub rule: String,
+    pub severity: Severity,
+    /// Component the finding is about, if any
+    #[serde(rename = "bomRef", skip_serializing_if = "Option::is_none")]
+    pub bom_ref: Option<String>,
+    /// JSON pointer into the CycloneDX JSON form of the AIBOM
+    pub pointer: String,
+    pub message: String,
+}
+
+/// Findings of a lint run, most severe first
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct LintReport {
+    pub findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    /// Severity of the most serious finding
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|f| f.severity).max()
+    }
+
+    /// Number of findings at `severity`
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .count()
+    }
+}
+
+/// What a check function reports: location and message only, the engine adds
+/// the rule name and severity
+struct Hit {
+    bom_ref: Option<String>,
+    pointer: String,
+    message: String,
+}
+
+type Check = fn(&AIBOM, &mut Vec<Hit>);
+
+/// A named lint rule
+#[derive(Clone)]
+pub struct LintRule {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default_severity: Severity,
+    check: Check,
+}
+
+/// All built-in rules
+pub fn lint_rules() -> Vec<LintRule> {
+    vec![
+        LintRule {
+            name: "dangling-dependency",
+            description: "dependency graph refers to a bom-ref no component defines",
+            default_severity: Severity::Error,
+            check: dangling_dependency,
+        },
+        LintRule {
+            name: "duplicate-bom-ref",
+            description: "several components share a bom-ref",
+            default_severity: Severity::Error,
+            check: duplicate_bom_ref,
+        },
+        LintRule {
+            name: "duplicate-dependency",
+            description: "a component has several dependency entries or repeats a dependsOn target",
+            default_severity: Severity::Warning,
+            check: duplicate_dependency,
+        },
+        LintRule {
+            name: "unreferenced-metadata-component",
+            description: "the metadata component is not part of the dependency graph",
+            default_severity: Severity::Warning,
+            check: unreferenced_metadata_component,
+        },
+        LintRule {
+            name: "missing-license",
+            description: "component declares no license",
+            default_severity: Severity::Warning,
+            check: missing_license,
+        },
+        LintRule {
+            name: "missing-supplier",
+            description: "component names neither a supplier nor a manufacturer",
+            default_severity: Severity::Warning,
+            check: missing_supplier,
+        },
+        LintRule {
+            name: "missing-hash",
+            description: "model or file component carries no hash",
+            default_severity: Severity::Info,
+            check: missing_hash,
+        },
+        LintRule {
+            name: "placeholder-value",
+            description: "value is a placeholder such as NOASSERTION or unknown",
+            default_severity: Severity::Warning,
+            check: placeholder_value,
+        },
+    ]
+}
+
+/// Runs the built-in rules, with per-rule severities
+pub struct Linter {
+    rules: Vec<(LintRule, Option<Severity>)>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self {
+            rules: lint_rules()
+                .into_iter()
+                .map(|rule| {
+                    let severity = Some(rule.default_severity);
+                    (rule, severity)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Linter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the severity of a rule; `None` disables it. Fails for unknown
+    /// rule names.
+    pub fn set_severity(
+        &mut self,
+        rule: &str,
+        severity: Option<Severity>,
+    ) -> Result<&mut Self, String> {
+        let (_, current) = self
+            .rules
+            .iter_mut()
+            .find(|(r, _)| r.name == rule)
+            .ok_or_else(|| format!("unknown lint rule '{}'", rule))?;
+        *current = severity;
+        Ok(self)
+    }
+
+    /// Disable a rule
+    pub fn disable(&mut self, rule: &str) -> Result<&mut Self, String> {
+        self.set_severity(rule, None)
+    }
+
+    pub fn lint(&self, aibom: &AIBOM) -> LintReport {
+        let mut findings = Vec::new();
+        for (rule, severity) in &self.rules {
+            let Some(severity) = severity else {
+                continue;
+            };
+            let mut hits = Vec::new();
+            (rule.check)(aibom, &mut hits);
+            findings.extend(hits.into_iter().map(|hit| LintFinding {
+                rule: rule.name.to_string(),
+                severity: *severity,
+                bom_ref: hit.bom_ref,
+                pointer: hit.pointer,
+                message: hit.message,
+            }));
+        }
+        findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+        LintReport { findings }
+    }
+}
+
+/// Lint an AIBOM with the default rule set
+pub fn lint(aibom: &AIBOM) -> LintReport {
+    Linter::default().lint(aibom)
+}
+
+/// Components that can carry a bom-ref, with their JSON pointers: the
+/// metadata component, top-level components and their nested components
+fn components(aibom: &AIBOM) -> Vec<(String, &Component)> {
+    fn walk<'a>(pointer: String, component: &'a Component, out: &mut Vec<(String, &'a Component)>) {
+        out.push((pointer.clone(), component));
+        for (index, child) in component.components.iter().flatten().enumerate() {
+            walk(format!("{}/components/{}", pointer, index), child, out);
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(
+        "/metadata/component".to_string(),
+        &aibom.metadata.component,
+        &mut out,
+    );
+    for (index, component) in aibom.components.iter().enumerate() {
+        walk(format!("/components/{}", index), component, &mut out);
+    }
+    out
+}
+
+/// Components described by the BOM, i.e. everything but the metadata component
+fn described_components(aibom: &AIBOM) -> Vec<(String, &Component)> {
+    components(aibom)
+        .into_iter()
+        .filter(|(pointer, _)| !pointer.starts_with("/metadata/"))
+        .collect()
+}
+
+fn bom_ref_of(component: &Component) -> Option<String> {
+    (!component.bom_ref.is_empty()).then(|| component.bom_ref.clone())
+}
+
+fn dangling_dependency(aibom: &AIBOM, hits: &mut Vec<Hit>) {
+    let defined: HashSet<&str> = components(aibom)
+        .into_iter()
+        .map(|(_, c)| c.bom_ref.as_str())
+        .filter(|r| !r.is_empty())
+        .collect();
+
+    for (index, dependency) in aibom.dependencies.iter().enumerate() {
+        if !defined.contains(dependency.reference.as_str()) {
+            hits.push(Hit {
+                bom_ref: Some(dependency.reference.clone()),
+                pointer: format!("/dependencies/{}/ref", index),
+                message: format!("no component has bom-ref '{}'", dependency.reference),
+            });
+        }
+        for (target_index, target) in dependency.depends_on.iter().enumerate() {
+            if !defined.contains(target.as_str()) {
+                hits.push(Hit {
+                    bom_ref: Some(dependency.reference.clone()),
+                    pointer: format!("/dependencies/{}/dependsOn/{}", index, target_index),
+                    message: format!("depends on '{}', which no component defines", target),
+                });
+            }
+        }
+    }
+}
+
+fn duplicate_bom_ref(aibom: &AIBOM, hits: &mut Vec<Hit>) {
+    let mut seen: HashMap<&str, String> = HashMap::new();
+    // Components come in document order, so the later occurrence is reported
+    for (pointer, component) in components(aibom) {
+        if component.bom_ref.is_empty() {
+            continue;
+        }
+        match seen.get(component.bom_ref.as_str()) {
+            Some(first) => hits.push(Hit {
+                bom_ref: bom_ref_of(component),
+                pointer,
+                message: format!(
+                    "bom-ref '{}' is already used at {}",
+                    component.bom_ref, first
+                ),
+            }),
+            None => {
+                seen.insert(&component.bom_ref, pointer);
+            }
+        }
+    }
+}
+
+fn duplicate_dependency(aibom: &AIBOM, hits: &mut Vec<Hit>) {
+    let mut entries = HashSet::new();
+    for (index, dependency) in aibom.dependencies.iter().enumerate() {
+        if !entries.insert(dependency.reference.as_str()) {
+            hits.push(Hit {
+                bom_ref: Some(dependency.reference.clone()),
+                pointer: format!("/dependencies/{}", index),
+                message: format!(
+                    "'{}' has more than one dependency entry",
+                    dependency.reference
+                ),
+            });
+        }
+        let mut targets = HashSet::new();
+        for (target_index, target) in dependency.depends_on.iter().enumerate() {
+            if !targets.insert(target.as_str()) {
+                hits.push(Hit {
+                    bom_ref: Some(dependency.reference.clone()),
+                    pointer: format!("/dependencies/{}/dependsOn/{}", index, target_index),
+                    message: format!("'{}' is listed more than once", target),
+                });
+            }
+        }
+    }
+}
+
+fn unreferenced_metadata_component(aibom: &AIBOM, hits: &mut Vec<Hit>) {
+    let reference = aibom.metadata.component.bom_ref.as_str();
+    if reference.is_empty() {
+        hits.push(Hit {
+            bom_ref: None,
+            pointer: "/metadata/component".to_string(),
+            message: "metadata component has no bom-ref".to_string(),
+        });
+        return;
+    }
+    let referenced = aibom.dependencies.iter().any(|dependency| {
+        dependency.reference == reference || dependency.depends_on.iter().any(|t| t == reference)
+    });
+    if !referenced {
+        hits.push(Hit {
+            bom_ref: Some(reference.to_string()),
+            pointer: "/metadata/component".to_string(),
+            message: format!(
+                "'{}' does not appear in the dependency graph, so nothing connects it to the components",
+                reference
+            ),
+        });
+    }
+}
+
+fn missing_license(aibom: &AIBOM, hits: &mut Vec<Hit>) {
+    for (pointer, component) in described_components(aibom) {
+        if component.licenses.as_ref().is_none_or(|l| l.is_empty()) {
+            hits.push(Hit {
+                bom_ref: bom_ref_of(component),
+                pointer,
+                message: format!("'{}' declares no license", component.name),
+            });
+        }
+    }
+}
+
+fn missing_supplier(aibom: &AIBOM, hits: &mut Vec<Hit>) {
+    for (pointer, component) in described_components(aibom) {
+        if component.supplier.is_none() && component.manufacturer.is_none() {
+            hits.push(Hit {
+                bom_ref: bom_ref_of(component),
+                pointer,
+                message: format!("'{}' has no supplier or manufacturer", component.name),
+            });
+        }
+    }
+}
+
+fn missing_hash(aibom: &AIBOM, hits: &mut Vec<Hit>) {
+    for (pointer, component) in described_components(aibom) {
+        let hashable = matches!(
+            component.component_type.as_str(),
+            "machine-learning-model" | "file"
+        );
+        if hashable && component.hashes.as_ref().is_none_or(|h| h.is_empty()) {
+            hits.push(Hit {
+                bom_ref: bom_ref_of(component),
+                pointer,
+                message: format!("'{}' carries no hash", component.name),
+            });
+        }
+    }
+}
+
+const PLACEHOLDERS: &[&str] = &["NOASSERTION", "NONE", "unknown", "N/A", "TBD", "TODO"];
+
+fn placeholder_value(aibom: &AIBOM, hits: &mut Vec<Hit>) {
+    fn walk(pointer: &str, value: &Value, hits: &mut Vec<Hit>) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    let key = key.replace('~', "~0").replace('/', "~1");
+                    walk(&format!("{}/{}", pointer, key), value, hits);
+                }
+            }
+            Value::Array(items) => {
+                for (index, value) in items.iter().enumerate() {
+                    walk(&format!("{}/{}", pointer, index), value, hits);
+                }
+            }
+            Value::String(text) => {
+                let trimmed = text.trim();
+                if trimmed.is_empty() {
+                    hits.push(Hit {
+                        bom_ref: None,
+                        pointer: pointer.to_string(),
+                        message: "value is empty".to_string(),
+                    });
+                } else if PLACEHOLDERS.iter().any(|p| p.eq_ignore_ascii_case(trimmed)) {
+                    hits.push(Hit {
+                        bom_ref: None,
+                        pointer: pointer.to_string(),
+                        message: format!("placeholder value '{}'", trimmed),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Ok(document) = serde_json::to_value(aibom) {
+        walk("", &document, hits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::BomFixture;
+    use serde_json::json;
+
+    /// A component that passes the license, supplier and hash rules
+    fn complete(component_type: &str, bom_ref: &str) -> Value {
+        json!({
+            "type": component_type,
+            "bom-ref": bom_ref,
+            "name": bom_ref,
+            "supplier": { "name": "org" },
+            "hashes": [{ "alg": "SHA-256", "content": "aa" }],
+            "licenses": [{ "license": { "id": "MIT" } }]
+        })
+    }
+
+    fn found(aibom: &AIBOM) -> Vec<(String, String)> {
+        lint(aibom)
+            .findings
+            .into_iter()
+            .map(|f| (f.rule, f.pointer))
+            .collect()
+    }
+
+    fn finding(rule: &str, pointer: &str) -> (String, String) {
+        (rule.to_string(), pointer.to_string())
+    }
+
+    #[test]
+    fn accepts_a_connected_complete_document() {
+        let aibom = BomFixture::new()
+            .component(complete("machine-learning-model", "model"))
+            .component(complete("data", "data"))
+            .dependency("app", &["model"])
+            .dependency("model", &["data"])
+            .build();
+        assert_eq!(found(&aibom), []);
+    }
+
+    #[test]
+    fn flags_only_the_unconnected_metadata_component_of_an_empty_document() {
+        assert_eq!(
+            found(&BomFixture::new().build()),
+            [finding(
+                "unreferenced-metadata-component",
+                "/metadata/component"
+            )]
+        );
+    }
+
+    #[test]
+    fn flags_dangling_dependencies() {
+        let aibom = BomFixture::new()
+            .component(complete("machine-learning-model", "model"))
+            .dependency("app", &["model", "failed"])
+            .dependency("gone", &[])
+            .build();
+
+        let report = lint(&aibom);
+        assert_eq!(report.max_severity(), Some(Severity::Error));
+        assert_eq!(
+            found(&aibom),
+            [
+                finding("dangling-dependency", "/dependencies/0/dependsOn/1"),
+                finding("dangling-dependency", "/dependencies/1/ref"),
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_duplicate_bom_refs_at_the_later_occurrence() {
+        let mut model = complete("machine-learning-model", "model");
+        model["components"] = json!([complete("data", "data")]);
+        let aibom = BomFixture::new()
+            .component(model)
+            .component(complete("data", "data"))
+            .dependency("app", &["model"])
+            .build();
+
+        assert_eq!(
+            found(&aibom),
+            [finding("duplicate-bom-ref", "/components/1")]
+        );
+    }
+
+    #[test]
+    fn flags_repeated_dependency_entries_and_targets() {
+        let aibom = BomFixture::new()
+            .component(complete("machine-learning-model", "model"))
+            .dependency("app", &["model", "model"])
+            .dependency("app", &[])
+            .build();
+
+        assert_eq!(
+            found(&aibom),
+            [
+                finding("duplicate-dependency", "/dependencies/0/dependsOn/1"),
+                finding("duplicate-dependency", "/dependencies/1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_missing_license_supplier_and_model_hash() {
+        let aibom = BomFixture::new()
+            .component(
+                json!({ "type": "machine-learning-model", "bom-ref": "model", "name": "model" }),
+            )
+            .component(json!({ "type": "data", "bom-ref": "data", "name": "data" }))
+            .dependency("app", &["model", "data"])
+            .build();
+
+        let report = lint(&aibom);
+        assert_eq!(report.count(Severity::Warning), 4);
+        assert_eq!(report.count(Severity::Info), 1);
+        let found = found(&aibom);
+        for expected in [
+            finding("missing-license", "/components/0"),
+            finding("missing-license", "/components/1"),
+            finding("missing-supplier", "/components/0"),
+            finding("missing-supplier", "/components/1"),
+            // Only models and files are expected to carry hashes
+            finding("missing-hash", "/components/0"),
+        ] {
+            assert!(found.contains(&expected), "missing {:?}", expected);
+        }
+    }
+
+    #[test]
+    fn flags_placeholder_and_empty_values() {
+        let mut model = complete("machine-learning-model", "model");
+        model["licenses"] = json!([{ "license": { "name": "NOASSERTION" } }]);
+        model["version"] = json!("Unknown");
+        model["description"] = json!(" ");
+        let aibom = BomFixture::new()
+            .component(model)
+            .dependency("app", &["model"])
+            .build();
+
+        assert_eq!(
+            found(&aibom),
+            [
+                finding("placeholder-value", "/components/0/description"),
+                finding("placeholder-value", "/components/0/licenses/0/license/name"),
+                finding("placeholder-value", "/components/0/version"),
+            ]
+        );
+    }
+
+    #[test]
+    fn applies_configured_severities() {
+        let aibom = BomFixture::new()
+            .component(complete("data", "data"))
+            .component(complete("data", "data"))
+            .dependency("app", &["data", "failed"])
+            .build();
+        assert_eq!(lint(&aibom).max_severity(), Some(Severity::Error));
+
+        let mut linter = Linter::new();
+        linter.disable("dangling-dependency").unwrap();
+        linter
+            .set_severity("duplicate-bom-ref", Some(Severity::Info))
+            .unwrap();
+        let report = linter.lint(&aibom);
+        assert_eq!(report.max_severity(), Some(Severity::Info));
+        assert_eq!(report.findings[0].rule, "duplicate-bom-ref");
+        assert!(linter.disable("no-such-rule").is_err());
+    }
+}
@@ -0,0 +1,488 @@
+//! Combining the AIBOMs of several models into one describing a product that
+//! uses all of them.
+
+use crate::generator::generator_tools;
+use crate::*;
+use std::collections::{HashMap, HashSet};
+
+/// Component property naming the `serialNumber` of each input BOM that
+/// contributed the component
+pub const MERGED_FROM_PROPERTY: &str = "aibom:mergedFrom";
+
+/// Metadata property listing the `serialNumber` of each input BOM
+pub const MERGED_INPUT_PROPERTY: &str = "aibom:mergedInput";
+
+/// Merge `inputs` into a new AIBOM whose metadata component is an application
+/// named `name`.
+///
+/// Components are deduplicated by purl across inputs and by bom-ref within
+/// one input, combining their properties and licenses; the first input's
+/// other fields win. A component whose bom-ref is already taken by a different
+/// component of an earlier input is renamed `input<n>:<bom-ref>`, `n` being
+/// the 1-based position of its input. Each input's metadata component becomes a regular component that the
+/// new application depends on, and which itself depends on the roots of its
+/// input's dependency graph, so the merged graph stays connected.
+pub fn merge_aiboms(name: &str, version: &str, inputs: &[AIBOM]) -> AIBOM {
+    merge_aiboms_into(application_component(name, version), inputs)
+}
+
+/// Like [`merge_aiboms`], with a caller-built metadata component
+pub fn merge_aiboms_into(product: Component, inputs: &[AIBOM]) -> AIBOM {
+    merge(product, inputs, true)
+}
+
+/// Merge `inputs` under `product`; without `provenance` no `aibom:mergedFrom`
+/// or `aibom:mergedInput` properties are added, for inputs that are only
+/// intermediate results
+pub(crate) fn merge(product: Component, inputs: &[AIBOM], provenance: bool) -> AIBOM {
+    let mut merger = Merger {
+        provenance,
+        ..Default::default()
+    };
+    merger.bom_refs.insert(product.bom_ref.clone());
+    merger.depend(&product.bom_ref, []);
+
+    for (position, input) in inputs.iter().enumerate() {
+        let source = input.serial_number.as_str();
+        merger.start_input(format!("input{}:", position + 1));
+        // Bom-refs of this input's components as they appear in the output
+        let mut refs: HashMap<&str, String> = HashMap::new();
+
+        let application = merger.add(&input.metadata.component, source);
+        refs.insert(&input.metadata.component.bom_ref, application.clone());
+        for component in &input.components {
+            let merged = merger.add(component, source);
+            refs.insert(&component.bom_ref, merged);
+        }
+        let resolve = |reference: &str| {
+            refs.get(reference)
+                .cloned()
+                .unwrap_or_else(|| reference.to_string())
+        };
+
+        for dependency in &input.dependencies {
+            merger.depend(
+                &resolve(&dependency.reference),
+                dependency.depends_on.iter().map(|target| resolve(target)),
+            );
+        }
+        let targets: HashSet<&str> = input
+            .dependencies
+            .iter()
+            .flat_map(|d| d.depends_on.iter().map(String::as_str))
+            .collect();
+        let roots: Vec<String> = input
+            .components
+            .iter()
+            .filter(|c| !c.bom_ref.is_empty() && !targets.contains(c.bom_ref.as_str()))
+            .map(|c| resolve(&c.bom_ref))
+            .collect();
+        merger.depend(&application, roots);
+        merger.depend(&product.bom_ref, [application]);
+
+        for reference in input.external_references.iter().flatten() {
+            let duplicate = merger.references.iter().any(|r: &ExternalReference| {
+                r.ref_type == reference.ref_type && r.url == reference.url
+            });
+            if !duplicate {
+                merger.references.push(reference.clone());
+            }
+        }
+    }
+
+    AIBOM {
+        bom_format: "CycloneDX".to_string(),
+        spec_version: "1.6".to_string(),
+        serial_number: format!("urn:uuid:{}", uuid::Uuid::new_v4()),
+        version: 1,
+        metadata: Metadata {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            tools: generator_tools(),
+            component: product,
+            properties: provenance.then(|| {
+                inputs
+                    .iter()
+                    .map(|input| Property {
+                        name: MERGED_INPUT_PROPERTY.to_string(),
+                        value: input.serial_number.clone(),
+                    })
+                    .collect()
+            }),
+        },
+        components: merger.components,
+        dependencies: merger.dependencies,
+        external_references: (!merger.references.is_empty()).then_some(merger.references),
+        signature: None,
+    }
+}
+
+fn application_component(name: &str, version: &str) -> Component {
+    let purl = format!("pkg:generic/{}@{}", name.replace('/', "%2F"), version);
+    Component {
+        component_type: "application".to_string(),
+        bom_ref: purl.clone(),
+        name: name.to_string(),
+        version: Some(version.to_string()),
+        description: None,
+        group: None,
+        publisher: None,
+        supplier: None,
+        manufacturer: None,
+        authors: None,
+        copyright: None,
+        hashes: None,
+        licenses: None,
+        external_references: None,
+        purl: Some(purl),
+        pedigree: None,
+        model_card: None,
+        properties: None,
+        components: None,
+        signature: None,
+    }
+}
+
+#[derive(Default)]
+struct Merger {
+    components: Vec<Component>,
+    /// Index into `components` by purl
+    purls: HashMap<String, usize>,
+    /// Index into `components` by bom-ref as written in the current input
+    input_refs: HashMap<String, usize>,
+    /// Prefix for bom-refs of the current input that are taken in the output
+    prefix: String,
+    /// Bom-refs used in the output
+    bom_refs: HashSet<String>,
+    dependencies: Vec<Dependency>,
+    references: Vec<ExternalReference>,
+    /// Whether to record `aibom:mergedFrom` properties
+    provenance: bool,
+}
+
+impl Merger {
+    fn start_input(&mut self, prefix: String) {
+        self.input_refs.clear();
+        self.prefix = prefix;
+    }
+
+    /// Add or fold in `component`, returning its bom-ref in the output.
+    /// Bom-refs only identify components within one input, so across inputs
+    /// components are matched by purl alone.
+    fn add(&mut self, component: &Component, source: &str) -> String {
+        let existing = self
+            .input_refs
+            .get(&component.bom_ref)
+            .or_else(|| component.purl.as_ref().and_then(|p| self.purls.get(p)))
+            .copied();
+        let index = match existing {
+            Some(index) => {
+                let merged = &mut self.components[index];
+                for property in component.properties.iter().flatten() {
+                    add_property(merged, property.clone());
+                }
+                for license in component.licenses.iter().flatten() {
+                    let licenses = merged.licenses.get_or_insert_with(Vec::new);
+                    let duplicate = licenses.iter().any(|l| {
+                        l.license.id == license.license.id && l.license.name == license.license.name
+                    });
+                    if !duplicate {
+                        licenses.push(license.clone());
+                    }
+                }
+                index
+            }
+            None => {
+                let index = self.components.len();
+                let mut bom_ref = component.bom_ref.clone();
+                while self.bom_refs.contains(&bom_ref) {
+                    bom_ref = format!("{}{}", self.prefix, bom_ref);
+                }
+                self.bom_refs.insert(bom_ref.clone());
+                if let Some(purl) = &component.purl {
+                    self.purls.entry(purl.clone()).or_insert(index);
+                }
+                // Merged components are rewritten, so input signatures no longer hold
+                self.components.push(Component {
+                    bom_ref,
+                    signature: None,
+                    ..component.clone()
+                });
+                index
+            }
+        };
+        if !component.bom_ref.is_empty() {
+            self.input_refs.insert(component.bom_ref.clone(), index);
+        }
+        let merged = &mut self.components[index];
+        if self.provenance {
+            add_property(
+                merged,
+                Property {
+                    name: MERGED_FROM_PROPERTY.to_string(),
+                    value: source.to_string(),
+                },
+            );
+        }
+        merged.bom_ref.clone()
+    }
+
+    /// Union `targets` into the dependency entry of `reference`
+    fn depend(&mut self, reference: &str, targets: impl IntoIterator<Item = String>) {
+        let position = match self
+            .dependencies
+            .iter()
+            .position(|d| d.reference == reference)
+        {
+            Some(position) => position,
+            None => {
+                self.dependencies.push(Dependency {
+                    reference: reference.to_string(),
+                    depends_on: Vec::new(),
+                });
+                self.dependencies.len() - 1
+            }
+        };
+        let depends_on = &mut self.dependencies[position].depends_on;
+        for target in targets {
+            if !depends_on.contains(&target) {
+                depends_on.push(target);
+            }
+        }
+    }
+}
+
+fn add_property(component: &mut Component, property: Property) {
+    let properties = component.properties.get_or_insert_with(Vec::new);
+    if !properties
+        .iter()
+        .any(|p| p.name == property.name && p.value == property.value)
+    {
+        properties.push(property);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{BomFixture, model};
+    use serde_json::json;
+
+    const BASE: &str = "pkg:huggingface/org/base@1.0";
+
+    /// An application for `name` using a model fine-tuned from the shared
+    /// `org/base`, which is licensed `license` in this input
+    fn aibom(serial: &str, name: &str, license: &str) -> AIBOM {
+        let app = format!("pkg:generic/{}@1.0", name.replace('/', "%2F"));
+        let purl = format!("pkg:huggingface/{}@1.0", name);
+        let mut base = model("org/base", BASE);
+        base["description"] = json!(format!("base as seen by {}", serial));
+        base["licenses"] = json!([{ "license": { "id": license } }]);
+        base["properties"] = json!([{ "name": "source", "value": serial }]);
+        BomFixture::new()
+            .serial_number(serial)
+            .metadata(
+                "component",
+                json!({ "type": "application", "bom-ref": app, "name": "app", "purl": app }),
+            )
+            .component(model(name, &purl))
+            .component(base)
+            .dependency(&purl, &[BASE])
+            .dependency(BASE, &[])
+            .build()
+    }
+
+    fn inputs() -> [AIBOM; 2] {
+        [
+            aibom("urn:uuid:a", "org/embedder", "MIT"),
+            aibom("urn:uuid:b", "org/reranker", "Apache-2.0"),
+        ]
+    }
+
+    fn properties(component: &Component) -> Vec<(&str, &str)> {
+        component
+            .properties
+            .iter()
+            .flatten()
+            .map(|p| (p.name.as_str(), p.value.as_str()))
+            .collect()
+    }
+
+    fn depends_on(aibom: &AIBOM, reference: &str) -> Vec<String> {
+        aibom
+            .dependencies
+            .iter()
+            .find(|d| d.reference == reference)
+            .map(|d| d.depends_on.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn deduplicates_by_purl_combining_licenses_and_properties() {
+        let merged = merge_aiboms("product", "2.0", &inputs());
+        validate_aibom(&merged).unwrap();
+
+        // Two applications, two models and one shared base
+        assert_eq!(merged.components.len(), 5);
+        let base = merged
+            .components
+            .iter()
+            .find(|c| c.bom_ref == BASE)
+            .unwrap();
+        let licenses: Vec<_> = base
+            .licenses
+            .iter()
+            .flatten()
+            .map(|l| l.license.id.as_deref().unwrap())
+            .collect();
+        assert_eq!(licenses, ["MIT", "Apache-2.0"]);
+        let properties = properties(base);
+        for expected in [("source", "urn:uuid:a"), ("source", "urn:uuid:b")] {
+            assert!(properties.contains(&expected), "missing {:?}", expected);
+        }
+        // Other fields come from the first input
+        assert_eq!(
+            base.description.as_deref(),
+            Some("base as seen by urn:uuid:a")
+        );
+    }
+
+    #[test]
+    fn connects_each_input_under_the_product() {
+        let merged = merge_aiboms("product", "2.0", &inputs());
+        assert!(lint(&merged).max_severity() < Some(Severity::Error));
+
+        assert_eq!(
+            depends_on(&merged, "pkg:generic/product@2.0"),
+            [
+                "pkg:generic/org%2Fembedder@1.0",
+                "pkg:generic/org%2Freranker@1.0"
+            ]
+        );
+        assert_eq!(
+            depends_on(&merged, "pkg:generic/org%2Fembedder@1.0"),
+            ["pkg:huggingface/org/embedder@1.0"]
+        );
+        assert_eq!(
+            depends_on(&merged, "pkg:huggingface/org/reranker@1.0"),
+            [BASE]
+        );
+    }
+
+    #[test]
+    fn records_which_inputs_contributed_each_component() {
+        let merged = merge_aiboms("product", "2.0", &inputs());
+
+        let base = merged
+            .components
+            .iter()
+            .find(|c| c.bom_ref == BASE)
+            .unwrap();
+        let sources: Vec<&str> = properties(base)
+            .into_iter()
+            .filter(|(name, _)| *name == MERGED_FROM_PROPERTY)
+            .map(|(_, value)| value)
+            .collect();
+        assert_eq!(sources, ["urn:uuid:a", "urn:uuid:b"]);
+        let listed: Vec<&str> = merged
+            .metadata
+            .properties
+            .iter()
+            .flatten()
+            .filter(|p| p.name == MERGED_INPUT_PROPERTY)
+            .map(|p| p.value.as_str())
+            .collect();
+        assert_eq!(listed, ["urn:uuid:a", "urn:uuid:b"]);
+
+        let intermediate = merge(application_component("product", "2.0"), &inputs(), false);
+        assert!(intermediate.metadata.properties.is_none());
+        assert!(
+            intermediate
+                .components
+                .iter()
+                .flat_map(|c| c.properties.iter().flatten())
+                .all(|p| p.name != MERGED_FROM_PROPERTY)
+        );
+    }
+
+    #[test]
+    fn keeps_one_copy_of_each_external_reference() {
+        let reference = json!([{ "type": "website", "url": "https://huggingface.co/org" }]);
+        let input = |serial: &str| {
+            BomFixture::new()
+                .serial_number(serial)
+                .set("externalReferences", reference.clone())
+                .build()
+        };
+
+        let merged = merge_aiboms(
+            "product",
+            "2.0",
+            &[input("urn:uuid:a"), input("urn:uuid:b")],
+        );
+        assert_eq!(merged.external_references.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn merges_no_inputs_into_a_lone_product() {
+        let merged = merge_aiboms("product", "2.0", &[]);
+        validate_aibom(&merged).unwrap();
+
+        assert_eq!(merged.metadata.component.bom_ref, "pkg:generic/product@2.0");
+        assert!(merged.components.is_empty());
+        assert_eq!(merged.dependencies.len(), 1);
+        assert!(merged.dependencies[0].depends_on.is_empty());
+        assert!(merged.external_references.is_none());
+    }
+
+    #[test]
+    fn keeps_purl_less_components_of_different_inputs_apart() {
+        let input = |serial: &str, name: &str| {
+            BomFixture::new()
+                .serial_number(serial)
+                .component(
+                    json!({ "type": "machine-learning-model", "bom-ref": "model", "name": name }),
+                )
+                .component(json!({
+                    "type": "machine-learning-model",
+                    "bom-ref": "model",
+                    "name": name,
+                    "properties": [{ "name": "listed", "value": "twice" }]
+                }))
+                .dependency("app", &["model"])
+                .build()
+        };
+        let merged = merge_aiboms(
+            "product",
+            "2.0",
+            &[
+                input("urn:uuid:a", "org/embedder"),
+                input("urn:uuid:b", "org/reranker"),
+            ],
+        );
+        validate_aibom(&merged).unwrap();
+
+        // Duplicates within an input are folded, equal bom-refs across inputs are not
+        let components: Vec<(&str, &str)> = merged
+            .components
+            .iter()
+            .map(|c| (c.bom_ref.as_str(), c.name.as_str()))
+            .collect();
+        assert_eq!(
+            components,
+            [
+                ("app", "app"),
+                ("model", "org/embedder"),
+                ("input2:app", "app"),
+                ("input2:model", "org/reranker"),
+            ]
+        );
+        assert!(properties(&merged.components[3]).contains(&("listed", "twice")));
+
+        assert_eq!(
+            depends_on(&merged, "pkg:generic/product@2.0"),
+            ["app", "input2:app"]
+        );
+        assert_eq!(depends_on(&merged, "app"), ["model"]);
+        assert_eq!(depends_on(&merged, "input2:app"), ["input2:model"]);
+    }
+}
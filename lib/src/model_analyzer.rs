@@ -1,40 +1,1014 @@
 use crate::*;
+use tracing::{debug, warn};
 
-pub struct ModelAnalyzer;
+pub struct ModelAnalyzer {
+    hub: HubClient,
+}
+
+impl Default for ModelAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl ModelAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self::with_hub(HubClient::default())
+    }
+
+    /// Create an analyzer that fetches through a shared Hub client
+    pub fn with_hub(hub: HubClient) -> Self {
+        Self { hub }
     }
 
-    pub fn fetch_model_info_from_hf_api(
+    pub fn fetch_model_info_from_hf_api(&self, model_id: &str) -> Result<ModelInfo, GenerateError> {
+        self.fetch_model_info_at_revision(model_id, None)
+    }
+
+    /// Fetch model info as of a branch, tag or commit; `None` means `main`
+    pub fn fetch_model_info_at_revision(
         &self,
         model_id: &str,
-    ) -> Result<ModelInfo, Box<dyn std::error::Error>> {
-        use reqwest::blocking::Client;
-        use std::time::Duration;
+        revision: Option<&str>,
+    ) -> Result<ModelInfo, GenerateError> {
+        let path = match revision {
+            Some(revision) => format!(
+                "api/models/{}/revision/{}",
+                model_id,
+                encode_revision(revision)
+            ),
+            None => format!("api/models/{}", model_id),
+        };
+        debug!("Fetching model info from: {}", self.hub.url(&path));
+
+        let body = self.hub.get_text(&path, &format!("model {}", model_id))?;
+
+        let mut model_info = serde_json::from_str::<ModelInfo>(&body)?;
+        model_info.revision = revision.map(str::to_string);
+        debug!("Successfully fetched model info for: {}", model_id);
+        Ok(model_info)
+    }
+
+    /// List models from the Hub's paginated listing API, following `Link: rel="next"`
+    /// headers until exhausted or `max_results` entries have been collected
+    pub fn fetch_model_list(
+        &self,
+        query: &[(&str, &str)],
+        max_results: usize,
+    ) -> Result<Vec<ModelSummary>, GenerateError> {
+        self.fetch_repo_list(RepoKind::Model, query, max_results)
+    }
+
+    /// Like [`fetch_model_list`](Self::fetch_model_list) for any kind of repository
+    pub fn fetch_repo_list(
+        &self,
+        kind: RepoKind,
+        query: &[(&str, &str)],
+        max_results: usize,
+    ) -> Result<Vec<ModelSummary>, GenerateError> {
+        let mut repos = Vec::new();
+        let mut next_url = Some(
+            reqwest::Url::parse_with_params(
+                &self.hub.url(&format!("api/{}", kind.api_path())),
+                query.iter().chain(&[("limit", "100")]),
+            )
+            .map_err(|e| GenerateError::Parse(e.to_string()))?,
+        );
 
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .danger_accept_invalid_certs(false)
-            .build()?;
+        while let Some(url) = next_url.take() {
+            debug!("Fetching {} list from: {}", kind.as_str(), url);
 
-        let url = format!("https://huggingface.co/api/models/{}", model_id);
+            let response = self
+                .hub
+                .get(url.as_str(), &format!("{} listing", kind.as_str()))?;
 
-        println!("Fetching model info from: {}", url);
+            next_url = response
+                .headers()
+                .get(reqwest::header::LINK)
+                .and_then(|link| link.to_str().ok())
+                .and_then(|link| self.parse_next_link(link))
+                .and_then(|link| reqwest::Url::parse(&link).ok());
 
-        let response = client
-            .get(&url)
-            .header("User-Agent", "rust-aibom-generator/1.0")
-            .send()?;
+            let page = response.json::<Vec<ModelSummary>>()?;
+            repos.extend(page);
 
-        if !response.status().is_success() {
-            return Err(format!("API request failed with status: {}", response.status()).into());
+            if repos.len() >= max_results {
+                repos.truncate(max_results);
+                break;
+            }
         }
 
-        let model_info = response.json::<ModelInfo>()?;
-        println!("Successfully fetched model info for: {}", model_id);
-        Ok(model_info)
+        Ok(repos)
+    }
+
+    /// Fetch dataset metadata from `api/datasets/{id}`
+    pub fn fetch_dataset_info(&self, dataset_id: &str) -> Result<DatasetInfo, GenerateError> {
+        let body = self.hub.get_text(
+            &format!("api/datasets/{}", dataset_id),
+            &format!("dataset {}", dataset_id),
+        )?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    /// Fetch Space metadata from `api/spaces/{id}`
+    pub fn fetch_space_info(&self, space_id: &str) -> Result<SpaceInfo, GenerateError> {
+        let body = self.hub.get_text(
+            &format!("api/spaces/{}", space_id),
+            &format!("space {}", space_id),
+        )?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    /// Extract the `rel="next"` target from an RFC 8288 Link header
+    fn parse_next_link(&self, link_header: &str) -> Option<String> {
+        link_header.split(',').find_map(|link| {
+            let (target, params) = link.split_once(';')?;
+            params
+                .split(';')
+                .any(|p| p.trim() == "rel=\"next\"")
+                .then(|| {
+                    target
+                        .trim()
+                        .trim_start_matches('<')
+                        .trim_end_matches('>')
+                        .to_string()
+                })
+        })
+    }
+
+    /// Download a raw file from the main branch of a model repository
+    pub fn fetch_repo_file(&self, model_id: &str, filename: &str) -> Result<String, GenerateError> {
+        self.fetch_repo_file_at(model_id, "main", filename)
+    }
+
+    /// Download a raw file from a model repository as of `revision`
+    pub fn fetch_repo_file_at(
+        &self,
+        model_id: &str,
+        revision: &str,
+        filename: &str,
+    ) -> Result<String, GenerateError> {
+        self.hub.get_text(
+            &format!(
+                "{}/resolve/{}/{}",
+                model_id,
+                encode_revision(revision),
+                filename
+            ),
+            &format!("file {} in {}", filename, model_id),
+        )
+    }
+
+    /// Download a file from the revision `model_info` was fetched at
+    fn fetch_model_file(
+        &self,
+        model_info: &ModelInfo,
+        filename: &str,
+    ) -> Result<String, GenerateError> {
+        let revision = model_info.revision.as_deref().unwrap_or("main");
+        self.fetch_repo_file_at(&model_info.model_id, revision, filename)
+    }
+
+    /// Download and parse a JSON file from a model repository, logging failures
+    fn fetch_repo_json<T: serde::de::DeserializeOwned>(
+        &self,
+        model_info: &ModelInfo,
+        filename: &str,
+    ) -> Option<T> {
+        let model_id = &model_info.model_id;
+        match self
+            .fetch_model_file(model_info, filename)
+            .and_then(|content| Ok(serde_json::from_str::<T>(&content)?))
+        {
+            Ok(value) => Some(value),
+            Err(e) => {
+                warn!("Failed to load {} for {}: {}", filename, model_id, e);
+                None
+            }
+        }
+    }
+
+    /// Load configuration files referenced by the repository listing into `model_info`
+    pub fn load_repo_configs(&self, model_info: &mut ModelInfo, enrichers: &Enrichers) {
+        if enrichers.adapter && model_info.has_file("adapter_config.json") {
+            model_info.adapter_config = self.fetch_repo_json(model_info, "adapter_config.json");
+        }
+
+        if model_info.has_file("config.json") {
+            model_info.config = self.fetch_repo_json(model_info, "config.json");
+        }
+
+        if enrichers.quantization {
+            let quantize_config = if model_info.has_file("quantize_config.json") {
+                self.fetch_repo_json(model_info, "quantize_config.json")
+            } else {
+                None
+            };
+            model_info.quantization =
+                self.detect_quantization(model_info, quantize_config.as_ref());
+            if model_info.quantization.is_none() {
+                model_info.conversion = self.detect_format_conversion(model_info);
+            }
+        }
+
+        if enrichers.merge {
+            model_info.merge_config = self.load_merge_config(model_info);
+        }
+
+        if enrichers.pipeline && model_info.has_file("model_index.json") {
+            model_info.model_index = self.fetch_repo_json(model_info, "model_index.json");
+            model_info.pipeline_components = self.load_diffusers_components(model_info);
+        }
+
+        if enrichers.remote_code {
+            model_info.remote_code = self.detect_remote_code(model_info);
+        }
+
+        if enrichers.pipeline && model_info.has_file("modules.json") {
+            let modules: Option<serde_json::Value> =
+                self.fetch_repo_json(model_info, "modules.json");
+            if let Some(modules) = modules {
+                let stages = self.load_sentence_transformer_components(model_info, &modules);
+                model_info.embedding = self.summarize_embedding(&stages);
+                model_info.pipeline_components.extend(stages);
+            }
+        }
+    }
+
+    /// Inventory the Python files a model executes at load time, as referenced by
+    /// `auto_map` (config and tokenizer config), `custom_pipelines` and custom
+    /// diffusers pipeline classes
+    pub fn detect_remote_code(&self, model_info: &ModelInfo) -> Vec<RemoteCodeFile> {
+        let mut references: Vec<(String, String)> = Vec::new();
+        let mut add_auto_map = |prefix: &str, config: &serde_json::Value| {
+            let Some(auto_map) = config.get("auto_map").and_then(|m| m.as_object()) else {
+                return;
+            };
+            for (key, value) in auto_map {
+                // Tokenizer entries are [slow, fast] pairs where either may be null
+                let targets: Vec<&str> = match value {
+                    serde_json::Value::String(s) => vec![s.as_str()],
+                    serde_json::Value::Array(items) => {
+                        items.iter().filter_map(|v| v.as_str()).collect()
+                    }
+                    _ => Vec::new(),
+                };
+                for target in targets {
+                    references.push((format!("{}.{}", prefix, key), target.to_string()));
+                }
+            }
+        };
+
+        if let Some(config) = &model_info.config {
+            add_auto_map("auto_map", config);
+        }
+        if model_info.has_file("tokenizer_config.json") {
+            let tokenizer_config: Option<serde_json::Value> =
+                self.fetch_repo_json(model_info, "tokenizer_config.json");
+            if let Some(tokenizer_config) = tokenizer_config {
+                add_auto_map("tokenizer_config.auto_map", &tokenizer_config);
+            }
+        }
+
+        if let Some(custom_pipelines) = model_info
+            .config
+            .as_ref()
+            .and_then(|c| c.get("custom_pipelines"))
+            .and_then(|p| p.as_object())
+        {
+            for (task, pipeline) in custom_pipelines {
+                if let Some(implementation) = pipeline.get("impl").and_then(|i| i.as_str()) {
+                    references.push((
+                        format!("custom_pipelines.{}", task),
+                        implementation.to_string(),
+                    ));
+                }
+            }
+        }
+
+        // Community diffusers pipelines declare their class as [module, ClassName]
+        if let Some(class_name) = model_info
+            .model_index
+            .as_ref()
+            .and_then(|index| index.get("_class_name"))
+            .and_then(|c| c.as_array())
+            && let [Some(module), Some(class)] = [
+                class_name.first().and_then(|m| m.as_str()),
+                class_name.get(1).and_then(|c| c.as_str()),
+            ]
+        {
+            references.push((
+                "model_index._class_name".to_string(),
+                format!("{}.{}", module, class),
+            ));
+        }
+
+        let mut files: Vec<RemoteCodeFile> = Vec::new();
+        for (reference, target) in references {
+            let Some((repo, path)) = self.resolve_code_reference(&model_info.model_id, &target)
+            else {
+                continue;
+            };
+            match files.iter_mut().find(|f| f.repo == repo && f.path == path) {
+                Some(file) => file.references.push(reference),
+                None => files.push(RemoteCodeFile {
+                    // Code in other repositories is loaded from their main branch
+                    revision: model_info
+                        .revision
+                        .clone()
+                        .filter(|_| repo == model_info.model_id),
+                    repo,
+                    path,
+                    sha256: None,
+                    references: vec![reference],
+                }),
+            }
+        }
+
+        for file in &mut files {
+            let revision = file.revision.as_deref().unwrap_or("main");
+            match self.fetch_repo_file_at(&file.repo, revision, &file.path) {
+                Ok(content) => {
+                    use sha2::{Digest, Sha256};
+                    file.sha256 = Some(format!("{:x}", Sha256::digest(content.as_bytes())));
+                }
+                Err(e) => warn!(
+                    "Failed to download remote code {}/{}: {}",
+                    file.repo, file.path, e
+                ),
+            }
+            debug!(
+                "Found remote code file: {}/{} (referenced by {})",
+                file.repo,
+                file.path,
+                file.references.join(", ")
+            );
+        }
+
+        files
+    }
+
+    /// Resolve a `module.Class` or `org/repo--module.Class` reference to the
+    /// repository and path of the Python file defining it
+    fn resolve_code_reference(&self, model_id: &str, target: &str) -> Option<(String, String)> {
+        let (repo, class_path) = match target.split_once("--") {
+            Some((repo, class_path)) => (repo.to_string(), class_path),
+            None => (model_id.to_string(), target),
+        };
+        let (module, _class) = class_path.rsplit_once('.')?;
+        if module.is_empty() {
+            return None;
+        }
+        Some((repo, format!("{}.py", module.replace('.', "/"))))
+    }
+
+    /// Decompose a sentence-transformers model into its `modules.json` stages
+    /// (Transformer, Pooling, Dense, Normalize)
+    fn load_sentence_transformer_components(
+        &self,
+        model_info: &ModelInfo,
+        modules: &serde_json::Value,
+    ) -> Vec<PipelineComponent> {
+        let Some(modules) = modules.as_array() else {
+            return Vec::new();
+        };
+
+        let mut components = Vec::new();
+        for (position, module) in modules.iter().enumerate() {
+            let Some(module_type) = module.get("type").and_then(|t| t.as_str()) else {
+                continue;
+            };
+            let class_name = module_type.rsplit('.').next().unwrap_or(module_type);
+            let path = module
+                .get("path")
+                .and_then(|p| p.as_str())
+                .unwrap_or_default()
+                .trim_matches('/');
+            let index = module
+                .get("idx")
+                .and_then(|i| i.as_u64())
+                .unwrap_or(position as u64);
+
+            // A Transformer stage stored at the repository root shares the root config.json
+            let (config, has_weights) = if path.is_empty() {
+                let has_weights = model_info.file_names().any(|f| {
+                    !f.contains('/')
+                        && [".safetensors", ".bin", ".onnx"]
+                            .iter()
+                            .any(|ext| f.ends_with(ext))
+                });
+                (model_info.config.clone(), has_weights)
+            } else {
+                let config_file = format!("{}/config.json", path);
+                let config: Option<serde_json::Value> = if model_info.has_file(&config_file) {
+                    self.fetch_repo_json(model_info, &config_file)
+                } else {
+                    None
+                };
+                let prefix = format!("{}/", path);
+                let has_weights = model_info.file_names().any(|f| {
+                    f.starts_with(&prefix)
+                        && [".safetensors", ".bin", ".onnx"]
+                            .iter()
+                            .any(|ext| f.ends_with(ext))
+                });
+                (config, has_weights)
+            };
+
+            // The wrapped transformer names the base model the embedder was trained from
+            let source_repo = if class_name == "Transformer" {
+                config
+                    .as_ref()
+                    .and_then(|c| c.get("_name_or_path"))
+                    .and_then(|p| p.as_str())
+                    .map(|p| p.trim_end_matches('/').to_string())
+                    .filter(|p| self.is_hub_repo_id(p) && p != &model_info.model_id)
+            } else {
+                None
+            };
+
+            components.push(PipelineComponent {
+                name: if path.is_empty() {
+                    format!("{}_{}", index, class_name)
+                } else {
+                    path.to_string()
+                },
+                library: Some(
+                    module_type
+                        .split('.')
+                        .next()
+                        .unwrap_or("sentence_transformers")
+                        .to_string(),
+                ),
+                class_name: Some(class_name.to_string()),
+                config,
+                has_weights,
+                // Nothing in the module says how the embedder was derived
+                relation: None,
+                source_repo,
+            });
+        }
+
+        components
+    }
+
+    /// Derive pooling mode and output dimension from sentence-transformers stages
+    fn summarize_embedding(&self, stages: &[PipelineComponent]) -> Option<EmbeddingInfo> {
+        let stage_config = |class_name: &'static str| {
+            stages
+                .iter()
+                .filter(move |s| s.class_name.as_deref() == Some(class_name))
+                .filter_map(|s| s.config.as_ref())
+        };
+
+        if stages.is_empty() {
+            return None;
+        }
+        let pooling = stage_config("Pooling").next();
+
+        let pooling_mode = pooling.and_then(|config| {
+            // Newer releases store the mode directly; older ones use one flag per mode
+            if let Some(mode) = config.get("pooling_mode").and_then(|m| m.as_str()) {
+                return Some(mode.to_string());
+            }
+            let modes: Vec<&str> = config
+                .as_object()?
+                .iter()
+                .filter(|(_, enabled)| enabled.as_bool() == Some(true))
+                .filter_map(|(key, _)| key.strip_prefix("pooling_mode_"))
+                .map(|mode| mode.trim_end_matches("_tokens").trim_end_matches("_token"))
+                .collect();
+            (!modes.is_empty()).then(|| modes.join("+"))
+        });
+
+        // The last Dense projection determines the output size, otherwise the pooled width
+        let dimension = stage_config("Dense")
+            .filter_map(|config| config.get("out_features").and_then(|d| d.as_u64()))
+            .next_back()
+            .or_else(|| {
+                pooling
+                    .and_then(|config| config.get("word_embedding_dimension"))
+                    .and_then(|d| d.as_u64())
+            });
+
+        Some(EmbeddingInfo {
+            pooling_mode,
+            dimension,
+            normalized: stages
+                .iter()
+                .any(|s| s.class_name.as_deref() == Some("Normalize")),
+        })
+    }
+
+    /// Decompose a diffusers pipeline into its sub-models using `model_index.json`
+    /// and each module's own config file
+    fn load_diffusers_components(&self, model_info: &ModelInfo) -> Vec<PipelineComponent> {
+        let Some(model_index) = model_info.model_index.as_ref().and_then(|m| m.as_object()) else {
+            return Vec::new();
+        };
+
+        let mut components = Vec::new();
+        for (name, entry) in model_index {
+            // Keys starting with an underscore are pipeline metadata, not modules
+            if name.starts_with('_') {
+                continue;
+            }
+            let Some(entry) = entry.as_array() else {
+                continue;
+            };
+            let library = entry.first().and_then(|v| v.as_str()).map(String::from);
+            let class_name = entry.get(1).and_then(|v| v.as_str()).map(String::from);
+            // Optional modules (e.g. a missing safety checker) are declared as [null, null]
+            if library.is_none() && class_name.is_none() {
+                continue;
+            }
+
+            let config_file = [
+                "config.json",
+                "scheduler_config.json",
+                "tokenizer_config.json",
+                "preprocessor_config.json",
+            ]
+            .iter()
+            .map(|f| format!("{}/{}", name, f))
+            .find(|f| model_info.has_file(f));
+            let config: Option<serde_json::Value> = config_file
+                .as_deref()
+                .and_then(|f| self.fetch_repo_json(model_info, f));
+
+            let prefix = format!("{}/", name);
+            let has_weights = model_info.file_names().any(|f| {
+                f.starts_with(&prefix)
+                    && [".safetensors", ".bin", ".ckpt", ".pt", ".onnx"]
+                        .iter()
+                        .any(|ext| f.ends_with(ext))
+            });
+
+            // A module whose config names another Hub repo was taken from that repo
+            let source_repo = config
+                .as_ref()
+                .and_then(|c| c.get("_name_or_path"))
+                .and_then(|p| p.as_str())
+                .map(|p| p.trim_end_matches('/').to_string())
+                .filter(|p| self.is_hub_repo_id(p) && p != &model_info.model_id);
+
+            components.push(PipelineComponent {
+                name: name.clone(),
+                library,
+                class_name,
+                config,
+                has_weights,
+                relation: source_repo.as_ref().map(|_| "component".to_string()),
+                source_repo,
+            });
+        }
+
+        components
+    }
+
+    /// Load a mergekit recipe from `mergekit_config.yml`, or from the YAML block
+    /// many merge cards embed in their README
+    fn load_merge_config(&self, model_info: &ModelInfo) -> Option<MergeConfig> {
+        for filename in ["mergekit_config.yml", "mergekit_config.yaml"] {
+            if model_info.has_file(filename) {
+                match self.fetch_model_file(model_info, filename) {
+                    Ok(content) => return self.parse_merge_config(&content, filename),
+                    Err(e) => warn!(
+                        "Failed to load {} for {}: {}",
+                        filename, model_info.model_id, e
+                    ),
+                }
+            }
+        }
+
+        // Quantized repos often copy the original merge card verbatim, so only
+        // trust an embedded recipe when the model looks like the merge itself
+        let looks_merged = model_info.tags.iter().any(|tag| {
+            let tag = tag.to_lowercase();
+            tag == "mergekit" || tag == "merge"
+        });
+        if !looks_merged
+            || model_info.quantization.is_some()
+            || model_info.conversion.is_some()
+            || !model_info.has_file("README.md")
+        {
+            return None;
+        }
+
+        match self.fetch_model_file(model_info, "README.md") {
+            Ok(readme) => self
+                .extract_yaml_blocks(&readme)
+                .into_iter()
+                .filter(|block| block.contains("merge_method"))
+                .find_map(|block| self.parse_merge_config(&block, "README.md")),
+            Err(e) => {
+                warn!(
+                    "Failed to load README.md for {}: {}",
+                    model_info.model_id, e
+                );
+                None
+            }
+        }
+    }
+
+    /// Collect the contents of fenced ```yaml code blocks in a markdown document
+    fn extract_yaml_blocks(&self, markdown: &str) -> Vec<String> {
+        let mut blocks = Vec::new();
+        let mut current: Option<Vec<&str>> = None;
+
+        for line in markdown.lines() {
+            let trimmed = line.trim();
+            match current.as_mut() {
+                Some(lines) if trimmed.starts_with("```") => {
+                    blocks.push(lines.join("\n"));
+                    current = None;
+                }
+                Some(lines) => lines.push(line),
+                None => {
+                    if let Some(lang) = trimmed.strip_prefix("```")
+                        && matches!(lang.trim(), "yaml" | "yml")
+                    {
+                        current = Some(Vec::new());
+                    }
+                }
+            }
+        }
+
+        blocks
+    }
+
+    /// Parse a mergekit configuration, collecting sources from both `models`
+    /// and `slices` layouts
+    pub fn parse_merge_config(&self, yaml: &str, source_file: &str) -> Option<MergeConfig> {
+        use serde_yaml::Value as Yaml;
+
+        let config: Yaml = match serde_yaml::from_str(yaml) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Failed to parse merge config from {}: {}", source_file, e);
+                return None;
+            }
+        };
+        let get_str = |value: &Yaml, key: &str| {
+            value
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| self.strip_adapter_suffix(s))
+        };
+
+        let mut sources: Vec<MergeSource> = Vec::new();
+        let mut add_source = |entry: &Yaml| {
+            let model = match entry.as_str() {
+                Some(model) => self.strip_adapter_suffix(model),
+                None => match get_str(entry, "model") {
+                    Some(model) => model,
+                    None => return,
+                },
+            };
+            let parameters = entry.get("parameters");
+            let param = |key: &str| {
+                parameters
+                    .and_then(|p| p.get(key))
+                    .and_then(|v| self.render_yaml_scalar(v))
+            };
+            let layer_range = entry
+                .get("layer_range")
+                .and_then(|v| self.render_yaml_scalar(v));
+
+            let index = match sources.iter().position(|s| s.model == model) {
+                Some(index) => index,
+                None => {
+                    sources.push(MergeSource {
+                        model,
+                        weight: None,
+                        density: None,
+                        layer_ranges: Vec::new(),
+                    });
+                    sources.len() - 1
+                }
+            };
+            let source = &mut sources[index];
+            source.weight = source.weight.take().or_else(|| param("weight"));
+            source.density = source.density.take().or_else(|| param("density"));
+            if let Some(layer_range) = layer_range
+                && !source.layer_ranges.contains(&layer_range)
+            {
+                source.layer_ranges.push(layer_range);
+            }
+        };
+
+        if let Some(models) = config.get("models").and_then(|m| m.as_sequence()) {
+            models.iter().for_each(&mut add_source);
+        }
+        if let Some(slices) = config.get("slices").and_then(|s| s.as_sequence()) {
+            slices
+                .iter()
+                .filter_map(|slice| slice.get("sources").and_then(|s| s.as_sequence()))
+                .flatten()
+                .for_each(&mut add_source);
+        }
+
+        let merge_method = config
+            .get("merge_method")
+            .and_then(|m| m.as_str())
+            .map(String::from);
+        if merge_method.is_none() && sources.is_empty() {
+            return None;
+        }
+
+        Some(MergeConfig {
+            merge_method,
+            base_model: get_str(&config, "base_model"),
+            dtype: config
+                .get("dtype")
+                .and_then(|d| d.as_str())
+                .map(String::from),
+            sources,
+            source_file: source_file.to_string(),
+        })
+    }
+
+    /// mergekit allows `model+lora` to apply a LoRA before merging; keep the model
+    fn strip_adapter_suffix(&self, model: &str) -> String {
+        model.split('+').next().unwrap_or(model).trim().to_string()
+    }
+
+    fn render_yaml_scalar(&self, value: &serde_yaml::Value) -> Option<String> {
+        match value {
+            serde_yaml::Value::Number(n) => Some(n.to_string()),
+            serde_yaml::Value::String(s) => Some(s.clone()),
+            serde_yaml::Value::Bool(b) => Some(b.to_string()),
+            serde_yaml::Value::Sequence(items) => Some(format!(
+                "[{}]",
+                items
+                    .iter()
+                    .filter_map(|v| self.render_yaml_scalar(v))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            _ => None,
+        }
+    }
+
+    /// Whether a model reference names a Hub repository (`owner/name`) rather
+    /// than a local path such as `./model`, `../base` or `/data/models/base`
+    pub fn is_hub_repo_id(&self, model_id: &str) -> bool {
+        is_hub_repo_id(model_id)
+    }
+
+    /// Detect quantization from `quantization_config` in config.json, AutoGPTQ's
+    /// `quantize_config.json`, quantized GGUF weight files or EXL2's `measurement.json`
+    pub fn detect_quantization(
+        &self,
+        model_info: &ModelInfo,
+        quantize_config: Option<&serde_json::Value>,
+    ) -> Option<QuantizationInfo> {
+        if let Some(quantization_config) = model_info
+            .config
+            .as_ref()
+            .and_then(|config| config.get("quantization_config"))
+            .filter(|q| q.is_object())
+        {
+            return Some(self.parse_quantization_config(quantization_config, "config.json"));
+        }
+
+        if let Some(quantize_config) = quantize_config.filter(|q| q.is_object()) {
+            return Some(self.parse_quantization_config(quantize_config, "quantize_config.json"));
+        }
+
+        // GGUF repositories ship one file per quantization type, e.g. model.Q4_K_M.gguf;
+        // only the Q*/IQ* types below 16 bits are quantizations
+        let gguf_precisions: Vec<String> = self
+            .gguf_precisions(model_info)
+            .into_iter()
+            .filter(|p| self.is_quantized_gguf_type(p))
+            .collect();
+        if !gguf_precisions.is_empty() {
+            let bits = match gguf_precisions.as_slice() {
+                [single] => self.bits_from_precision(single),
+                _ => None,
+            };
+            return Some(QuantizationInfo {
+                method: "gguf".to_string(),
+                bits,
+                precision: Some(gguf_precisions.join(",")),
+                group_size: None,
+                desc_act: None,
+                source: "gguf".to_string(),
+                settings: Vec::new(),
+            });
+        }
+
+        // EXL2 repositories carry measurement.json and name branches/repos by bits
+        // per weight; a name alone is not evidence
+        if model_info.has_file("measurement.json") {
+            let bpw = self.bpw_from_name(&model_info.model_id.to_lowercase());
+            return Some(QuantizationInfo {
+                method: "exl2".to_string(),
+                bits: bpw,
+                precision: bpw.map(|b| format!("{}bpw", b)),
+                group_size: None,
+                desc_act: None,
+                source: "exl2".to_string(),
+                settings: Vec::new(),
+            });
+        }
+
+        None
+    }
+
+    fn parse_quantization_config(
+        &self,
+        config: &serde_json::Value,
+        source: &str,
+    ) -> QuantizationInfo {
+        let get_bool = |key: &str| config.get(key).and_then(|v| v.as_bool());
+        let get_str = |key: &str| config.get(key).and_then(|v| v.as_str()).map(String::from);
+
+        let load_in_4bit = get_bool("load_in_4bit").unwrap_or(false);
+        let load_in_8bit = get_bool("load_in_8bit").unwrap_or(false);
+
+        // bitsandbytes only sets load_in_*bit; other configs without quant_method
+        // (such as old AutoGPTQ ones) do not say which method produced them
+        let method = get_str("quant_method")
+            .map(|m| m.to_lowercase())
+            .unwrap_or_else(|| {
+                if load_in_4bit || load_in_8bit {
+                    "bitsandbytes".to_string()
+                } else {
+                    "unknown".to_string()
+                }
+            });
+
+        let mut bits = config.get("bits").and_then(|b| b.as_f64());
+        if bits.is_none() {
+            if load_in_4bit {
+                bits = Some(4.0);
+            } else if load_in_8bit {
+                bits = Some(8.0);
+            }
+        }
+
+        let precision = if method == "bitsandbytes" && load_in_4bit {
+            get_str("bnb_4bit_quant_type").or(Some("int4".to_string()))
+        } else if method == "bitsandbytes" && load_in_8bit {
+            Some("int8".to_string())
+        } else if method.starts_with("fp8") {
+            Some("fp8".to_string())
+        } else {
+            bits.map(|b| format!("int{}", b))
+        };
+
+        // Keep scalar settings that matter for reproducing the quantization
+        let settings = [
+            "bnb_4bit_compute_dtype",
+            "bnb_4bit_use_double_quant",
+            "llm_int8_threshold",
+            "sym",
+            "zero_point",
+            "version",
+            "checkpoint_format",
+            "damp_percent",
+            "true_sequential",
+        ]
+        .iter()
+        .filter_map(|key| {
+            config.get(*key).and_then(|v| match v {
+                serde_json::Value::String(s) => Some((key.to_string(), s.clone())),
+                serde_json::Value::Bool(_) | serde_json::Value::Number(_) => {
+                    Some((key.to_string(), v.to_string()))
+                }
+                _ => None,
+            })
+        })
+        .collect();
+
+        QuantizationInfo {
+            method,
+            bits,
+            precision,
+            group_size: config.get("group_size").and_then(|g| g.as_i64()),
+            desc_act: get_bool("desc_act"),
+            source: source.to_string(),
+            settings,
+        }
+    }
+
+    /// Detect GGUF files holding only full-precision (F16, BF16, F32) or
+    /// unrecognized types: a conversion of the source model, not a quantization
+    pub fn detect_format_conversion(&self, model_info: &ModelInfo) -> Option<FormatConversion> {
+        if !model_info
+            .file_names()
+            .any(|f| f.to_lowercase().ends_with(".gguf"))
+        {
+            return None;
+        }
+        let precisions = self.gguf_precisions(model_info);
+        Some(FormatConversion {
+            format: "gguf".to_string(),
+            precision: (!precisions.is_empty()).then(|| precisions.join(",")),
+        })
+    }
+
+    /// Distinct GGUF types named by the repository's `.gguf` files
+    fn gguf_precisions(&self, model_info: &ModelInfo) -> Vec<String> {
+        model_info
+            .file_names()
+            .filter(|f| f.to_lowercase().ends_with(".gguf"))
+            .filter_map(|f| self.gguf_precision_from_filename(f))
+            .fold(Vec::new(), |mut acc, p| {
+                if !acc.contains(&p) {
+                    acc.push(p);
+                }
+                acc
+            })
+    }
+
+    /// Whether a GGUF type stores weights below 16 bits (Q*, IQ*)
+    fn is_quantized_gguf_type(&self, precision: &str) -> bool {
+        precision.starts_with('Q') || precision.starts_with("IQ")
+    }
+
+    fn gguf_precision_from_filename(&self, filename: &str) -> Option<String> {
+        let name = filename.rsplit('/').next()?;
+        let stem = name.get(..name.len().checked_sub(".gguf".len())?)?;
+        stem.split(['.', '-'])
+            .rev()
+            .find(|part| self.is_gguf_type(part))
+            .map(|part| part.to_uppercase())
+    }
+
+    fn is_gguf_type(&self, part: &str) -> bool {
+        let upper = part.to_uppercase();
+        let body = upper
+            .strip_prefix("IQ")
+            .or_else(|| upper.strip_prefix('Q'))
+            .or_else(|| upper.strip_prefix("BF"))
+            .or_else(|| upper.strip_prefix('F'));
+        match body {
+            Some(body) => {
+                let digits = body.chars().take_while(|c| c.is_ascii_digit()).count();
+                digits > 0
+                    && body[digits..].split('_').all(|s| {
+                        s.is_empty()
+                            || ["K", "S", "M", "L", "XS", "XXS", "NL", "0", "1"].contains(&s)
+                    })
+            }
+            None => false,
+        }
+    }
+
+    fn bits_from_precision(&self, precision: &str) -> Option<f64> {
+        let digits: String = precision
+            .trim_start_matches(|c: char| c.is_ascii_alphabetic())
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        digits.parse().ok()
+    }
+
+    fn bpw_from_name(&self, model_name: &str) -> Option<f64> {
+        let index = model_name.find("bpw")?;
+        let number: String = model_name[..index]
+            .chars()
+            .rev()
+            .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == '_')
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .map(|c| if c == '_' { '.' } else { c })
+            .collect();
+        number.trim_matches('.').parse().ok()
+    }
+
+    /// Dataset ids from the `model-index` evaluation results of a model card
+    pub fn extract_evaluation_datasets(&self, card_data: &serde_json::Value) -> Vec<String> {
+        let mut datasets: Vec<String> = Vec::new();
+        let entries = card_data
+            .get("model-index")
+            .and_then(|index| index.as_array())
+            .into_iter()
+            .flatten();
+        for entry in entries {
+            let results = entry
+                .get("results")
+                .and_then(|results| results.as_array())
+                .into_iter()
+                .flatten();
+            for result in results {
+                if let Some(dataset) = result
+                    .get("dataset")
+                    .and_then(|dataset| dataset.get("type"))
+                    .and_then(|dataset| dataset.as_str())
+                    .map(str::trim)
+                    .filter(|dataset| !dataset.is_empty() && !dataset.contains(char::is_whitespace))
+                    && !datasets.iter().any(|d| d == dataset)
+                {
+                    datasets.push(dataset.to_string());
+                }
+            }
+        }
+        datasets
     }
 
     pub fn extract_dependencies(&self, model_info: &ModelInfo) -> Vec<(String, Option<String>)> {
@@ -56,7 +1030,7 @@ impl ModelAnalyzer {
 
                 if let Some(base_model_str) = base_model.as_str() {
                     dependencies.push((base_model_str.to_string(), relation.clone()));
-                    println!(
+                    debug!(
                         "Found base_model dependency: {} (relation: {:?})",
                         base_model_str, relation
                     );
@@ -64,7 +1038,7 @@ impl ModelAnalyzer {
                     for base_model_item in base_model_array {
                         if let Some(base_model_str) = base_model_item.as_str() {
                             dependencies.push((base_model_str.to_string(), relation.clone()));
-                            println!(
+                            debug!(
                                 "Found base_model dependency: {} (relation: {:?})",
                                 base_model_str, relation
                             );
@@ -74,11 +1048,11 @@ impl ModelAnalyzer {
             }
 
             // Check for parent_model field (some models use this)
-            if let Some(parent_model) = card_data.get("parent_model") {
-                if let Some(parent_model_str) = parent_model.as_str() {
-                    dependencies.push((parent_model_str.to_string(), Some("parent".to_string())));
-                    println!("Found parent_model dependency: {}", parent_model_str);
-                }
+            if let Some(parent_model) = card_data.get("parent_model")
+                && let Some(parent_model_str) = parent_model.as_str()
+            {
+                dependencies.push((parent_model_str.to_string(), Some("parent".to_string())));
+                debug!("Found parent_model dependency: {}", parent_model_str);
             }
 
             // Check for datasets used to train the model
@@ -87,24 +1061,74 @@ impl ModelAnalyzer {
                     for dataset in datasets_array {
                         if let Some(dataset_str) = dataset.as_str() {
                             dependencies.push((dataset_str.to_string(), Some("train".to_string())));
-                            println!("Found training dataset dependency: {}", dataset_str);
+                            debug!("Found training dataset dependency: {}", dataset_str);
                         }
                     }
                 } else if let Some(dataset_str) = datasets.as_str() {
                     dependencies.push((dataset_str.to_string(), Some("train".to_string())));
-                    println!("Found training dataset dependency: {}", dataset_str);
+                    debug!("Found training dataset dependency: {}", dataset_str);
                 }
             }
 
             // Also check for train_dataset field (alternative naming)
-            if let Some(train_dataset) = card_data.get("train_dataset") {
-                if let Some(dataset_str) = train_dataset.as_str() {
-                    dependencies.push((dataset_str.to_string(), Some("train".to_string())));
-                    println!("Found training dataset dependency: {}", dataset_str);
+            if let Some(train_dataset) = card_data.get("train_dataset")
+                && let Some(dataset_str) = train_dataset.as_str()
+            {
+                dependencies.push((dataset_str.to_string(), Some("train".to_string())));
+                debug!("Found training dataset dependency: {}", dataset_str);
+            }
+
+            // Datasets the reported evaluation results were measured on
+            for dataset_str in self.extract_evaluation_datasets(card_data) {
+                if !dependencies.iter().any(|(dep, _)| *dep == dataset_str) {
+                    debug!("Found evaluation dataset dependency: {}", dataset_str);
+                    dependencies.push((dataset_str, Some("test".to_string())));
                 }
             }
         }
 
+        // The base model declared by a PEFT adapter is authoritative, even when
+        // the card omits base_model or lists it with a different relation
+        if let Some(adapter_config) = &model_info.adapter_config
+            && let Some(base_model) = adapter_config.base_model_id()
+        {
+            let relation = adapter_config.relation();
+            dependencies.retain(|(dep, _)| dep != base_model);
+            dependencies.push((base_model.to_string(), Some(relation.clone())));
+            debug!(
+                "Found adapter base model dependency: {} (relation: {})",
+                base_model, relation
+            );
+        }
+
+        // Every model listed in a merge recipe is a merge input, including the base
+        if let Some(merge_config) = &model_info.merge_config {
+            let merge_inputs = merge_config
+                .sources
+                .iter()
+                .map(|source| source.model.as_str())
+                .chain(merge_config.base_model.as_deref())
+                .filter(|model| self.is_hub_repo_id(model));
+            for model in merge_inputs {
+                dependencies.retain(|(dep, _)| dep != model);
+                dependencies.push((model.to_string(), Some("merged".to_string())));
+                debug!("Found merge source dependency: {}", model);
+            }
+        }
+
+        // Pipeline modules taken from other repositories are separate lineage dependencies
+        for pipeline_component in &model_info.pipeline_components {
+            if let Some(source_repo) = &pipeline_component.source_repo
+                && !dependencies.iter().any(|(dep, _)| dep == source_repo)
+            {
+                dependencies.push((source_repo.clone(), pipeline_component.relation.clone()));
+                debug!(
+                    "Found pipeline component dependency: {} ({})",
+                    source_repo, pipeline_component.name
+                );
+            }
+        }
+
         // Remove duplicates and self-references
         dependencies.sort_by(|a, b| a.0.cmp(&b.0));
         dependencies.dedup();
@@ -112,8 +1136,8 @@ impl ModelAnalyzer {
 
         // Log warning if no dependencies found
         if dependencies.is_empty() {
-            println!(
-                "Warning: No explicit dependencies found for model: {}. Consider adding base_model, parent_model, or dependencies fields to the model card.",
+            warn!(
+                "No explicit dependencies found for model: {}. Consider adding base_model, parent_model, or dependencies fields to the model card.",
                 model_info.model_id
             );
         }
@@ -126,26 +1150,46 @@ impl ModelAnalyzer {
         model_info: &ModelInfo,
         card_data: &serde_json::Value,
     ) -> Option<String> {
+        // An adapter_config.json is direct evidence of a PEFT adapter
+        if let Some(adapter_config) = &model_info.adapter_config {
+            return Some(adapter_config.relation());
+        }
+
+        // Quantization settings in the repository files are direct evidence
+        if model_info.quantization.is_some() {
+            return Some("quantized".to_string());
+        }
+
+        // So are full-precision weights re-packaged in another format
+        if model_info.conversion.is_some() {
+            return Some("converted".to_string());
+        }
+
+        // A merge recipe is direct evidence of a merged model
+        if model_info.merge_config.is_some() {
+            return Some("merged".to_string());
+        }
+
         // Check library_name first (highest priority for specific model types)
-        if let Some(library_name) = card_data.get("library_name") {
-            if let Some(lib_str) = library_name.as_str() {
-                match lib_str {
-                    "adapter-transformers" | "adapters" => {
-                        return Some("adapter".to_string());
-                    }
-                    "peft" => {
-                        return Some("lora".to_string());
-                    }
-                    _ => {}
+        if let Some(library_name) = card_data.get("library_name")
+            && let Some(lib_str) = library_name.as_str()
+        {
+            match lib_str {
+                "adapter-transformers" | "adapters" => {
+                    return Some("adapter".to_string());
                 }
+                "peft" => {
+                    return Some("lora".to_string());
+                }
+                _ => {}
             }
         }
 
         // Check for quantized_by field
-        if let Some(quantized_by) = card_data.get("quantized_by") {
-            if quantized_by.is_string() {
-                return Some("quantized".to_string());
-            }
+        if let Some(quantized_by) = card_data.get("quantized_by")
+            && quantized_by.is_string()
+        {
+            return Some("quantized".to_string());
         }
 
         // Check tags for relation indicators
@@ -155,16 +1199,11 @@ impl ModelAnalyzer {
             // Check for base_model:relation:model format
             if tag_lower.starts_with("base_model:") {
                 let parts: Vec<&str> = tag_lower.split(':').collect();
-                if parts.len() >= 3 && parts[0] == "base_model" {
-                    match parts[1] {
-                        "finetune" | "finetuned" => return Some("finetuned".to_string()),
-                        "adapter" => return Some("adapter".to_string()),
-                        "lora" | "qlora" => return Some("lora".to_string()),
-                        "quantized" | "quantization" => return Some("quantized".to_string()),
-                        "merged" | "merge" => return Some("merged".to_string()),
-                        "distilled" | "distillation" => return Some("distilled".to_string()),
-                        _ => {}
-                    }
+                if parts.len() >= 3
+                    && parts[0] == "base_model"
+                    && let Some(relation) = self.relation_from_tag_kind(parts[1])
+                {
+                    return Some(relation);
                 }
             } else {
                 // Check for simple tag matches
@@ -198,22 +1237,17 @@ impl ModelAnalyzer {
 
         // Check model name patterns for common relations
         let model_name = model_info.model_id.to_lowercase();
-        if model_name.contains("gguf")
-            || model_name.contains("gptq")
-            || model_name.contains("awq")
-            || model_name.contains("int4")
-            || model_name.contains("int8")
-        {
-            Some("quantized".to_string())
-        } else if model_name.contains("lora") || model_name.contains("qlora") {
+        if model_name.contains("lora") || model_name.contains("qlora") {
             Some("lora".to_string())
         } else if model_name.contains("adapter") {
             Some("adapter".to_string())
         } else if model_name.contains("merge") {
             Some("merged".to_string())
-        } else if model_name.contains("finetune") || model_name.contains("ft") {
-            Some("finetuned".to_string())
-        } else if model_name.contains("instruct") || model_name.contains("chat") {
+        } else if model_name.contains("finetune")
+            || model_name.contains("ft")
+            || model_name.contains("instruct")
+            || model_name.contains("chat")
+        {
             Some("finetuned".to_string())
         } else if model_name.contains("distil") {
             Some("distilled".to_string())
@@ -226,6 +1260,19 @@ impl ModelAnalyzer {
         }
     }
 
+    /// Map the relation part of a `base_model:<relation>:<model>` Hub tag
+    pub fn relation_from_tag_kind(&self, kind: &str) -> Option<String> {
+        match kind.to_lowercase().as_str() {
+            "finetune" | "finetuned" => Some("finetuned".to_string()),
+            "adapter" => Some("adapter".to_string()),
+            "lora" | "qlora" => Some("lora".to_string()),
+            "quantized" | "quantization" => Some("quantized".to_string()),
+            "merged" | "merge" => Some("merged".to_string()),
+            "distilled" | "distillation" => Some("distilled".to_string()),
+            _ => None,
+        }
+    }
+
     pub fn extract_organization_from_model_id(&self, model_id: &str) -> (String, String) {
         let parts: Vec<&str> = model_id.split('/').collect();
         if parts.len() >= 2 {
@@ -251,6 +1298,7 @@ impl ModelAnalyzer {
             "conversational",
             "text-classification",
             "feature-extraction",
+            "sentence-similarity",
             "translation",
             "summarization",
             "question-answering",
@@ -275,6 +1323,7 @@ impl ModelAnalyzer {
                 "conversational" => return "conversational".to_string(),
                 "text-classification" => return "text-classification".to_string(),
                 "feature-extraction" => return "feature-extraction".to_string(),
+                "sentence-similarity" => return "sentence-similarity".to_string(),
                 "translation" => return "translation".to_string(),
                 _ => continue,
             }
@@ -285,27 +1334,90 @@ impl ModelAnalyzer {
     pub fn get_model_architecture(&self, model_info: &ModelInfo) -> String {
         // Try to extract architecture from model card data
         if let Some(card_data) = &model_info.card_data {
-            if let Some(architecture) = card_data.get("architecture") {
-                if let Some(arch_str) = architecture.as_str() {
-                    return arch_str.to_string();
-                }
+            if let Some(architecture) = card_data.get("architecture")
+                && let Some(arch_str) = architecture.as_str()
+            {
+                return arch_str.to_string();
             }
             // Also check for architectures array in config
-            if let Some(architectures) = card_data.get("architectures") {
-                if let Some(arch_array) = architectures.as_array() {
-                    if let Some(first_arch) = arch_array.first() {
-                        if let Some(arch_str) = first_arch.as_str() {
-                            return arch_str.to_string();
-                        }
-                    }
-                }
+            if let Some(architectures) = card_data.get("architectures")
+                && let Some(arch_array) = architectures.as_array()
+                && let Some(first_arch) = arch_array.first()
+                && let Some(arch_str) = first_arch.as_str()
+            {
+                return arch_str.to_string();
             }
         }
 
+        // Fall back to the repository's own config files
+        if let Some(arch_str) = model_info
+            .config
+            .as_ref()
+            .and_then(|config| config.get("architectures"))
+            .and_then(|a| a.as_array())
+            .and_then(|a| a.first())
+            .and_then(|a| a.as_str())
+        {
+            return arch_str.to_string();
+        }
+        if let Some(class_name) = model_info
+            .model_index
+            .as_ref()
+            .and_then(|index| index.get("_class_name"))
+            .and_then(|c| c.as_str())
+        {
+            return class_name.to_string();
+        }
+
         // Default to generic transformer if no specific architecture found
         "TransformerModel".to_string()
     }
 
+    /// Extract access conditions of a gated repository: the click-through prompt
+    /// and the fields requesters must fill in
+    pub fn extract_gating(&self, model_info: &ModelInfo) -> Option<GatingInfo> {
+        let status = gated_status(model_info.gated.as_ref())?;
+
+        let card_str = |key: &str| {
+            model_info
+                .card_data
+                .as_ref()
+                .and_then(|card_data| card_data.get(key))
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+        let terms_parts: Vec<String> = [
+            "extra_gated_heading",
+            "extra_gated_description",
+            "extra_gated_prompt",
+        ]
+        .iter()
+        .filter_map(|key| card_str(key))
+        .collect();
+        let terms = (!terms_parts.is_empty()).then(|| terms_parts.join("\n\n"));
+
+        let fields = model_info
+            .card_data
+            .as_ref()
+            .and_then(|card_data| card_data.get("extra_gated_fields"))
+            .and_then(|f| f.as_object())
+            .map(|f| f.keys().cloned().collect())
+            .unwrap_or_default();
+
+        let terms_sha256 = terms.as_ref().map(|terms| {
+            use sha2::{Digest, Sha256};
+            format!("{:x}", Sha256::digest(terms.as_bytes()))
+        });
+
+        Some(GatingInfo {
+            status,
+            terms,
+            terms_sha256,
+            fields,
+        })
+    }
+
     pub fn extract_license_from_tags(&self, tags: &[String]) -> Option<String> {
         for tag in tags {
             if tag.starts_with("license:") {
@@ -315,3 +1427,568 @@ impl ModelAnalyzer {
         None
     }
 }
+
+/// See [`ModelAnalyzer::is_hub_repo_id`]
+pub(crate) fn is_hub_repo_id(model_id: &str) -> bool {
+    let mut segments = model_id.split('/');
+    let (Some(owner), Some(name), None) = (segments.next(), segments.next(), segments.next())
+    else {
+        return false;
+    };
+    [owner, name].iter().all(|segment| {
+        !segment.is_empty()
+            && !segment.starts_with(['.', '-'])
+            && !segment.ends_with('.')
+            && !segment.contains("..")
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    })
+}
+
+/// Approval mode of a repository's `gated` value, `None` if it is not gated
+pub(crate) fn gated_status(gated: Option<&serde_json::Value>) -> Option<String> {
+    match gated? {
+        serde_json::Value::String(mode) => Some(mode.clone()),
+        serde_json::Value::Bool(true) => Some("true".to_string()),
+        _ => None,
+    }
+}
+
+/// Revisions such as `refs/pr/1` are a single path segment in Hub URLs
+pub(crate) fn encode_revision(revision: &str) -> String {
+    revision.replace('/', "%2F")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_merge_config_from_models_and_slices() {
+        let analyzer = ModelAnalyzer::new();
+        let readme = r#"
+## Configuration
+
+```yaml
+slices:
+  - sources:
+      - model: org/model-a
+        layer_range: [0, 32]
+      - model: org/model-b+org/some-lora
+        layer_range: [0, 32]
+merge_method: slerp
+base_model: org/model-a
+parameters:
+  t: 0.5
+dtype: bfloat16
+```
+"#;
+        let block = analyzer.extract_yaml_blocks(readme).pop().unwrap();
+        let config = analyzer.parse_merge_config(&block, "README.md").unwrap();
+
+        assert_eq!(config.merge_method.as_deref(), Some("slerp"));
+        assert_eq!(config.base_model.as_deref(), Some("org/model-a"));
+        assert_eq!(config.dtype.as_deref(), Some("bfloat16"));
+        assert_eq!(config.sources.len(), 2);
+        assert_eq!(config.sources[1].model, "org/model-b");
+        assert_eq!(config.sources[1].layer_ranges, vec!["[0, 32]".to_string()]);
+
+        let config = analyzer
+            .parse_merge_config(
+                "models:\n  - model: org/a\n    parameters:\n      weight: 0.6\n      density: 0.5\n  - model: org/b\nmerge_method: ties\nbase_model: org/base\n",
+                "mergekit_config.yml",
+            )
+            .unwrap();
+        assert_eq!(config.sources[0].weight.as_deref(), Some("0.6"));
+        assert_eq!(config.sources[0].density.as_deref(), Some("0.5"));
+        assert_eq!(config.sources[1].weight, None);
+    }
+
+    fn model_with_files(model_id: &str, files: &[&str]) -> ModelInfo {
+        let siblings: Vec<_> = files
+            .iter()
+            .map(|f| serde_json::json!({ "rfilename": f }))
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "modelId": model_id,
+            "tags": [],
+            "siblings": siblings,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn reads_gguf_types_from_file_names() {
+        let analyzer = ModelAnalyzer::new();
+        let precision = |f| analyzer.gguf_precision_from_filename(f);
+        assert_eq!(precision("model.Q4_K_M.gguf").as_deref(), Some("Q4_K_M"));
+        assert_eq!(
+            precision("quants/llama-3-8b-iq3_xxs.gguf").as_deref(),
+            Some("IQ3_XXS")
+        );
+        assert_eq!(precision("model-f16.gguf").as_deref(), Some("F16"));
+        assert_eq!(precision("model.BF16.gguf").as_deref(), Some("BF16"));
+        assert_eq!(precision("model.gguf"), None);
+        assert!(analyzer.is_quantized_gguf_type("Q8_0"));
+        assert!(analyzer.is_quantized_gguf_type("IQ2_XS"));
+        assert!(!analyzer.is_quantized_gguf_type("F16"));
+        assert!(!analyzer.is_quantized_gguf_type("BF16"));
+    }
+
+    #[test]
+    fn counts_only_sub_16_bit_gguf_files_as_quantized() {
+        let analyzer = ModelAnalyzer::new();
+        let mixed = model_with_files("org/m-GGUF", &["m.Q4_K_M.gguf", "m.F16.gguf"]);
+        let quantization = analyzer.detect_quantization(&mixed, None).unwrap();
+        assert_eq!(quantization.method, "gguf");
+        assert_eq!(quantization.precision.as_deref(), Some("Q4_K_M"));
+        assert_eq!(quantization.bits, Some(4.0));
+
+        let full = model_with_files("org/m-GGUF", &["m.F16.gguf", "m.BF16.gguf", "README.md"]);
+        assert_eq!(analyzer.detect_quantization(&full, None), None);
+        assert_eq!(
+            analyzer.detect_format_conversion(&full),
+            Some(FormatConversion {
+                format: "gguf".to_string(),
+                precision: Some("F16,BF16".to_string()),
+            })
+        );
+        assert_eq!(
+            analyzer.detect_format_conversion(&model_with_files("org/m", &["model.safetensors"])),
+            None
+        );
+    }
+
+    #[test]
+    fn marks_full_precision_gguf_as_converted() {
+        let analyzer = ModelAnalyzer::new();
+        let mut model = model_with_files("org/m-GGUF", &["m.F32.gguf"]);
+        model.card_data = Some(serde_json::json!({ "base_model": "org/m" }));
+        model.conversion = analyzer.detect_format_conversion(&model);
+        assert_eq!(
+            analyzer.extract_dependencies(&model),
+            vec![("org/m".to_string(), Some("converted".to_string()))]
+        );
+    }
+
+    #[test]
+    fn requires_exl2_evidence_beyond_the_name() {
+        let analyzer = ModelAnalyzer::new();
+        let named = model_with_files("org/m-exl2-4.5bpw", &["output.safetensors"]);
+        assert_eq!(analyzer.detect_quantization(&named, None), None);
+
+        let measured = model_with_files("org/m-exl2-4_5bpw", &["measurement.json"]);
+        let quantization = analyzer.detect_quantization(&measured, None).unwrap();
+        assert_eq!(quantization.method, "exl2");
+        assert_eq!(quantization.bits, Some(4.5));
+        assert_eq!(quantization.precision.as_deref(), Some("4.5bpw"));
+
+        let mut configured = model_with_files("org/m", &["config.json"]);
+        configured.config = Some(serde_json::json!({
+            "quantization_config": { "quant_method": "exl2", "bits": 6.0 }
+        }));
+        let quantization = analyzer.detect_quantization(&configured, None).unwrap();
+        assert_eq!(quantization.method, "exl2");
+        assert_eq!(quantization.source, "config.json");
+    }
+
+    #[test]
+    fn parses_quantization_configs() {
+        let analyzer = ModelAnalyzer::new();
+        let parse = |config| analyzer.parse_quantization_config(&config, "quantize_config.json");
+
+        let unlabeled =
+            parse(serde_json::json!({ "bits": 4, "group_size": 128, "desc_act": true }));
+        assert_eq!(unlabeled.method, "unknown");
+        assert_eq!(unlabeled.precision.as_deref(), Some("int4"));
+        assert_eq!(unlabeled.group_size, Some(128));
+        assert_eq!(unlabeled.desc_act, Some(true));
+
+        let awq =
+            parse(serde_json::json!({ "quant_method": "AWQ", "bits": 4, "zero_point": true }));
+        assert_eq!(awq.method, "awq");
+        assert_eq!(
+            awq.settings,
+            vec![("zero_point".to_string(), "true".to_string())]
+        );
+
+        let bnb = parse(serde_json::json!({
+            "load_in_4bit": true,
+            "bnb_4bit_quant_type": "nf4",
+            "bnb_4bit_compute_dtype": "bfloat16"
+        }));
+        assert_eq!(bnb.method, "bitsandbytes");
+        assert_eq!(bnb.bits, Some(4.0));
+        assert_eq!(bnb.precision.as_deref(), Some("nf4"));
+
+        let fp8 = parse(serde_json::json!({ "quant_method": "fp8" }));
+        assert_eq!(fp8.precision.as_deref(), Some("fp8"));
+        assert_eq!(fp8.bits, None);
+    }
+
+    #[test]
+    fn resolves_local_and_cross_repo_code_references() {
+        let analyzer = ModelAnalyzer::new();
+        let resolve = |target| analyzer.resolve_code_reference("org/model", target);
+        assert_eq!(
+            resolve("modeling_phi.PhiForCausalLM"),
+            Some(("org/model".to_string(), "modeling_phi.py".to_string()))
+        );
+        assert_eq!(
+            resolve("models.vision.encoder.Encoder"),
+            Some((
+                "org/model".to_string(),
+                "models/vision/encoder.py".to_string()
+            ))
+        );
+        assert_eq!(
+            resolve("org/shared--tokenization_x.XTokenizer"),
+            Some(("org/shared".to_string(), "tokenization_x.py".to_string()))
+        );
+        assert_eq!(resolve("BareClass"), None);
+        assert_eq!(resolve(".Class"), None);
+    }
+
+    #[test]
+    fn detects_remote_code_from_auto_maps() {
+        let config = serde_json::json!({
+            "auto_map": {
+                "AutoConfig": "configuration_x.XConfig",
+                "AutoModel": "modeling_x.XModel",
+                "AutoModelForCausalLM": "modeling_x.XForCausalLM"
+            },
+            "custom_pipelines": {
+                "text-classification": { "impl": "pipeline_x.XPipeline" }
+            }
+        });
+        let tokenizer_config = serde_json::json!({
+            "auto_map": {
+                "AutoTokenizer": ["org/shared--tokenization_x.XTokenizer", null]
+            }
+        });
+        let hub = HubClient::stub([
+            (
+                "org/model/resolve/main/tokenizer_config.json",
+                tokenizer_config.to_string(),
+            ),
+            (
+                "org/model/resolve/main/modeling_x.py",
+                "class XModel: ...".to_string(),
+            ),
+            (
+                "org/shared/resolve/main/tokenization_x.py",
+                "class XTokenizer: ...".to_string(),
+            ),
+        ]);
+        let analyzer = ModelAnalyzer::with_hub(hub);
+        let mut model = model_with_files("org/model", &["config.json", "tokenizer_config.json"]);
+        model.config = Some(config);
+
+        let files = analyzer.detect_remote_code(&model);
+        let summary: Vec<_> = files
+            .iter()
+            .map(|f| (f.repo.as_str(), f.path.as_str(), f.sha256.is_some()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("org/model", "configuration_x.py", false),
+                ("org/model", "modeling_x.py", true),
+                ("org/shared", "tokenization_x.py", true),
+                ("org/model", "pipeline_x.py", false),
+            ]
+        );
+        assert_eq!(
+            files[1].references,
+            vec![
+                "auto_map.AutoModel".to_string(),
+                "auto_map.AutoModelForCausalLM".to_string()
+            ]
+        );
+        assert_eq!(
+            files[2].references,
+            vec!["tokenizer_config.auto_map.AutoTokenizer".to_string()]
+        );
+        assert!(files.iter().all(|f| f.revision.is_none()));
+    }
+
+    #[test]
+    fn extracts_gating_terms_and_fields() {
+        let analyzer = ModelAnalyzer::new();
+        let mut model = model_with_files("org/gated", &[]);
+        model.gated = Some(serde_json::json!("manual"));
+        model.card_data = Some(serde_json::json!({
+            "extra_gated_heading": "Access the model",
+            "extra_gated_prompt": "  You agree to the acceptable use policy.  ",
+            "extra_gated_description": "",
+            "extra_gated_fields": { "Name": "text", "Affiliation": "text" }
+        }));
+
+        let gating = analyzer.extract_gating(&model).unwrap();
+        assert_eq!(gating.status, "manual");
+        assert_eq!(
+            gating.terms.as_deref(),
+            Some("Access the model\n\nYou agree to the acceptable use policy.")
+        );
+        assert_eq!(gating.terms_sha256.as_ref().map(String::len), Some(64));
+        assert_eq!(gating.fields, vec!["Affiliation", "Name"]);
+
+        model.gated = Some(serde_json::json!(true));
+        model.card_data = None;
+        let gating = analyzer.extract_gating(&model).unwrap();
+        assert_eq!(gating.status, "true");
+        assert_eq!(gating.terms, None);
+        assert_eq!(gating.terms_sha256, None);
+        assert!(gating.fields.is_empty());
+
+        model.gated = Some(serde_json::json!(false));
+        assert_eq!(analyzer.extract_gating(&model), None);
+        model.gated = None;
+        assert_eq!(analyzer.extract_gating(&model), None);
+    }
+
+    #[test]
+    fn accepts_only_hub_repo_ids() {
+        let analyzer = ModelAnalyzer::new();
+        for id in ["org/model", "BAAI/bge-small-en-v1.5", "user_1/my.model-v2"] {
+            assert!(analyzer.is_hub_repo_id(id), "{}", id);
+        }
+        for id in [
+            "model",
+            "./model",
+            "../base/model",
+            ".cache/model",
+            "/data/model",
+            "models/base/v1",
+            "org/",
+            "/model",
+            "org/../model",
+            "org/..",
+            "~/model",
+            "C:\\models\\base",
+            "c:/model",
+            "org/model name",
+        ] {
+            assert!(!analyzer.is_hub_repo_id(id), "{}", id);
+        }
+    }
+
+    fn stage(class_name: &str, config: serde_json::Value) -> PipelineComponent {
+        PipelineComponent {
+            name: class_name.to_string(),
+            library: Some("sentence_transformers".to_string()),
+            class_name: Some(class_name.to_string()),
+            config: Some(config),
+            has_weights: false,
+            source_repo: None,
+            relation: None,
+        }
+    }
+
+    #[test]
+    fn summarizes_embedding_pipelines() {
+        let analyzer = ModelAnalyzer::new();
+        assert_eq!(analyzer.summarize_embedding(&[]), None);
+
+        // Pooling flags of older releases, a Dense projection and normalization
+        let stages = [
+            stage("Transformer", serde_json::json!({ "hidden_size": 768 })),
+            stage(
+                "Pooling",
+                serde_json::json!({
+                    "word_embedding_dimension": 768,
+                    "pooling_mode_cls_token": false,
+                    "pooling_mode_mean_tokens": true
+                }),
+            ),
+            stage(
+                "Dense",
+                serde_json::json!({ "in_features": 768, "out_features": 512 }),
+            ),
+            stage(
+                "Dense",
+                serde_json::json!({ "in_features": 512, "out_features": 256 }),
+            ),
+            stage("Normalize", serde_json::json!({})),
+        ];
+        assert_eq!(
+            analyzer.summarize_embedding(&stages),
+            Some(EmbeddingInfo {
+                pooling_mode: Some("mean".to_string()),
+                dimension: Some(256),
+                normalized: true,
+            })
+        );
+
+        // Newer releases name the mode; without Dense the pooled width is the output
+        let stages = [stage(
+            "Pooling",
+            serde_json::json!({ "pooling_mode": "lasttoken", "word_embedding_dimension": 1024 }),
+        )];
+        assert_eq!(
+            analyzer.summarize_embedding(&stages),
+            Some(EmbeddingInfo {
+                pooling_mode: Some("lasttoken".to_string()),
+                dimension: Some(1024),
+                normalized: false,
+            })
+        );
+    }
+
+    #[test]
+    fn links_sentence_transformer_to_its_hub_base_only() {
+        let analyzer = ModelAnalyzer::new();
+        let modules = serde_json::json!([
+            { "idx": 0, "name": "0", "path": "", "type": "sentence_transformers.models.Transformer" },
+            { "idx": 1, "name": "1", "path": "1_Pooling", "type": "sentence_transformers.models.Pooling" }
+        ]);
+        let mut model = model_with_files("org/embedder", &["model.safetensors", "modules.json"]);
+
+        model.config = Some(serde_json::json!({ "_name_or_path": "org/base-encoder" }));
+        let stages = analyzer.load_sentence_transformer_components(&model, &modules);
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[0].name, "0_Transformer");
+        assert!(stages[0].has_weights);
+        assert_eq!(stages[0].source_repo.as_deref(), Some("org/base-encoder"));
+        // The base is known, how it was derived is not
+        assert_eq!(stages[0].relation, None);
+        assert_eq!(stages[1].name, "1_Pooling");
+        assert_eq!(stages[1].config, None);
+
+        // A local training path is not a lineage edge
+        model.config = Some(serde_json::json!({ "_name_or_path": "./output/checkpoint-500" }));
+        let stages = analyzer.load_sentence_transformer_components(&model, &modules);
+        assert_eq!(stages[0].source_repo, None);
+        assert_eq!(stages[0].relation, None);
+    }
+
+    #[test]
+    fn uses_adapter_base_model_as_authoritative_dependency() {
+        let analyzer = ModelAnalyzer::new();
+        let adapter: AdapterConfig = serde_json::from_value(serde_json::json!({
+            "base_model_name_or_path": "org/base",
+            "peft_type": "LORA",
+            "r": 16,
+            "lora_alpha": 32,
+            "target_modules": ["q_proj", "v_proj"],
+            "task_type": "CAUSAL_LM"
+        }))
+        .unwrap();
+        assert_eq!(adapter.base_model_id(), Some("org/base"));
+        assert_eq!(adapter.relation(), "lora");
+        assert_eq!(adapter.target_modules_list(), vec!["q_proj", "v_proj"]);
+
+        // The card lists the base as a plain fine-tune and omits the relation
+        let mut model = model_with_files("org/adapter", &["adapter_config.json"]);
+        model.card_data = Some(serde_json::json!({
+            "base_model": ["org/base", "org/other"],
+            "base_model_relation": "finetune"
+        }));
+        model.adapter_config = Some(adapter);
+        assert_eq!(
+            analyzer.extract_dependencies(&model),
+            vec![
+                ("org/base".to_string(), Some("lora".to_string())),
+                ("org/other".to_string(), Some("finetune".to_string())),
+            ]
+        );
+
+        // Without base_model in the card, and without a Hub base at all
+        model.card_data = None;
+        assert_eq!(
+            analyzer.extract_dependencies(&model),
+            vec![("org/base".to_string(), Some("lora".to_string()))]
+        );
+        let local: AdapterConfig = serde_json::from_value(serde_json::json!({
+            "base_model_name_or_path": "../checkpoints/base",
+            "peft_type": "IA3",
+            "target_modules": ".*attn.*"
+        }))
+        .unwrap();
+        assert_eq!(local.base_model_id(), None);
+        assert_eq!(local.relation(), "adapter");
+        assert_eq!(local.target_modules_list(), vec![".*attn.*"]);
+        model.adapter_config = Some(local);
+        assert!(analyzer.extract_dependencies(&model).is_empty());
+    }
+
+    #[test]
+    fn decomposes_diffusers_pipelines() {
+        let hub = HubClient::stub([
+            (
+                "org/sd/resolve/main/unet/config.json",
+                serde_json::json!({ "_class_name": "UNet2DConditionModel", "sample_size": 64 })
+                    .to_string(),
+            ),
+            (
+                "org/sd/resolve/main/text_encoder/config.json",
+                serde_json::json!({ "_name_or_path": "openai/clip-vit-large-patch14" }).to_string(),
+            ),
+            (
+                "org/sd/resolve/main/scheduler/scheduler_config.json",
+                serde_json::json!({ "num_train_timesteps": 1000 }).to_string(),
+            ),
+        ]);
+        let analyzer = ModelAnalyzer::with_hub(hub);
+        let mut model = model_with_files(
+            "org/sd",
+            &[
+                "model_index.json",
+                "unet/config.json",
+                "unet/diffusion_pytorch_model.safetensors",
+                "text_encoder/config.json",
+                "text_encoder/model.safetensors",
+                "scheduler/scheduler_config.json",
+                "vae/diffusion_pytorch_model.safetensors",
+            ],
+        );
+        model.model_index = Some(serde_json::json!({
+            "_class_name": "StableDiffusionPipeline",
+            "_diffusers_version": "0.30.0",
+            "safety_checker": [null, null],
+            "scheduler": ["diffusers", "PNDMScheduler"],
+            "text_encoder": ["transformers", "CLIPTextModel"],
+            "unet": ["diffusers", "UNet2DConditionModel"],
+            "vae": ["diffusers", "AutoencoderKL"]
+        }));
+
+        let components = analyzer.load_diffusers_components(&model);
+        let summary: Vec<_> = components
+            .iter()
+            .map(|c| {
+                (
+                    c.name.as_str(),
+                    c.class_name.as_deref(),
+                    c.has_weights,
+                    c.config.is_some(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("scheduler", Some("PNDMScheduler"), false, true),
+                ("text_encoder", Some("CLIPTextModel"), true, true),
+                ("unet", Some("UNet2DConditionModel"), true, true),
+                ("vae", Some("AutoencoderKL"), true, false),
+            ]
+        );
+        assert_eq!(
+            components[1].source_repo.as_deref(),
+            Some("openai/clip-vit-large-patch14")
+        );
+        assert_eq!(components[1].relation.as_deref(), Some("component"));
+        assert!(components[2].source_repo.is_none());
+
+        // Modules from other repositories become lineage dependencies
+        model.pipeline_components = components;
+        assert_eq!(
+            analyzer.extract_dependencies(&model),
+            vec![(
+                "openai/clip-vit-large-patch14".to_string(),
+                Some("component".to_string())
+            )]
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Contents of a PEFT `adapter_config.json`
+#[derive(Deserialize, Debug, Clone)]
+pub struct AdapterConfig {
+    pub base_model_name_or_path: Option<String>,
+    pub peft_type: Option<String>,
+    pub r: Option<u32>,
+    pub lora_alpha: Option<f64>,
+    pub target_modules: Option<Value>,
+    pub task_type: Option<String>,
+}
+
+impl AdapterConfig {
+    /// Base model id, if it refers to a Hub repository rather than a local path
+    pub fn base_model_id(&self) -> Option<&str> {
+        self.base_model_name_or_path
+            .as_deref()
+            .map(|s| s.trim())
+            .filter(|s| crate::model_analyzer::is_hub_repo_id(s))
+    }
+
+    /// Target modules as a list (PEFT also allows a single regex string)
+    pub fn target_modules_list(&self) -> Vec<String> {
+        match &self.target_modules {
+            Some(Value::Array(modules)) => modules
+                .iter()
+                .filter_map(|m| m.as_str().map(|s| s.to_string()))
+                .collect(),
+            Some(Value::String(pattern)) => vec![pattern.clone()],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Lineage relation implied by the PEFT method
+    pub fn relation(&self) -> String {
+        match self.peft_type.as_deref().map(|t| t.to_uppercase()) {
+            Some(t) if t == "LORA" || t == "ADALORA" => "lora".to_string(),
+            _ => "adapter".to_string(),
+        }
+    }
+}
@@ -1,7 +1,7 @@
+use super::{Component, Dependency, ExternalReference, Metadata, Signature};
 use serde::{Deserialize, Serialize};
-use super::{Metadata, Component, Dependency, ExternalReference};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AIBOM {
     #[serde(rename = "bomFormat")]
     pub bom_format: String,
@@ -15,4 +15,7 @@ pub struct AIBOM {
     pub dependencies: Vec<Dependency>,
     #[serde(rename = "externalReferences", skip_serializing_if = "Option::is_none")]
     pub external_references: Option<Vec<ExternalReference>>,
-}
\ No newline at end of file
+    /// JSF signature over the whole document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Signature>,
+}
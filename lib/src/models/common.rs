@@ -20,4 +20,19 @@ pub struct ExternalReference {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
-}
\ No newline at end of file
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Hash {
+    pub alg: String,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AttachedText {
+    #[serde(rename = "contentType", skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+    pub content: String,
+}
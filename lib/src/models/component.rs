@@ -1,11 +1,14 @@
+use super::{
+    AttachedText, ExternalReference, Hash, ModelCard, Organization, Pedigree, Property, Signature,
+};
 use serde::{Deserialize, Serialize};
-use super::{Organization, ModelCard, ExternalReference};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Component {
     #[serde(rename = "type")]
     pub component_type: String,
-    #[serde(rename = "bom-ref")]
+    /// Empty for components nested in a pedigree, which must not repeat a bom-ref
+    #[serde(rename = "bom-ref", default, skip_serializing_if = "String::is_empty")]
     pub bom_ref: String,
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -25,13 +28,23 @@ pub struct Component {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub copyright: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub hashes: Option<Vec<Hash>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub licenses: Option<Vec<License>>,
     #[serde(rename = "externalReferences", skip_serializing_if = "Option::is_none")]
     pub external_references: Option<Vec<ExternalReference>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub purl: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pedigree: Option<Pedigree>,
     #[serde(rename = "modelCard", skip_serializing_if = "Option::is_none")]
     pub model_card: Option<ModelCard>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Vec<Property>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<Component>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Signature>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -53,6 +66,10 @@ pub struct LicenseInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub text: Option<String>,
+    pub text: Option<AttachedText>,
+    /// Whether the license was `declared` by the author or `concluded` by analysis
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acknowledgement: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Vec<Property>>,
 }
-
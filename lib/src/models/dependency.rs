@@ -1,6 +1,7 @@
+use super::Property;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Dependency {
     #[serde(rename = "ref")]
     pub reference: String,
@@ -8,8 +9,8 @@ pub struct Dependency {
     pub depends_on: Vec<String>, // Simplified to just strings as per CycloneDX 1.6
 }
 
-// Keep the old structure for backward compatibility if needed
-#[derive(Serialize, Deserialize, Debug)]
+/// A single dependency edge together with the lineage relation that produced it
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DependencyReference {
     #[serde(rename = "ref")]
     pub reference: String,
@@ -17,4 +18,7 @@ pub struct DependencyReference {
     pub relation: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub scope: Option<String>,
-}
\ No newline at end of file
+    /// Edge-specific details, e.g. merge weight and density
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Vec<Property>>,
+}
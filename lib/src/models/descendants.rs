@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// A model derived, directly or transitively, from a base model
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DescendantEntry {
+    #[serde(rename = "modelId")]
+    pub model_id: String,
+    /// The model this one was derived from
+    pub parent: String,
+    /// Distance from the base model (direct derivatives are at depth 1)
+    pub depth: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relation: Option<String>,
+}
+
+/// Flat report of all derivatives found for a base model
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DescendantReport {
+    #[serde(rename = "baseModel")]
+    pub base_model: String,
+    #[serde(rename = "maxDepth")]
+    pub max_depth: usize,
+    pub descendants: Vec<DescendantEntry>,
+    /// Set when the search stopped at the descendant limit before exhausting the tree
+    pub truncated: bool,
+}
@@ -0,0 +1,12 @@
+/// Access conditions of a gated repository
+#[derive(Debug, Clone, PartialEq)]
+pub struct GatingInfo {
+    /// Approval mode, `auto` or `manual`
+    pub status: String,
+    /// Terms shown before access is granted (`extra_gated_prompt` and description)
+    pub terms: Option<String>,
+    /// SHA-256 of `terms`
+    pub terms_sha256: Option<String>,
+    /// Names of the fields requesters must fill in (`extra_gated_fields`)
+    pub fields: Vec<String>,
+}
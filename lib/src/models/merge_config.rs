@@ -0,0 +1,21 @@
+/// Merge recipe read from a mergekit configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConfig {
+    /// Merge method, e.g. `slerp`, `ties`, `dare_ties`, `linear`
+    pub merge_method: Option<String>,
+    pub base_model: Option<String>,
+    pub dtype: Option<String>,
+    pub sources: Vec<MergeSource>,
+    /// File the recipe was read from
+    pub source_file: String,
+}
+
+/// One input model of a merge with its per-model parameters
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeSource {
+    pub model: String,
+    /// Scalar weight, or a gradient rendered as a list
+    pub weight: Option<String>,
+    pub density: Option<String>,
+    pub layer_ranges: Vec<String>,
+}
@@ -1,7 +1,7 @@
+use super::{Component, Organization, Property};
 use serde::{Deserialize, Serialize};
-use super::{Component, Property, Organization};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Metadata {
     pub timestamp: String,
     pub tools: Tools,
@@ -10,12 +10,58 @@ pub struct Metadata {
     pub properties: Option<Vec<Property>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// `metadata.tools`; reads both the 1.5+ object and the array form of
+/// CycloneDX 1.4, and always writes the object form
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "ToolsForm")]
 pub struct Tools {
     pub components: Vec<ToolComponent>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ToolsForm {
+    Components {
+        #[serde(default)]
+        components: Vec<ToolComponent>,
+    },
+    Legacy(Vec<LegacyTool>),
+}
+
+/// A tool as listed by CycloneDX 1.4
+#[derive(Deserialize)]
+struct LegacyTool {
+    #[serde(default)]
+    vendor: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    version: String,
+}
+
+impl From<ToolsForm> for Tools {
+    fn from(form: ToolsForm) -> Self {
+        let components = match form {
+            ToolsForm::Components { components } => components,
+            ToolsForm::Legacy(tools) => tools
+                .into_iter()
+                .map(|tool| ToolComponent {
+                    bom_ref: format!("pkg:generic/{}@{}", tool.name, tool.version),
+                    manufacturer: Organization {
+                        name: tool.vendor,
+                        url: None,
+                    },
+                    name: tool.name,
+                    component_type: "application".to_string(),
+                    version: tool.version,
+                })
+                .collect(),
+        };
+        Self { components }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ToolComponent {
     #[serde(rename = "bom-ref")]
     pub bom_ref: String,
@@ -25,4 +71,3 @@ pub struct ToolComponent {
     pub component_type: String,
     pub version: String,
 }
-
@@ -1,15 +1,37 @@
+pub mod adapter_config;
 pub mod aibom;
 pub mod common;
 pub mod component;
 pub mod dependency;
+pub mod descendants;
+pub mod gating;
+pub mod merge_config;
 pub mod metadata;
 pub mod model_card;
 pub mod model_info;
+pub mod pedigree;
+pub mod pipeline;
+pub mod quantization;
+pub mod remote_code;
+pub mod repo_info;
+pub mod report;
+pub mod signature;
 
+pub use adapter_config::*;
 pub use aibom::*;
 pub use common::*;
 pub use component::*;
 pub use dependency::*;
+pub use descendants::*;
+pub use gating::*;
+pub use merge_config::*;
 pub use metadata::*;
 pub use model_card::*;
-pub use model_info::*;
\ No newline at end of file
+pub use model_info::*;
+pub use pedigree::*;
+pub use pipeline::*;
+pub use quantization::*;
+pub use remote_code::*;
+pub use repo_info::*;
+pub use report::*;
+pub use signature::*;
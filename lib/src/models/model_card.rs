@@ -1,6 +1,6 @@
+use super::{EmbeddingInfo, Property};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use super::Property;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ModelCard {
@@ -23,10 +23,40 @@ pub struct ModelParameters {
     pub model_architecture: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub task: Option<String>,
+    /// Training datasets, referenced by bom-ref
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datasets: Option<Vec<DatasetReference>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub inputs: Option<Vec<InputOutputData>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub outputs: Option<Vec<InputOutputData>>,
+    /// Dimension, pooling and normalization of embedding models. CycloneDX
+    /// has no field for these, so this is an extension of `modelParameters`;
+    /// XML and protobuf output carry it as `ai.embedding.*` model card properties.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<EmbeddingInfo>,
+}
+
+impl ModelCard {
+    /// Model card properties followed by the `modelParameters` extensions as
+    /// properties, for formats whose schema has no place for the extensions
+    pub fn properties_with_extensions(&self) -> Vec<Property> {
+        let mut properties = self.properties.clone().unwrap_or_default();
+        if let Some(embedding) = self
+            .model_parameters
+            .as_ref()
+            .and_then(|parameters| parameters.embedding.as_ref())
+        {
+            properties.extend(embedding.to_properties());
+        }
+        properties
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DatasetReference {
+    #[serde(rename = "ref")]
+    pub reference: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -47,4 +77,4 @@ pub struct PerformanceMetric {
     #[serde(rename = "type")]
     pub metric_type: String,
     pub value: String,
-}
\ No newline at end of file
+}
@@ -1,3 +1,7 @@
+use super::{
+    AdapterConfig, EmbeddingInfo, FormatConversion, MergeConfig, PipelineComponent,
+    QuantizationInfo, RemoteCodeFile,
+};
 use serde::Deserialize;
 use serde_json::Value;
 
@@ -15,10 +19,64 @@ pub struct ModelInfo {
     #[allow(dead_code)]
     pub last_modified: Option<String>,
     pub license: Option<String>,
+    /// Gating status: `false`, `"auto"` or `"manual"`
+    pub gated: Option<Value>,
     #[serde(rename = "cardData")]
     pub card_data: Option<Value>,
     #[allow(dead_code)]
     pub siblings: Option<Vec<Value>>,
     #[allow(dead_code)]
     pub sha: Option<String>,
-}
\ No newline at end of file
+    /// Git revision the repository files are read from; `None` for `main`
+    #[serde(skip)]
+    pub revision: Option<String>,
+    /// Parsed `adapter_config.json`, loaded separately from the repository files
+    #[serde(skip)]
+    pub adapter_config: Option<AdapterConfig>,
+    /// Raw `config.json`, loaded separately from the repository files
+    #[serde(skip)]
+    pub config: Option<Value>,
+    /// Quantization details derived from the repository files
+    #[serde(skip)]
+    pub quantization: Option<QuantizationInfo>,
+    /// Format conversion of unquantized weights, e.g. F16 GGUF files
+    #[serde(skip)]
+    pub conversion: Option<FormatConversion>,
+    /// Merge recipe from `mergekit_config.yml` or the model card
+    #[serde(skip)]
+    pub merge_config: Option<MergeConfig>,
+    /// Raw diffusers `model_index.json`
+    #[serde(skip)]
+    pub model_index: Option<Value>,
+    /// Sub-models of a multi-part pipeline
+    #[serde(skip)]
+    pub pipeline_components: Vec<PipelineComponent>,
+    /// Embedding output of a sentence-transformers pipeline
+    #[serde(skip)]
+    pub embedding: Option<EmbeddingInfo>,
+    /// Custom code files referenced by `auto_map` or custom pipeline entries
+    #[serde(skip)]
+    pub remote_code: Vec<RemoteCodeFile>,
+}
+
+impl ModelInfo {
+    /// Check whether the repository contains a file at the given path
+    pub fn has_file(&self, filename: &str) -> bool {
+        self.file_names().any(|f| f == filename)
+    }
+
+    /// Paths of all files in the repository listing
+    pub fn file_names(&self) -> impl Iterator<Item = &str> {
+        self.siblings
+            .iter()
+            .flatten()
+            .filter_map(|s| s.get("rfilename").and_then(|f| f.as_str()))
+    }
+}
+/// Entry of the Hub's paginated model, dataset or Space listing
+#[derive(Deserialize, Debug, Clone)]
+pub struct ModelSummary {
+    pub id: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
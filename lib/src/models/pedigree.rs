@@ -0,0 +1,18 @@
+use super::Component;
+use serde::{Deserialize, Serialize};
+
+/// CycloneDX pedigree describing where a component came from
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Pedigree {
+    /// Components this one was derived from (fine-tunes, adapters, merges)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ancestors: Option<Vec<Component>>,
+    /// Components derived from this one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub descendants: Option<Vec<Component>>,
+    /// Components this one is a variation of (quantizations, format conversions)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variants: Option<Vec<Component>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
@@ -0,0 +1,76 @@
+use super::Property;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A sub-model or support module of a multi-part pipeline (e.g. a diffusers
+/// UNet, VAE or scheduler)
+#[derive(Debug, Clone)]
+pub struct PipelineComponent {
+    /// Key of the module in the pipeline definition, usually also its subfolder
+    pub name: String,
+    /// Library providing the module class, e.g. `diffusers` or `transformers`
+    pub library: Option<String>,
+    pub class_name: Option<String>,
+    /// Parsed configuration file of the module, if present
+    pub config: Option<Value>,
+    /// Whether the module ships weight files
+    pub has_weights: bool,
+    /// Hub repository the module was taken from, when it differs from the pipeline repo
+    pub source_repo: Option<String>,
+    /// Lineage relation to `source_repo`
+    pub relation: Option<String>,
+}
+
+/// Output of an embedding pipeline such as sentence-transformers
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EmbeddingInfo {
+    /// Pooling strategy, e.g. `mean`, `cls`, `max`
+    #[serde(rename = "poolingMode", skip_serializing_if = "Option::is_none")]
+    pub pooling_mode: Option<String>,
+    /// Dimension of the produced embeddings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimension: Option<u64>,
+    /// Whether embeddings are L2-normalized
+    #[serde(default)]
+    pub normalized: bool,
+}
+
+impl EmbeddingInfo {
+    /// `ai.embedding.*` properties for formats that cannot carry the
+    /// `modelParameters.embedding` extension
+    pub fn to_properties(&self) -> Vec<Property> {
+        let mut properties = Vec::new();
+        if let Some(pooling_mode) = &self.pooling_mode {
+            properties.push(Property {
+                name: "ai.embedding.poolingMode".to_string(),
+                value: pooling_mode.clone(),
+            });
+        }
+        if let Some(dimension) = self.dimension {
+            properties.push(Property {
+                name: "ai.embedding.dimension".to_string(),
+                value: dimension.to_string(),
+            });
+        }
+        properties.push(Property {
+            name: "ai.embedding.normalized".to_string(),
+            value: self.normalized.to_string(),
+        });
+        properties
+    }
+
+    /// Read back the properties written by [`to_properties`](Self::to_properties)
+    pub fn from_properties(properties: &[Property]) -> Option<Self> {
+        let value = |name: &str| {
+            properties
+                .iter()
+                .find(|p| p.name == format!("ai.embedding.{}", name))
+                .map(|p| p.value.as_str())
+        };
+        Some(Self {
+            pooling_mode: value("poolingMode").map(str::to_string),
+            dimension: value("dimension").and_then(|d| d.parse().ok()),
+            normalized: value("normalized")? == "true",
+        })
+    }
+}
@@ -0,0 +1,25 @@
+/// Quantization details detected from repository files
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantizationInfo {
+    /// Quantization method, e.g. `gptq`, `awq`, `bitsandbytes`, `gguf`, `exl2`
+    pub method: String,
+    /// Weight bit width (fractional for EXL2 bits-per-weight)
+    pub bits: Option<f64>,
+    /// Numeric precision or format, e.g. `int4`, `nf4`, `Q4_K_M`
+    pub precision: Option<String>,
+    pub group_size: Option<i64>,
+    pub desc_act: Option<bool>,
+    /// File or convention the details were read from
+    pub source: String,
+    /// Method-specific settings, e.g. bitsandbytes compute dtype
+    pub settings: Vec<(String, String)>,
+}
+
+/// Full-precision weights re-packaged in another file format, e.g. F16 GGUF
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatConversion {
+    /// Target file format, e.g. `gguf`
+    pub format: String,
+    /// Precisions of the converted files, e.g. `F16` or `BF16,F32`
+    pub precision: Option<String>,
+}
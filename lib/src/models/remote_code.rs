@@ -0,0 +1,14 @@
+/// A Python file executed when loading a model with `trust_remote_code=True`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteCodeFile {
+    /// Repository hosting the file, which may differ from the model repository
+    pub repo: String,
+    /// Path of the file within `repo`
+    pub path: String,
+    /// Branch, tag or commit the file was read from; `None` for `main`
+    pub revision: Option<String>,
+    /// SHA-256 of the file contents, if it could be downloaded
+    pub sha256: Option<String>,
+    /// Config entries referencing the file, e.g. `auto_map.AutoModelForCausalLM`
+    pub references: Vec<String>,
+}
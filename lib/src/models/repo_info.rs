@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Kinds of Hub repository
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum RepoKind {
+    Model,
+    Dataset,
+    Space,
+}
+
+impl RepoKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Model => "model",
+            Self::Dataset => "dataset",
+            Self::Space => "space",
+        }
+    }
+
+    /// Path segment of the kind in Hub API URLs, e.g. `api/datasets`
+    pub fn api_path(self) -> &'static str {
+        match self {
+            Self::Model => "models",
+            Self::Dataset => "datasets",
+            Self::Space => "spaces",
+        }
+    }
+}
+
+/// Dataset metadata from `api/datasets/{id}`
+#[derive(Deserialize, Debug, Clone)]
+pub struct DatasetInfo {
+    pub id: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(rename = "cardData")]
+    pub card_data: Option<Value>,
+    /// Gating status: `false`, `"auto"` or `"manual"`
+    pub gated: Option<Value>,
+}
+
+/// Space metadata from `api/spaces/{id}`
+#[derive(Deserialize, Debug, Clone)]
+pub struct SpaceInfo {
+    pub id: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(rename = "cardData")]
+    pub card_data: Option<Value>,
+    /// `gradio`, `streamlit`, `docker` or `static`
+    pub sdk: Option<String>,
+    /// Models the Space declares it uses
+    #[serde(default)]
+    pub models: Vec<String>,
+    /// Datasets the Space declares it uses
+    #[serde(default)]
+    pub datasets: Vec<String>,
+}
+
+/// License of a dataset or Space: the card's `license` (the first one if
+/// several are listed), else a `license:` tag
+pub(crate) fn declared_license_name(card_data: Option<&Value>, tags: &[String]) -> Option<String> {
+    let from_card =
+        card_data
+            .and_then(|card| card.get("license"))
+            .and_then(|license| match license {
+                Value::String(license) => Some(license.clone()),
+                Value::Array(licenses) => licenses.first()?.as_str().map(str::to_string),
+                _ => None,
+            });
+    from_card.or_else(|| {
+        tags.iter()
+            .find_map(|tag| tag.strip_prefix("license:"))
+            .map(str::to_string)
+    })
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// A lineage node that was not fully processed
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NodeIssue {
+    #[serde(rename = "modelId")]
+    pub model_id: String,
+    /// Model whose lineage led to this node (absent for the main model)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    pub depth: usize,
+    /// Error kind, e.g. `not-found`, `gated`, `limit-exceeded`
+    pub kind: String,
+    pub message: String,
+}
+
+/// Outcome of a generation run alongside the AIBOM
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct GenerationReport {
+    /// Nodes whose metadata could not be fetched or parsed
+    pub failures: Vec<NodeIssue>,
+    /// Nodes deliberately not visited because a traversal limit was reached
+    pub skipped: Vec<NodeIssue>,
+    /// Fields rewritten or lost when rendering an older spec version
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub downgraded: Vec<DowngradeNote>,
+}
+
+impl GenerationReport {
+    /// Whether every node was processed
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty() && self.skipped.is_empty()
+    }
+}
+
+/// A field an older CycloneDX spec version cannot represent
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DowngradeNote {
+    /// Component the field belonged to
+    #[serde(rename = "bomRef")]
+    pub bom_ref: String,
+    /// JSON name of the field, e.g. `modelCard` or `licenses.acknowledgement`
+    pub field: String,
+    /// `mapped` when the value was kept in `aibom:downgrade:` properties or
+    /// another field, `dropped` when it was discarded
+    pub action: String,
+}
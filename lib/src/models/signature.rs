@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// JSON Signature Format (JSF) signature of the enclosing BOM or component
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Signature {
+    /// JWA name of the algorithm, e.g. `Ed25519`, `ES256`, `RS256`
+    pub algorithm: String,
+    #[serde(rename = "keyId", skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    #[serde(rename = "publicKey", skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<PublicKeyJwk>,
+    /// Base64url-encoded signature bytes
+    pub value: String,
+}
+
+/// Public key in JSON Web Key form
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PublicKeyJwk {
+    /// `OKP` (Ed25519), `EC` or `RSA`
+    pub kty: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    /// RSA modulus
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    /// RSA public exponent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+}
@@ -0,0 +1,51 @@
+use serde::Serialize;
+
+/// Progress of a generation run, reported to a [`ProgressObserver`]
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum ProgressEvent {
+    /// Metadata for a model is about to be fetched from the Hub
+    FetchStarted {
+        #[serde(rename = "modelId")]
+        model_id: String,
+        depth: usize,
+    },
+    /// A lineage, dataset or code dependency was discovered
+    DependencyFound {
+        #[serde(rename = "modelId")]
+        model_id: String,
+        dependency: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        relation: Option<String>,
+    },
+    /// A component was added to the BOM; `total` counts components so far
+    ComponentAdded {
+        #[serde(rename = "bomRef")]
+        bom_ref: String,
+        name: String,
+        total: usize,
+    },
+    /// A dependency failed or was skipped; generation carries on without it
+    Warning {
+        #[serde(rename = "modelId", skip_serializing_if = "Option::is_none")]
+        model_id: Option<String>,
+        message: String,
+    },
+}
+
+/// Receives progress events while an AIBOM is generated.
+///
+/// Called synchronously from the generating thread, so implementations should
+/// hand events off quickly (e.g. to a channel or progress bar).
+pub trait ProgressObserver: Send + Sync {
+    fn on_event(&self, event: &ProgressEvent);
+}
+
+impl<F> ProgressObserver for F
+where
+    F: Fn(&ProgressEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &ProgressEvent) {
+        self(event)
+    }
+}
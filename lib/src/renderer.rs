@@ -0,0 +1,167 @@
+use crate::*;
+use tracing::debug;
+
+/// Serializes AIBOMs according to [`OutputOptions`]: downgrades and validates
+/// CycloneDX output and signs it when a [`Signer`] is set. Needs no Hub access,
+/// so documents read from files can be rendered without a generator.
+#[derive(Clone)]
+pub struct Renderer {
+    output: OutputOptions,
+    signer: Option<Signer>,
+}
+
+impl Renderer {
+    pub fn new(output: OutputOptions) -> Self {
+        Self {
+            output,
+            signer: None,
+        }
+    }
+
+    /// Sign rendered output with `signer`; requires CycloneDX JSON output
+    pub fn signer(mut self, signer: Signer) -> Result<Self, GenerateError> {
+        if self.output.format != OutputFormat::CyclonedxJson {
+            return Err(GenerateError::Invalid(
+                "JSF signatures require CycloneDX JSON output".to_string(),
+            ));
+        }
+        self.signer = Some(signer);
+        Ok(self)
+    }
+
+    pub fn output(&self) -> &OutputOptions {
+        &self.output
+    }
+
+    /// Serialize an AIBOM according to the output options
+    pub fn render(&self, aibom: &AIBOM) -> Result<String, GenerateError> {
+        Ok(self.render_with_notes(aibom)?.0)
+    }
+
+    /// Like [`render`](Self::render), also listing the fields the configured
+    /// spec version could not represent. CycloneDX output is checked against
+    /// the bundled schema first and rejected with [`GenerateError::Invalid`].
+    /// With a [`Signer`] set the document is signed last.
+    pub fn render_with_notes(
+        &self,
+        aibom: &AIBOM,
+    ) -> Result<(String, Vec<DowngradeNote>), GenerateError> {
+        let output = &self.output;
+        if !output.format.is_cyclonedx() {
+            return Ok((
+                formats::render(aibom, output.format, output.pretty)?,
+                Vec::new(),
+            ));
+        }
+
+        let (aibom, notes) = formats::downgrade::downgrade(aibom, output.spec_version);
+        for note in &notes {
+            debug!(
+                bom_ref = %note.bom_ref,
+                field = %note.field,
+                "{} for CycloneDX {}",
+                note.action,
+                output.spec_version.as_str()
+            );
+        }
+        validation::validate_aibom(&aibom)?;
+        let Some(signer) = &self.signer else {
+            return Ok((
+                formats::render(&aibom, output.format, output.pretty)?,
+                notes,
+            ));
+        };
+
+        // Sign the exact document being written, after any downgrade rewrites
+        let mut document = formats::to_json_value(&aibom, output.format)?;
+        signer
+            .sign_document(&mut document)
+            .map_err(|e| GenerateError::Invalid(e.to_string()))?;
+        let output = if output.pretty {
+            serde_json::to_string_pretty(&document)?
+        } else {
+            serde_json::to_string(&document)?
+        };
+        Ok((output, notes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::BomFixture;
+    use serde_json::json;
+
+    /// A CycloneDX 1.4 document listing its tools as an array
+    fn legacy_aibom() -> AIBOM {
+        BomFixture::new()
+            .spec_version("1.4")
+            .metadata(
+                "tools",
+                json!([{ "vendor": "Acme", "name": "bom-tool", "version": "2.1" }]),
+            )
+            .build()
+    }
+
+    fn output(format: OutputFormat, spec_version: SpecVersion) -> OutputOptions {
+        OutputOptions {
+            format,
+            pretty: false,
+            spec_version,
+        }
+    }
+
+    #[test]
+    fn reads_legacy_tool_arrays() {
+        let tools = legacy_aibom().metadata.tools.components;
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].bom_ref, "pkg:generic/bom-tool@2.1");
+        assert_eq!(tools[0].manufacturer.name, "Acme");
+        assert_eq!(tools[0].name, "bom-tool");
+        assert_eq!(tools[0].version, "2.1");
+    }
+
+    #[test]
+    fn renders_tools_in_the_form_of_the_target_version() {
+        // Documents read as 1.4 and rewritten as 1.6, as merging does
+        let mut aibom = legacy_aibom();
+        aibom.spec_version = SpecVersion::V1_6.as_str().to_string();
+        let render = |spec_version| {
+            let rendered = Renderer::new(output(OutputFormat::CyclonedxJson, spec_version))
+                .render(&aibom)
+                .unwrap();
+            serde_json::from_str::<Value>(&rendered).unwrap()["metadata"]["tools"].clone()
+        };
+
+        assert_eq!(
+            render(SpecVersion::V1_4),
+            json!([{ "vendor": "Acme", "name": "bom-tool", "version": "2.1" }])
+        );
+        assert_eq!(
+            render(SpecVersion::V1_6)["components"][0]["bom-ref"],
+            "pkg:generic/bom-tool@2.1"
+        );
+    }
+
+    #[test]
+    fn signs_only_cyclonedx_json() {
+        use p256::pkcs8::{EncodePrivateKey, LineEnding};
+
+        let key = ed25519_dalek::SigningKey::from_bytes(&[7; 32])
+            .to_pkcs8_pem(LineEnding::LF)
+            .unwrap();
+        let signer = Signer::new(SigningKey::from_pem(&key).unwrap());
+
+        let rejected = Renderer::new(output(OutputFormat::Spdx3Json, SpecVersion::V1_6))
+            .signer(signer.clone());
+        assert!(matches!(rejected, Err(GenerateError::Invalid(_))));
+
+        let renderer = Renderer::new(output(OutputFormat::CyclonedxJson, SpecVersion::V1_6))
+            .signer(signer)
+            .unwrap();
+        let rendered: Value =
+            serde_json::from_str(&renderer.render(&legacy_aibom()).unwrap()).unwrap();
+        assert!(rendered["metadata"]["tools"].is_array());
+        assert_eq!(rendered["signature"]["algorithm"], "Ed25519");
+    }
+}
@@ -0,0 +1,97 @@
+//! Hand-built CycloneDX documents shared by the unit tests.
+
+use crate::*;
+use serde_json::json;
+
+/// Builds a CycloneDX document piece by piece. Starts as 1.6 with a fixed
+/// serial number and timestamp, no tools, an `app` metadata component and no
+/// components or dependencies.
+pub(crate) struct BomFixture {
+    document: Value,
+}
+
+impl BomFixture {
+    pub(crate) fn new() -> Self {
+        Self {
+            document: json!({
+                "bomFormat": "CycloneDX",
+                "specVersion": "1.6",
+                "serialNumber": "urn:uuid:00000000-0000-4000-8000-000000000000",
+                "version": 1,
+                "metadata": {
+                    "timestamp": "2024-05-01T12:00:00Z",
+                    "tools": { "components": [] },
+                    "component": { "type": "application", "bom-ref": "app", "name": "app" }
+                },
+                "components": [],
+                "dependencies": []
+            }),
+        }
+    }
+
+    pub(crate) fn spec_version(mut self, spec_version: &str) -> Self {
+        self.document["specVersion"] = json!(spec_version);
+        self
+    }
+
+    pub(crate) fn serial_number(mut self, serial_number: &str) -> Self {
+        self.document["serialNumber"] = json!(serial_number);
+        self
+    }
+
+    /// Set a top-level field, e.g. `externalReferences`
+    pub(crate) fn set(mut self, key: &str, value: Value) -> Self {
+        self.document[key] = value;
+        self
+    }
+
+    /// Set a `metadata` field, e.g. `tools` or `component`
+    pub(crate) fn metadata(mut self, key: &str, value: Value) -> Self {
+        self.document["metadata"][key] = value;
+        self
+    }
+
+    pub(crate) fn component(mut self, component: Value) -> Self {
+        self.document["components"]
+            .as_array_mut()
+            .unwrap()
+            .push(component);
+        self
+    }
+
+    pub(crate) fn dependency(mut self, reference: &str, depends_on: &[&str]) -> Self {
+        self.document["dependencies"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({ "ref": reference, "dependsOn": depends_on }));
+        self
+    }
+
+    pub(crate) fn to_value(&self) -> Value {
+        self.document.clone()
+    }
+
+    pub(crate) fn build(&self) -> AIBOM {
+        serde_json::from_value(self.to_value()).unwrap()
+    }
+}
+
+/// A model component using `purl` as its bom-ref
+pub(crate) fn model(name: &str, purl: &str) -> Value {
+    json!({
+        "type": "machine-learning-model",
+        "bom-ref": purl,
+        "name": name,
+        "purl": purl
+    })
+}
+
+/// A dataset component using `purl` as its bom-ref
+pub(crate) fn dataset(name: &str, purl: &str) -> Value {
+    json!({
+        "type": "data",
+        "bom-ref": purl,
+        "name": name,
+        "purl": purl
+    })
+}
@@ -0,0 +1,239 @@
+//! JSON Schema validation of CycloneDX documents against the AIBOM subset.
+//!
+//! The schemas for 1.4–1.6 are bundled with the crate, so validation works
+//! offline. They are not the official CycloneDX schemas but structural
+//! subsets written for AIBOMs: every object an AIBOM contains is checked key
+//! by key, while sections AIBOMs never produce (services, vulnerabilities,
+//! evidence, ...) are only checked to be objects. Passing them does not mean
+//! a document conforms to the full CycloneDX specification.
+//!
+//! License ids refer to `spdx.schema.json` as the official schemas do; it is
+//! built from the SPDX license list of the `spdx` crate, and no schema is ever
+//! fetched over the network.
+
+use crate::*;
+use jsonschema::{Resource, Retrieve, Uri, Validator};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+const SCHEMA_1_4: &str = include_str!("../schemas/aibom-subset-1.4.schema.json");
+const SCHEMA_1_5: &str = include_str!("../schemas/aibom-subset-1.5.schema.json");
+const SCHEMA_1_6: &str = include_str!("../schemas/aibom-subset-1.6.schema.json");
+
+/// URI the CycloneDX schemas use for the SPDX license id enumeration
+const SPDX_SCHEMA_URI: &str = "http://cyclonedx.org/schema/spdx.schema.json";
+
+/// A schema violation in a BOM document
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SchemaError {
+    /// JSON pointer to the offending value, e.g. `/components/0/type`
+    pub pointer: String,
+    /// JSON pointer to the schema keyword that failed
+    #[serde(rename = "schemaPath")]
+    pub schema_path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pointer = if self.pointer.is_empty() {
+            "/"
+        } else {
+            &self.pointer
+        };
+        write!(f, "{}: {}", pointer, self.message)
+    }
+}
+
+fn validator(version: SpecVersion) -> &'static Validator {
+    static VALIDATORS: [OnceLock<Validator>; 3] = [const { OnceLock::new() }; 3];
+    let (index, schema) = match version {
+        SpecVersion::V1_4 => (0, SCHEMA_1_4),
+        SpecVersion::V1_5 => (1, SCHEMA_1_5),
+        SpecVersion::V1_6 => (2, SCHEMA_1_6),
+    };
+    VALIDATORS[index].get_or_init(|| {
+        let schema: Value = serde_json::from_str(schema).expect("bundled schema is JSON");
+        let spdx = Resource::from_contents(spdx_schema()).expect("SPDX schema has a draft");
+        jsonschema::draft7::options()
+            .should_validate_formats(true)
+            .with_resource(SPDX_SCHEMA_URI, spdx)
+            .with_retriever(BundledOnly)
+            .build(&schema)
+            .expect("bundled schema compiles")
+    })
+}
+
+/// `spdx.schema.json`: every SPDX license and exception id
+fn spdx_schema() -> Value {
+    let ids: Vec<&str> = spdx::identifiers::LICENSES
+        .iter()
+        .map(|(id, _, _)| *id)
+        .chain(spdx::identifiers::EXCEPTIONS.iter().map(|(id, _)| *id))
+        .collect();
+    serde_json::json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "$id": SPDX_SCHEMA_URI,
+        "$comment": format!("SPDX license list {}", spdx::identifiers::VERSION),
+        "enum": ids,
+    })
+}
+
+/// Refuses every reference not registered with the validator, so validation
+/// never goes to the network
+struct BundledOnly;
+
+impl Retrieve for BundledOnly {
+    fn retrieve(&self, uri: &Uri<&str>) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        Err(format!("schema {} is not bundled", uri).into())
+    }
+}
+
+/// Validate a CycloneDX JSON document against the AIBOM subset schema of its
+/// `specVersion`
+pub fn validate_document(document: &Value) -> Vec<SchemaError> {
+    let spec_version = document.get("specVersion").and_then(Value::as_str);
+    match spec_version.and_then(SpecVersion::parse) {
+        Some(version) => validate_document_as(document, version),
+        None => vec![SchemaError {
+            pointer: "/specVersion".to_string(),
+            schema_path: String::new(),
+            message: match spec_version {
+                Some(version) => format!("unsupported CycloneDX version {:?}", version),
+                None => "missing CycloneDX specVersion".to_string(),
+            },
+        }],
+    }
+}
+
+/// Validate a CycloneDX JSON document against the AIBOM subset schema of `version`
+pub fn validate_document_as(document: &Value, version: SpecVersion) -> Vec<SchemaError> {
+    validator(version)
+        .iter_errors(document)
+        .map(|error| SchemaError {
+            pointer: error.instance_path.to_string(),
+            schema_path: error.schema_path.to_string(),
+            message: error.to_string(),
+        })
+        .collect()
+}
+
+/// Validate the CycloneDX JSON form of an AIBOM
+pub fn validate_aibom(aibom: &AIBOM) -> Result<(), GenerateError> {
+    let document = formats::to_json_value(aibom, OutputFormat::CyclonedxJson)?;
+    let errors = validate_document(&document);
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let details: Vec<String> = errors.iter().take(5).map(|e| e.to_string()).collect();
+    Err(GenerateError::Invalid(format!(
+        "{} schema violation(s): {}",
+        errors.len(),
+        details.join("; ")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn document(spec_version: &str, component: Value) -> Value {
+        json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": spec_version,
+            "version": 1,
+            "components": [component]
+        })
+    }
+
+    fn pointers(errors: Vec<SchemaError>) -> Vec<String> {
+        errors.into_iter().map(|e| e.pointer).collect()
+    }
+
+    #[test]
+    fn accepts_keys_the_subset_does_not_model() {
+        let third_party = json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.6",
+            "version": 1,
+            "metadata": { "lifecycles": [{ "phase": "build" }] },
+            "components": [{
+                "type": "machine-learning-model",
+                "name": "model",
+                "x-vendor-extension": true,
+                "modelCard": { "modelParameters": { "architectureFamily": "llama" } }
+            }],
+            "services": [{ "name": "inference" }]
+        });
+        assert_eq!(validate_document(&third_party), Vec::new());
+
+        // 1.5 model cards have properties
+        let card = json!({
+            "type": "machine-learning-model",
+            "name": "model",
+            "modelCard": { "properties": [{ "name": "ai.peft.type", "value": "LORA" }] }
+        });
+        assert_eq!(validate_document(&document("1.5", card)), Vec::new());
+    }
+
+    #[test]
+    fn rejects_keys_added_by_later_versions() {
+        let component = json!({
+            "type": "machine-learning-model",
+            "name": "model",
+            "authors": [{ "name": "org" }],
+            "modelCard": {}
+        });
+        assert_eq!(
+            pointers(validate_document(&document("1.5", component.clone()))),
+            ["/components/0/authors"]
+        );
+
+        let mut errors = pointers(validate_document(&document("1.4", component)));
+        errors.sort();
+        assert_eq!(
+            errors,
+            [
+                "/components/0/authors",
+                "/components/0/modelCard",
+                "/components/0/type"
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_license_ids_against_the_spdx_list() {
+        let licensed = |id: &str| {
+            document(
+                "1.6",
+                json!({
+                    "type": "library",
+                    "name": "lib",
+                    "licenses": [{ "license": { "id": id } }]
+                }),
+            )
+        };
+        assert_eq!(validate_document(&licensed("Apache-2.0")), Vec::new());
+        assert_eq!(validate_document(&licensed("LLVM-exception")), Vec::new());
+        // Reported at the license choice, whose alternatives all fail
+        assert_eq!(
+            pointers(validate_document(&licensed("llama2"))),
+            ["/components/0/licenses/0"]
+        );
+    }
+
+    #[test]
+    fn reports_missing_and_unsupported_spec_versions() {
+        let mut document = document("1.3", json!({ "type": "library", "name": "lib" }));
+        let errors = validate_document(&document);
+        assert_eq!(pointers(errors.clone()), ["/specVersion"]);
+        assert!(errors[0].message.contains("unsupported"));
+
+        document.as_object_mut().unwrap().remove("specVersion");
+        assert_eq!(
+            validate_document(&document)[0].message,
+            "missing CycloneDX specVersion"
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! Checking the JSF signatures of CycloneDX JSON documents against a set of
+//! trusted public keys.
+
+use crate::signing::{ec_jwk, okp_jwk, rsa_jwk, signing_input};
+use crate::*;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::Serialize;
+use std::path::Path;
+
+/// Public key whose signatures are accepted
+#[derive(Clone)]
+pub struct TrustedKey {
+    /// Name reported for documents this key signed, e.g. the key file's stem
+    pub name: String,
+    jwk: PublicKeyJwk,
+    key: PublicKey,
+}
+
+#[derive(Clone)]
+enum PublicKey {
+    Ed25519(ed25519_dalek::VerifyingKey),
+    P256(p256::ecdsa::VerifyingKey),
+    P384(p384::ecdsa::VerifyingKey),
+    Rsa(Box<rsa::RsaPublicKey>),
+}
+
+impl TrustedKey {
+    /// Read an Ed25519, P-256, P-384 or RSA public key from PEM: SPKI
+    /// (`PUBLIC KEY`) or PKCS#1 (`RSA PUBLIC KEY`)
+    pub fn from_pem(name: &str, pem: &str) -> Result<Self, SignatureError> {
+        use p256::pkcs8::DecodePublicKey;
+        use rsa::pkcs1::DecodeRsaPublicKey;
+        use rsa::traits::PublicKeyParts;
+
+        let key = if pem.contains("-----BEGIN RSA PUBLIC KEY-----") {
+            rsa::RsaPublicKey::from_pkcs1_pem(pem)
+                .map(|key| PublicKey::Rsa(Box::new(key)))
+                .ok()
+        } else {
+            ed25519_dalek::VerifyingKey::from_public_key_pem(pem)
+                .map(PublicKey::Ed25519)
+                .or_else(|_| {
+                    p256::ecdsa::VerifyingKey::from_public_key_pem(pem).map(PublicKey::P256)
+                })
+                .or_else(|_| {
+                    p384::ecdsa::VerifyingKey::from_public_key_pem(pem).map(PublicKey::P384)
+                })
+                .or_else(|_| {
+                    rsa::RsaPublicKey::from_public_key_pem(pem)
+                        .map(|key| PublicKey::Rsa(Box::new(key)))
+                })
+                .ok()
+        };
+        let key = key.ok_or_else(|| {
+            SignatureError::Key(format!(
+                "{}: expected an Ed25519, P-256, P-384 or RSA public key in PEM form",
+                name
+            ))
+        })?;
+
+        let jwk = match &key {
+            PublicKey::Ed25519(key) => okp_jwk(key.as_bytes()),
+            PublicKey::P256(key) => {
+                let point = key.to_encoded_point(false);
+                ec_jwk("P-256", point.x().unwrap(), point.y().unwrap())
+            }
+            PublicKey::P384(key) => {
+                let point = key.to_encoded_point(false);
+                ec_jwk("P-384", point.x().unwrap(), point.y().unwrap())
+            }
+            PublicKey::Rsa(key) => rsa_jwk(&key.n().to_bytes_be(), &key.e().to_bytes_be()),
+        };
+        Ok(Self {
+            name: name.to_string(),
+            jwk,
+            key,
+        })
+    }
+
+    /// Read a PEM public key file, named after the file's stem
+    pub fn from_pem_file(path: &Path) -> Result<Self, SignatureError> {
+        let pem = std::fs::read_to_string(path)
+            .map_err(|e| SignatureError::Key(format!("{}: {}", path.display(), e)))?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        Self::from_pem(&name, &pem)
+    }
+
+    /// The key as a JWK, as it appears in a signature's `publicKey`
+    pub fn jwk(&self) -> &PublicKeyJwk {
+        &self.jwk
+    }
+
+    fn verify(&self, algorithm: &str, message: &[u8], value: &[u8]) -> Result<(), String> {
+        use rsa::signature::Verifier;
+        let mismatch = || "signature does not match".to_string();
+        match (&self.key, algorithm) {
+            (PublicKey::Ed25519(key), "Ed25519") => {
+                let signature =
+                    ed25519_dalek::Signature::from_slice(value).map_err(|_| mismatch())?;
+                key.verify(message, &signature).map_err(|_| mismatch())
+            }
+            (PublicKey::P256(key), "ES256") => {
+                let signature =
+                    p256::ecdsa::Signature::from_slice(value).map_err(|_| mismatch())?;
+                key.verify(message, &signature).map_err(|_| mismatch())
+            }
+            (PublicKey::P384(key), "ES384") => {
+                let signature =
+                    p384::ecdsa::Signature::from_slice(value).map_err(|_| mismatch())?;
+                key.verify(message, &signature).map_err(|_| mismatch())
+            }
+            (PublicKey::Rsa(key), "RS256" | "RS384" | "RS512") => {
+                let key = key.as_ref().clone();
+                let signature =
+                    rsa::pkcs1v15::Signature::try_from(value).map_err(|_| mismatch())?;
+                match algorithm {
+                    "RS256" => rsa::pkcs1v15::VerifyingKey::<sha2::Sha256>::new(key)
+                        .verify(message, &signature),
+                    "RS384" => rsa::pkcs1v15::VerifyingKey::<sha2::Sha384>::new(key)
+                        .verify(message, &signature),
+                    _ => rsa::pkcs1v15::VerifyingKey::<sha2::Sha512>::new(key)
+                        .verify(message, &signature),
+                }
+                .map_err(|_| mismatch())
+            }
+            _ => Err(format!(
+                "algorithm {} does not match the {} key {}",
+                algorithm, self.jwk.kty, self.name
+            )),
+        }
+    }
+}
+
+/// Outcome of checking one signature
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureCheck {
+    pub valid: bool,
+    /// Name of the trusted key that produced the signature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of checking a signed component
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentSignatureCheck {
+    #[serde(rename = "bom-ref")]
+    pub bom_ref: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub check: SignatureCheck,
+}
+
+/// Signature checks of a document and of every signed component in it
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationReport {
+    pub document: SignatureCheck,
+    /// Signed components; unsigned components are not listed
+    pub components: Vec<ComponentSignatureCheck>,
+}
+
+impl VerificationReport {
+    /// Whether the document signature and every component signature are valid
+    pub fn passed(&self) -> bool {
+        self.document.valid && self.components.iter().all(|c| c.check.valid)
+    }
+}
+
+/// Check the JSF signature of a CycloneDX JSON document, and those of its
+/// (nested) components, against `trusted` keys. A signature is accepted only
+/// when its `publicKey` is one of the trusted keys or, lacking one, its
+/// `keyId` names one. An unsigned document fails.
+pub fn verify_document(document: &Value, trusted: &[TrustedKey]) -> VerificationReport {
+    let mut components = Vec::new();
+    if let Some(items) = document.get("components").and_then(Value::as_array) {
+        collect_component_checks(items, trusted, &mut components);
+    }
+    VerificationReport {
+        document: check_signature(document, trusted),
+        components,
+    }
+}
+
+fn collect_component_checks(
+    items: &[Value],
+    trusted: &[TrustedKey],
+    out: &mut Vec<ComponentSignatureCheck>,
+) {
+    for item in items {
+        if item.get("signature").is_some() {
+            let text = |field: &str| item[field].as_str().unwrap_or_default().to_string();
+            out.push(ComponentSignatureCheck {
+                bom_ref: text("bom-ref"),
+                name: text("name"),
+                check: check_signature(item, trusted),
+            });
+        }
+        if let Some(children) = item.get("components").and_then(Value::as_array) {
+            collect_component_checks(children, trusted, out);
+        }
+    }
+}
+
+fn check_signature(value: &Value, trusted: &[TrustedKey]) -> SignatureCheck {
+    let mut check = SignatureCheck {
+        valid: false,
+        signer: None,
+        algorithm: None,
+        key_id: None,
+        error: None,
+    };
+    let Some(raw) = value.get("signature") else {
+        check.error = Some("not signed".to_string());
+        return check;
+    };
+    if raw.get("signers").is_some() || raw.get("chain").is_some() {
+        check.error = Some("multiple signatures (signers/chain) are not supported".to_string());
+        return check;
+    }
+    if raw.get("excludes").is_some() {
+        check.error = Some("signatures with excludes are not supported".to_string());
+        return check;
+    }
+    let signature: Signature = match serde_json::from_value(raw.clone()) {
+        Ok(signature) => signature,
+        Err(e) => {
+            check.error = Some(format!("malformed signature: {}", e));
+            return check;
+        }
+    };
+    check.algorithm = Some(signature.algorithm.clone());
+    check.key_id = signature.key_id.clone();
+
+    let key = match (&signature.public_key, &signature.key_id) {
+        (Some(jwk), _) => trusted.iter().find(|key| key.jwk() == jwk),
+        (None, Some(key_id)) => trusted.iter().find(|key| &key.name == key_id),
+        (None, None) => None,
+    };
+    let Some(key) = key else {
+        check.error = Some("signed by an untrusted key".to_string());
+        return check;
+    };
+    check.signer = Some(key.name.clone());
+
+    let Ok(bytes) = URL_SAFE_NO_PAD.decode(signature.value.trim_end_matches('=')) else {
+        check.error = Some("signature value is not base64url".to_string());
+        return check;
+    };
+    let message = signing_input(value.as_object().unwrap());
+    match key.verify(&signature.algorithm, &message, &bytes) {
+        Ok(()) => check.valid = true,
+        Err(error) => check.error = Some(error),
+    }
+    check
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+    use serde_json::json;
+
+    fn key_pair(seed: u8) -> (SigningKey, TrustedKey) {
+        let key = p256::ecdsa::SigningKey::from_slice(&[seed; 32]).unwrap();
+        let private = key.to_pkcs8_pem(LineEnding::LF).unwrap();
+        let public = key
+            .verifying_key()
+            .to_public_key_pem(LineEnding::LF)
+            .unwrap();
+        (
+            SigningKey::from_pem(&private).unwrap(),
+            TrustedKey::from_pem(&format!("key{}", seed), &public).unwrap(),
+        )
+    }
+
+    #[test]
+    fn verifies_signer_and_detects_tampering() {
+        let (signing_key, trusted) = key_pair(1);
+        let (_, other) = key_pair(2);
+        let mut document = json!({
+            "bomFormat": "CycloneDX",
+            "components": [
+                { "type": "machine-learning-model", "bom-ref": "a", "name": "a" },
+                { "type": "machine-learning-model", "bom-ref": "b", "name": "b" }
+            ]
+        });
+        Signer::new(signing_key)
+            .sign_components(true)
+            .sign_document(&mut document)
+            .unwrap();
+
+        let report = verify_document(&document, std::slice::from_ref(&trusted));
+        assert!(report.passed());
+        assert_eq!(report.document.signer.as_deref(), Some("key1"));
+        assert_eq!(report.document.algorithm.as_deref(), Some("ES256"));
+        assert_eq!(report.components.len(), 2);
+
+        let untrusted = verify_document(&document, &[other]);
+        assert_eq!(
+            untrusted.document.error.as_deref(),
+            Some("signed by an untrusted key")
+        );
+
+        document["components"][1]["name"] = json!("renamed");
+        let tampered = verify_document(&document, &[trusted]);
+        assert!(!tampered.passed());
+        assert!(!tampered.document.valid);
+        assert!(tampered.components[0].check.valid);
+        assert_eq!(
+            tampered.components[1].check.error.as_deref(),
+            Some("signature does not match")
+        );
+    }
+}
@@ -1,90 +1,348 @@
-use actix_web::{App, HttpResponse, HttpServer, Result, middleware::Logger, web};
-use lib::AIBOMGenerator;
+use actix_web::{
+    App, HttpRequest, HttpResponse, HttpServer, Result, http::StatusCode, http::header,
+    middleware::Logger, web,
+};
+use futures_util::stream;
+use lib::{
+    AIBOMGenerator, GenerateError, GenerationReport, HubClient, HubConfig, OutputFormat,
+    ProgressEvent, ProgressObserver, SpecVersion,
+};
+use log::{error, info};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Largest JSON request body accepted
+const MAX_JSON_BODY: usize = 16 * 1024 * 1024;
 
 #[derive(Deserialize)]
 struct GenerateRequest {
     model_id: String,
     #[serde(default)]
     verbose: bool,
+    /// Document format of the returned `aibom`
+    #[serde(default)]
+    format: OutputFormat,
+    /// CycloneDX spec version of the returned `aibom`
+    #[serde(default)]
+    spec_version: SpecVersion,
 }
 
 #[derive(Serialize)]
 struct GenerateResponse {
     success: bool,
     aibom: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    report: Option<GenerationReport>,
     error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_kind: Option<String>,
+}
+
+type GenerationResult = Result<(lib::AIBOM, GenerationReport), GenerateError>;
+
+impl GenerateResponse {
+    fn failure(error: String, error_kind: Option<String>) -> Self {
+        Self {
+            success: false,
+            aibom: None,
+            report: None,
+            error: Some(error),
+            error_kind,
+        }
+    }
+}
+
+/// Run generation on the blocking thread pool, reporting progress to `observer`
+async fn run_generation(
+    hub: HubClient,
+    model_id: String,
+    observer: Option<Arc<dyn ProgressObserver>>,
+) -> Result<GenerationResult, actix_web::error::BlockingError> {
+    web::block(move || -> GenerationResult {
+        let mut builder = AIBOMGenerator::builder().hub_client(hub);
+        if let Some(observer) = observer {
+            builder = builder.observer(observer);
+        }
+        builder.build()?.generate_aibom_with_report(&model_id)
+    })
+    .await
+}
+
+/// Downgrade CycloneDX output to the requested spec version, recording what
+/// was lost in the report, and check it against the bundled schema
+fn apply_spec_version(
+    aibom: lib::AIBOM,
+    report: &mut GenerationReport,
+    format: OutputFormat,
+    spec_version: SpecVersion,
+) -> Result<lib::AIBOM, GenerateError> {
+    if !format.is_cyclonedx() {
+        return Ok(aibom);
+    }
+    let (aibom, notes) = lib::formats::downgrade::downgrade(&aibom, spec_version);
+    lib::validate_aibom(&aibom)?;
+    report.downgraded = notes;
+    Ok(aibom)
+}
+
+/// Response for an AIBOM that could not be turned into the requested document
+fn serialization_failure(e: GenerateError) -> GenerateResponse {
+    error!("❌ AIBOM serialization failed: {}", e);
+    GenerateResponse::failure(
+        format!("Error serializing AIBOM: {}", e),
+        Some(e.kind().to_string()),
+    )
 }
 
-async fn generate_aibom(req: web::Json<GenerateRequest>) -> Result<HttpResponse> {
+/// Map a generation outcome to an HTTP status and response body
+fn into_response(
+    result: Result<GenerationResult, actix_web::error::BlockingError>,
+    model_id: &str,
+    verbose: bool,
+    format: OutputFormat,
+    spec_version: SpecVersion,
+) -> (StatusCode, GenerateResponse) {
+    match result {
+        Ok(Ok((aibom, mut report))) => {
+            let document = apply_spec_version(aibom, &mut report, format, spec_version)
+                .and_then(|aibom| Ok((lib::formats::to_json_value(&aibom, format)?, aibom)));
+            let (aibom_json, aibom) = match document {
+                Ok(document) => document,
+                Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, serialization_failure(e)),
+            };
+
+            if verbose {
+                info!("✅ AIBOM generation successful");
+                info!("📊 Component count: {}", aibom.components.len());
+                info!("🔗 Dependencies: {}", aibom.dependencies.len());
+            } else {
+                info!("✅ AIBOM generation successful: {}", model_id);
+            }
+
+            (
+                StatusCode::OK,
+                GenerateResponse {
+                    success: true,
+                    aibom: Some(aibom_json),
+                    report: Some(report),
+                    error: None,
+                    error_kind: None,
+                },
+            )
+        }
+        Ok(Err(e)) => {
+            error!("❌ AIBOM generation failed: {}", e);
+            let status = match e {
+                GenerateError::NotFound(_) => StatusCode::NOT_FOUND,
+                GenerateError::Gated(_) => StatusCode::FORBIDDEN,
+                GenerateError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+                GenerateError::Network(_) => StatusCode::BAD_GATEWAY,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (
+                status,
+                GenerateResponse::failure(
+                    format!("Error generating AIBOM: {}", e),
+                    Some(e.kind().to_string()),
+                ),
+            )
+        }
+        Err(e) => {
+            error!("❌ Thread pool execution failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                GenerateResponse::failure(format!("Error executing task: {}", e), None),
+            )
+        }
+    }
+}
+
+/// Document format named in the `Accept` header, if the client asked for the
+/// bare document instead of the JSON response envelope
+fn accepted_document_format(http: &HttpRequest) -> Option<OutputFormat> {
+    let accept = http.headers().get(header::ACCEPT)?.to_str().ok()?;
+    accept.split(',').find_map(OutputFormat::from_media_type)
+}
+
+async fn generate_aibom(
+    hub: web::Data<HubClient>,
+    http: HttpRequest,
+    req: web::Json<GenerateRequest>,
+) -> Result<HttpResponse> {
     let model_id = req.model_id.clone();
     let verbose = req.verbose;
+    let format = req.format;
+    let spec_version = req.spec_version;
 
     // Validate model_id format
     if model_id.is_empty() {
-        return Ok(HttpResponse::BadRequest().json(GenerateResponse {
-            success: false,
-            aibom: None,
-            error: Some("model_id cannot be empty".to_string()),
-        }));
+        return Ok(HttpResponse::BadRequest().json(GenerateResponse::failure(
+            "model_id cannot be empty".to_string(),
+            None,
+        )));
     }
 
-    println!(
+    info!(
         "📥 Received AIBOM generation request: model_id={}, verbose={}",
         model_id, verbose
     );
 
     if verbose {
-        println!("🚀 Generating AIBOM for model '{}'...", model_id);
+        info!("🚀 Generating AIBOM for model '{}'...", model_id);
     }
 
-    // Clone model_id for closure
-    let model_id_for_closure = model_id.clone();
-
     // Use web::block to run synchronous code in thread pool
-    let result = web::block(move || -> Result<lib::AIBOM, String> {
-        let mut generator = AIBOMGenerator::new().map_err(|e| e.to_string())?;
-        generator
-            .generate_aibom(&model_id_for_closure)
-            .map_err(|e| e.to_string())
-    })
-    .await;
+    let result = run_generation(hub.get_ref().clone(), model_id.clone(), None).await;
 
-    match result {
-        Ok(Ok(aibom)) => {
-            let aibom_json = serde_json::to_value(&aibom).unwrap();
+    // Content negotiation: answer with the document itself; errors stay JSON
+    let result = match (accepted_document_format(&http), result) {
+        (Some(format), Ok(Ok((aibom, mut report)))) => {
+            let document = apply_spec_version(aibom, &mut report, format, spec_version)
+                .and_then(|aibom| lib::formats::render(&aibom, format, true));
+            return match document {
+                Ok(document) => {
+                    info!("✅ AIBOM generation successful: {}", model_id);
+                    Ok(HttpResponse::Ok()
+                        .content_type(format.media_type())
+                        .body(document))
+                }
+                Err(e) => Ok(HttpResponse::InternalServerError().json(serialization_failure(e))),
+            };
+        }
+        (_, result) => result,
+    };
 
-            if verbose {
-                println!("✅ AIBOM generation successful");
-                println!("📊 Component count: {}", aibom.components.len());
-                println!("🔗 Dependencies: {}", aibom.dependencies.len());
-            } else {
-                println!("✅ AIBOM generation successful: {}", model_id);
-            }
+    let (status, response) = into_response(result, &model_id, verbose, format, spec_version);
+    Ok(HttpResponse::build(status).json(response))
+}
+
+/// Final line of a streamed generation, carrying the usual response body
+#[derive(Serialize)]
+struct StreamResult {
+    event: &'static str,
+    status: u16,
+    #[serde(flatten)]
+    response: GenerateResponse,
+}
 
-            Ok(HttpResponse::Ok().json(GenerateResponse {
-                success: true,
-                aibom: Some(aibom_json),
-                error: None,
-            }))
+/// Stream progress events as newline-delimited JSON, ending with a `result` line
+async fn generate_aibom_stream(
+    hub: web::Data<HubClient>,
+    req: web::Json<GenerateRequest>,
+) -> Result<HttpResponse> {
+    let model_id = req.model_id.clone();
+    let verbose = req.verbose;
+    let format = req.format;
+    let spec_version = req.spec_version;
+
+    if model_id.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(GenerateResponse::failure(
+            "model_id cannot be empty".to_string(),
+            None,
+        )));
+    }
+
+    info!(
+        "📥 Received streaming AIBOM generation request: model_id={}",
+        model_id
+    );
+
+    let (tx, rx) = mpsc::unbounded_channel::<String>();
+    let events = tx.clone();
+    let observer: Arc<dyn ProgressObserver> = Arc::new(move |event: &ProgressEvent| {
+        if let Ok(line) = serde_json::to_string(event) {
+            // The client may have gone away; generation finishes regardless
+            let _ = events.send(line);
         }
-        Ok(Err(e)) => {
-            eprintln!("❌ AIBOM generation failed: {}", e);
-            Ok(HttpResponse::InternalServerError().json(GenerateResponse {
-                success: false,
-                aibom: None,
-                error: Some(format!("Error generating AIBOM: {}", e)),
-            }))
+    });
+
+    let hub = hub.get_ref().clone();
+    actix_web::rt::spawn(async move {
+        let result = run_generation(hub, model_id.clone(), Some(observer)).await;
+        let (status, response) = into_response(result, &model_id, verbose, format, spec_version);
+        let result = StreamResult {
+            event: "result",
+            status: status.as_u16(),
+            response,
+        };
+        if let Ok(line) = serde_json::to_string(&result) {
+            let _ = tx.send(line);
         }
-        Err(e) => {
-            eprintln!("❌ Thread pool execution failed: {}", e);
-            Ok(HttpResponse::InternalServerError().json(GenerateResponse {
-                success: false,
-                aibom: None,
-                error: Some(format!("Error executing task: {}", e)),
-            }))
+    });
+
+    let body = stream::unfold(rx, |mut rx| async move {
+        let line = rx.recv().await?;
+        Some((Ok::<_, actix_web::Error>(web::Bytes::from(line + "\n")), rx))
+    });
+    Ok(HttpResponse::Ok()
+        .content_type("application/x-ndjson")
+        .streaming(body))
+}
+
+#[derive(Deserialize)]
+struct ValidateQuery {
+    /// Validate against this version instead of the document's `specVersion`
+    spec_version: Option<SpecVersion>,
+}
+
+#[derive(Serialize)]
+struct ValidateResponse {
+    valid: bool,
+    errors: Vec<lib::SchemaError>,
+}
+
+/// Validate a CycloneDX JSON document against the bundled AIBOM subset schemas
+async fn validate_bom(
+    query: web::Query<ValidateQuery>,
+    document: web::Json<serde_json::Value>,
+) -> Result<HttpResponse> {
+    let errors = match query.spec_version {
+        Some(version) => lib::validate_document_as(&document, version),
+        None => lib::validate_document(&document),
+    };
+    info!("📥 Validated BOM: {} schema violation(s)", errors.len());
+    Ok(HttpResponse::Ok().json(ValidateResponse {
+        valid: errors.is_empty(),
+        errors,
+    }))
+}
+
+#[derive(Deserialize)]
+struct LintQuery {
+    /// Comma-separated rule names to skip
+    #[serde(default)]
+    disable: String,
+}
+
+#[derive(Serialize)]
+struct LintResponse {
+    errors: usize,
+    warnings: usize,
+    infos: usize,
+    #[serde(flatten)]
+    report: lib::LintReport,
+}
+
+/// Run the lint rules over a posted AIBOM
+async fn lint_bom(
+    query: web::Query<LintQuery>,
+    aibom: web::Json<lib::AIBOM>,
+) -> Result<HttpResponse> {
+    let mut linter = lib::Linter::new();
+    for rule in query.disable.split(',').filter(|r| !r.is_empty()) {
+        if let Err(e) = linter.disable(rule) {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({ "error": e })));
         }
     }
+    let report = linter.lint(&aibom);
+    info!("📥 Linted BOM: {} finding(s)", report.findings.len());
+    Ok(HttpResponse::Ok().json(LintResponse {
+        errors: report.count(lib::Severity::Error),
+        warnings: report.count(lib::Severity::Warning),
+        infos: report.count(lib::Severity::Info),
+        report,
+    }))
 }
 
 async fn health_check() -> Result<HttpResponse> {
@@ -96,15 +354,24 @@ async fn health_check() -> Result<HttpResponse> {
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    env_logger::init();
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    info!("Starting AIBOM Generator Server on http://localhost:8080");
 
-    println!("Starting AIBOM Generator Server on http://localhost:8080");
+    // One Hub client for all requests, so repeated lookups hit its response cache
+    let hub = HubClient::new(&HubConfig::default()).map_err(std::io::Error::other)?;
 
-    HttpServer::new(|| {
+    HttpServer::new(move || {
         App::new()
+            .app_data(web::Data::new(hub.clone()))
+            // BOMs posted to /validate easily exceed the default 32 KiB limit
+            .app_data(web::JsonConfig::default().limit(MAX_JSON_BODY))
             .wrap(Logger::default())
             .route("/health", web::get().to(health_check))
             .route("/generate", web::post().to(generate_aibom))
+            .route("/generate/stream", web::post().to(generate_aibom_stream))
+            .route("/validate", web::post().to(validate_bom))
+            .route("/lint", web::post().to(lint_bom))
     })
     .bind("127.0.0.1:8080")?
     .run()